@@ -0,0 +1,155 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// A single in-flight background operation (upload, publish, relay connect...)
+#[derive(Debug, Clone)]
+pub struct Activity {
+    pub id: u64,
+    pub label: String,
+    pub started_at: Instant,
+    /// Writes to the network that shouldn't be cut off on exit (publishes, uploads)
+    pub critical: bool,
+}
+
+/// Tracks concurrent background work so the UI can show what is in flight.
+///
+/// The tracker is cheap to clone and can be moved into spawned tasks; each
+/// operation holds an `ActivityGuard` that unregisters it when dropped.
+#[derive(Debug, Clone, Default)]
+pub struct ActivityTracker {
+    activities: Arc<Mutex<Vec<Activity>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl ActivityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new operation. It stays active until the guard is dropped.
+    pub fn begin(&self, label: impl Into<String>) -> ActivityGuard {
+        self.register(label.into(), false)
+    }
+
+    /// Like `begin`, but shutdown waits for the operation to finish
+    pub fn begin_critical(&self, label: impl Into<String>) -> ActivityGuard {
+        self.register(label.into(), true)
+    }
+
+    fn register(&self, label: String, critical: bool) -> ActivityGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let activity = Activity {
+            id,
+            label,
+            started_at: Instant::now(),
+            critical,
+        };
+
+        tracing::debug!("Activity started: {}", activity.label);
+        if let Ok(mut activities) = self.activities.lock() {
+            activities.push(activity);
+        }
+
+        ActivityGuard {
+            tracker: self.clone(),
+            id,
+        }
+    }
+
+    /// Number of operations currently in flight
+    pub fn count(&self) -> usize {
+        self.activities.lock().map(|a| a.len()).unwrap_or(0)
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.count() > 0
+    }
+
+    /// Whether any operation that must not be interrupted is still running
+    pub fn has_critical(&self) -> bool {
+        self.activities
+            .lock()
+            .map(|a| a.iter().any(|activity| activity.critical))
+            .unwrap_or(false)
+    }
+
+    /// Get a copy of the currently running operations, oldest first
+    pub fn snapshot(&self) -> Vec<Activity> {
+        self.activities
+            .lock()
+            .map(|a| a.clone())
+            .unwrap_or_default()
+    }
+
+    fn finish(&self, id: u64) {
+        if let Ok(mut activities) = self.activities.lock() {
+            if let Some(index) = activities.iter().position(|a| a.id == id) {
+                let activity = activities.remove(index);
+                tracing::debug!(
+                    "Activity finished: {} ({:.1}s)",
+                    activity.label,
+                    activity.started_at.elapsed().as_secs_f32()
+                );
+            }
+        }
+    }
+}
+
+/// Keeps an activity registered for as long as it is alive
+#[derive(Debug)]
+pub struct ActivityGuard {
+    tracker: ActivityTracker,
+    id: u64,
+}
+
+impl Drop for ActivityGuard {
+    fn drop(&mut self) {
+        self.tracker.finish(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_tracks_activity_lifetime() {
+        let tracker = ActivityTracker::new();
+        assert!(!tracker.is_busy());
+
+        let upload = tracker.begin("Uploading image");
+        let publish = tracker.begin("Publishing post");
+        assert_eq!(tracker.count(), 2);
+        assert_eq!(tracker.snapshot()[0].label, "Uploading image");
+
+        drop(upload);
+        assert_eq!(tracker.count(), 1);
+        assert_eq!(tracker.snapshot()[0].label, "Publishing post");
+
+        drop(publish);
+        assert!(!tracker.is_busy());
+    }
+
+    #[test]
+    fn test_critical_activities() {
+        let tracker = ActivityTracker::new();
+        let _connect = tracker.begin("Connecting to relays");
+        assert!(!tracker.has_critical());
+
+        let publish = tracker.begin_critical("Publishing post");
+        assert!(tracker.has_critical());
+
+        drop(publish);
+        assert!(!tracker.has_critical());
+    }
+
+    #[test]
+    fn test_clones_share_state() {
+        let tracker = ActivityTracker::new();
+        let clone = tracker.clone();
+
+        let _guard = clone.begin("Connecting to relays");
+        assert_eq!(tracker.count(), 1);
+    }
+}
@@ -1,14 +1,55 @@
+use crate::activity::ActivityTracker;
+use crate::app_settings::AppSettings;
 use crate::blossom_client::{BlossomClient, BlossomSettings};
-use crate::components::{CredentialsDialog, EditorAction, MarkdownEditor, PublishDialog, RelayDialog, SettingsDialog, Sidebar, SidebarAction};
-use crate::nostr_client::NostrClient;
-use crate::post::BlogPost;
+use crate::components::{BlossomDialog, BlossomDialogServices, BlossomUploadsDialog, CommandPalette, CredentialsDialog, EditorAction, EventLookupDialog, ImportReportDialog, MarkdownEditor, NaddrDialog, OnboardingAction, OnboardingDialog, PaletteCommand, PassphraseDialog, PassphrasePurpose, PreferencesDialog, ProfileBroadcastDialog, PublishDialog, QrDialog, RecoverDialog, RelayDialog, RelayTestDialog, SearchDialog, SettingsDialog, ShortcutsDialog, Sidebar, SidebarAction, TrashDialog, WritingStatsDialog};
+use crate::credentials_file::CredentialsLocked;
+use crate::fonts::apply_fonts;
+use crate::html_export::HtmlExportOptions;
+use crate::nostr_client::{is_same_article, NostrClient, PublishReport};
+use crate::post::{BlogPost, PostStatus};
 use crate::relay_settings::RelaySettings;
 use crate::storage::Storage;
-use crate::theme::{Theme, CustomThemeColors};
+use crate::theme::{apply_window_opacity, system_prefers_dark, Theme, CustomThemeColors};
+use crate::writing_stats::WritingStats;
 use egui::{CentralPanel, RichText, SidePanel, TopBottomPanel};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// How many posts the recent posts list remembers
+const MAX_RECENT_POSTS: usize = 10;
+
+/// How long closing the app waits for publishes and uploads to finish
+const SHUTDOWN_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// How many times a scheduled post is tried before it is marked failed
+const SCHEDULED_PUBLISH_ATTEMPTS: u32 = 5;
+
+/// Wait before retrying a scheduled post; doubles after every failed attempt
+const SCHEDULED_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How often the OS light/dark preference is checked while following it
+const SYSTEM_THEME_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Where an uploaded image goes in the post
+#[derive(Debug, Clone, Copy)]
+enum ImageUploadTarget {
+    /// Appended to the content as Markdown
+    Inline,
+    Featured,
+}
+
+/// An image upload running in the background
+struct ImageUpload {
+    post_id: uuid::Uuid,
+    target: ImageUploadTarget,
+    path: std::path::PathBuf,
+    sent: u64,
+    total: u64,
+    progress: std::sync::mpsc::Receiver<(u64, u64)>,
+    receiver: std::sync::mpsc::Receiver<Result<crate::blossom_client::BlossomUpload, String>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
 pub struct BlogsterApp {
     // Core components
     storage: Storage,
@@ -22,17 +63,76 @@ pub struct BlogsterApp {
     publish_dialog: PublishDialog,
     settings_dialog: SettingsDialog,
     relay_dialog: RelayDialog,
+    blossom_dialog: BlossomDialog,
+    preferences_dialog: PreferencesDialog,
+    recover_dialog: RecoverDialog,
+    event_lookup_dialog: EventLookupDialog,
+    relay_test_dialog: RelayTestDialog,
+    onboarding_dialog: OnboardingDialog,
+    passphrase_dialog: PassphraseDialog,
+    search_dialog: SearchDialog,
+    qr_dialog: QrDialog,
+    profile_broadcast_dialog: ProfileBroadcastDialog,
+    blossom_uploads_dialog: BlossomUploadsDialog,
+    writing_stats_dialog: WritingStatsDialog,
+    naddr_dialog: NaddrDialog,
+    import_report_dialog: ImportReportDialog,
+    trash_dialog: TrashDialog,
+    shortcuts_dialog: ShortcutsDialog,
+    command_palette: CommandPalette,
     
     // State
     posts: Vec<BlogPost>,
+    recent_posts: Vec<uuid::Uuid>,
+    writing_stats: WritingStats,
     current_theme: Theme,
+    /// Whether the OS was in dark mode when last checked, for `Theme::System`
+    system_dark: bool,
+    system_theme_check: Option<std::sync::mpsc::Receiver<bool>>,
+    last_system_theme_check: std::time::Instant,
     custom_colors: CustomThemeColors,
     relay_settings: RelaySettings,
+    app_settings: AppSettings,
+    /// Labels of the saved Nostr accounts
+    accounts: Vec<String>,
+    /// The account currently signed in
+    active_account: String,
     error_message: Option<String>,
     success_message: Option<String>,
-    is_loading: bool,
-    show_settings: bool,
+    /// The status bar message being shown and when it first appeared
+    message_shown: Option<(String, std::time::Instant)>,
+    activity: ActivityTracker,
+    show_activity_list: bool,
+    /// Always-on-top and opacity need to be (re)applied on the next frame
+    window_options_changed: bool,
     blossom_settings: BlossomSettings,
+    /// BUD-03 server list being fetched at startup
+    blossom_server_fetch: Option<std::sync::mpsc::Receiver<Result<Option<Vec<String>>, String>>>,
+    /// NIP-09 deletion requests in flight: the post title and the outcome
+    relay_deletions: Vec<(String, std::sync::mpsc::Receiver<Result<usize, String>>)>,
+    /// NIP-37 draft being synced: the post title and how many relays took it
+    draft_sync: Option<(String, std::sync::mpsc::Receiver<Result<usize, String>>)>,
+    draft_pull: Option<std::sync::mpsc::Receiver<Result<Vec<BlogPost>, String>>>,
+    /// Account being signed in to, until the client has taken its credentials
+    account_switch: Option<(String, std::sync::mpsc::Receiver<Result<(), String>>)>,
+    /// Scheduled post being published: the copy that was sent, and the report with the npub it was signed with
+    scheduled_publish: Option<(BlogPost, std::sync::mpsc::Receiver<Result<(PublishReport, Option<String>), String>>)>,
+    /// Failed attempts at scheduled posts and when each may be tried again
+    scheduled_retries: std::collections::HashMap<uuid::Uuid, (u32, std::time::Instant)>,
+    image_upload: Option<ImageUpload>,
+    
+    /// A fetched article with the same id as a local post, until the user
+    /// chooses whether it replaces that post
+    local_copy_conflict: Option<BlogPost>,
+    
+    // Crash recovery
+    pending_recoveries: Vec<BlogPost>,
+    last_recovery_snapshot: std::time::Instant,
+    /// How often unsaved edits are written to the crash-recovery buffer
+    recovery_interval: std::time::Duration,
+    last_snapshot_version: Option<(uuid::Uuid, chrono::DateTime<chrono::Utc>)>,
+    /// When the open post was last edited, until autosave picks it up
+    last_edit: Option<std::time::Instant>,
     
     // Runtime
     runtime: tokio::runtime::Runtime,
@@ -41,7 +141,7 @@ pub struct BlogsterApp {
 impl BlogsterApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         // Initialize storage
-        let storage = Storage::new().expect("Failed to initialize storage");
+        let mut storage = Storage::new().expect("Failed to initialize storage");
         
         // Load theme preference
         let current_theme = storage.load_theme().unwrap_or_else(|e| {
@@ -61,8 +161,20 @@ impl BlogsterApp {
             RelaySettings::default()
         });
         
+        // Load general app settings
+        let app_settings = storage.load_app_settings().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load app settings: {}", e);
+            AppSettings::default()
+        });
+        storage.set_recovery_dir(app_settings.recovery_dir.clone());
+        let recovery_interval = std::time::Duration::from_secs(app_settings.recovery_interval_secs);
+        
+        // Apply fonts
+        let font_errors = apply_fonts(&cc.egui_ctx, &app_settings.ui_font, &app_settings.editor_font, app_settings.font_size);
+        
         // Apply theme
-        current_theme.apply(&cc.egui_ctx);
+        let system_dark = current_theme != Theme::System || system_prefers_dark();
+        current_theme.resolve(system_dark).apply_with(&cc.egui_ctx, &custom_colors, system_dark);
         
         // Migrate posts from old location if needed
         if let Err(e) = storage.migrate_posts_if_needed() {
@@ -75,6 +187,35 @@ impl BlogsterApp {
             Vec::new()
         });
 
+        // Load recently used posts, forgetting any that no longer exist
+        let mut recent_posts = storage.load_recent_posts().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load recent posts: {}", e);
+            Vec::new()
+        });
+        recent_posts.retain(|id| posts.iter().any(|p| p.id == *id));
+
+        // Load writing history; posts it hasn't seen yet start from their current length
+        let mut writing_stats = storage.load_writing_stats().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load writing stats: {}", e);
+            WritingStats::default()
+        });
+        // Locked private posts are seeded once their content is decrypted
+        writing_stats.seed(posts.iter().filter(|p| !p.is_locked()).map(|p| (p.id, p.word_count())));
+
+        // Find unsaved edits left behind by a crash
+        let mut pending_recoveries = Vec::new();
+        for snapshot in storage.load_recovery_snapshots().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load recovery snapshots: {}", e);
+            Vec::new()
+        }) {
+            let saved = posts.iter().find(|p| p.id == snapshot.id);
+            if saved.map_or(true, |saved| snapshot.updated_at > saved.updated_at) {
+                pending_recoveries.push(snapshot);
+            } else if let Err(e) = storage.clear_recovery_snapshot(snapshot.id) {
+                tracing::warn!("Failed to remove stale recovery snapshot: {}", e);
+            }
+        }
+
         // Load Blossom settings
         let blossom_settings = storage.load_blossom_settings().unwrap_or_else(|e| {
             tracing::error!("Failed to load Blossom settings: {}", e);
@@ -82,54 +223,642 @@ impl BlogsterApp {
         });
 
         // Initialize Nostr client
-        let nostr_client = Arc::new(Mutex::new(NostrClient::new()));
+        let mut nostr_client = NostrClient::new();
+        nostr_client.set_include_client_tag(app_settings.include_client_tag);
+        nostr_client.set_publish_timeout(std::time::Duration::from_secs(app_settings.publish_timeout_secs));
+        let nostr_client = Arc::new(Mutex::new(nostr_client));
 
         // Initialize Blossom client and set Nostr client
         let mut blossom_client = BlossomClient::new(blossom_settings.clone());
         blossom_client.set_nostr_client(nostr_client.clone());
+        blossom_client.set_strip_metadata(app_settings.strip_image_metadata);
         
         // Create runtime for async operations
         let runtime = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
         
+        let mut editor = MarkdownEditor::new();
+        editor.set_max_line_length(app_settings.max_line_length);
+        editor.set_tag_colors(app_settings.tag_colors.clone());
+        editor.set_preview_width(app_settings.preview_width);
+        editor.set_date_format(app_settings.date_format.clone());
+        
+        let mut sidebar = Sidebar::new();
+        sidebar.set_tag_colors(app_settings.tag_colors.clone());
+        sidebar.set_sort(app_settings.post_sort);
+        
+        let mut publish_dialog = PublishDialog::new();
+        publish_dialog.set_confirm_before_publish(app_settings.confirm_before_publish);
+        
+        let accounts = storage.list_accounts();
+        let active_account = storage.active_account();
+        
         // Try to load credentials
         let mut app = Self {
             storage,
             nostr_client,
             blossom_client,
-            sidebar: Sidebar::new(),
-            editor: MarkdownEditor::new(),
+            sidebar,
+            editor,
             credentials_dialog: CredentialsDialog::new(),
-            publish_dialog: PublishDialog::new(),
+            publish_dialog,
             settings_dialog: SettingsDialog::new(),
             relay_dialog: RelayDialog::new(),
+            blossom_dialog: BlossomDialog::new(),
+            preferences_dialog: PreferencesDialog::new(),
+            recover_dialog: RecoverDialog::new(),
+            event_lookup_dialog: EventLookupDialog::new(),
+            relay_test_dialog: RelayTestDialog::new(),
+            onboarding_dialog: OnboardingDialog::new(),
+            passphrase_dialog: PassphraseDialog::new(),
+            search_dialog: SearchDialog::new(),
+            qr_dialog: QrDialog::new(),
+            profile_broadcast_dialog: ProfileBroadcastDialog::new(),
+            blossom_uploads_dialog: BlossomUploadsDialog::new(),
+            writing_stats_dialog: WritingStatsDialog::new(),
+            naddr_dialog: NaddrDialog::new(),
+            import_report_dialog: ImportReportDialog::new(),
+            trash_dialog: TrashDialog::new(),
+            shortcuts_dialog: ShortcutsDialog::new(),
+            command_palette: CommandPalette::new(),
             posts,
+            recent_posts,
+            writing_stats,
             current_theme,
+            system_dark,
+            system_theme_check: None,
+            last_system_theme_check: std::time::Instant::now(),
             custom_colors,
             relay_settings,
+            app_settings,
+            accounts,
+            active_account,
             error_message: None,
             success_message: None,
-            is_loading: false,
-            show_settings: false,
+            message_shown: None,
+            activity: ActivityTracker::new(),
+            show_activity_list: false,
+            window_options_changed: true,
             blossom_settings,
+            blossom_server_fetch: None,
+            relay_deletions: Vec::new(),
+            draft_sync: None,
+            account_switch: None,
+            draft_pull: None,
+            scheduled_publish: None,
+            scheduled_retries: std::collections::HashMap::new(),
+            image_upload: None,
+            local_copy_conflict: None,
+            pending_recoveries,
+            last_recovery_snapshot: std::time::Instant::now(),
+            recovery_interval,
+            last_snapshot_version: None,
             runtime,
         };
         
         // Load credentials if available
-        if let Ok(Some(credentials)) = app.storage.load_credentials() {
-            app.runtime.block_on(async {
-                if let Ok(mut client) = app.nostr_client.try_lock() {
-                    if let Err(e) = client.set_credentials(credentials) {
-                        app.error_message = Some(format!("Failed to load credentials: {}", e));
+        let saved_credentials = app.storage.load_credentials(&app.active_account);
+        if let Err(e) = &saved_credentials {
+            if e.is::<CredentialsLocked>() {
+                // Ask for the passphrase of the encrypted credentials file
+                app.credentials_dialog.open_with_storage(&app.storage);
+            }
+        }
+        if let Ok(Some(credentials)) = saved_credentials {
+            if credentials.bunker_uri.is_some() {
+                // Reaching a remote signer can take a while; don't hold up startup
+                let client = app.nostr_client.clone();
+                let activity_guard = app.activity.begin("Connecting to remote signer");
+                app.runtime.spawn(async move {
+                    let _activity_guard = activity_guard;
+                    match NostrClient::connect_signer(credentials).await {
+                        Ok(signer) => client.lock().await.use_signer(signer),
+                        Err(e) => tracing::error!("Failed to connect to the remote signer: {:#}", e),
                     }
-                }
-            });
+                });
+            } else {
+                app.runtime.block_on(async {
+                    if let Ok(mut client) = app.nostr_client.try_lock() {
+                        if let Err(e) = client.set_credentials(credentials).await {
+                            app.error_message = Some(format!("Failed to load credentials: {}", e));
+                        }
+                    }
+                });
+            }
+        }
+        
+        // On a device without Blossom settings, pick up the server list
+        // published from another device
+        if !app.storage.has_blossom_settings() {
+            let has_credentials = app.nostr_client
+                .try_lock()
+                .map(|client| client.has_credentials())
+                .unwrap_or(false);
+            if has_credentials {
+                app.fetch_blossom_servers();
+            }
+        }
+        
+        if !font_errors.is_empty() {
+            app.error_message = Some(format!("Failed to load font: {}", font_errors.join("; ")));
+        }
+        
+        // Walk brand-new users through setup
+        if !app.storage.is_onboarding_complete() {
+            app.onboarding_dialog.open();
         }
         
         app
     }
 
+    /// Fetch the BUD-03 server list in the background
+    fn fetch_blossom_servers(&mut self) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let client = self.nostr_client.clone();
+        let relay_settings = self.relay_settings.clone();
+        let activity_guard = self.activity.begin("Fetching Blossom server list");
+        self.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = client.lock().await.fetch_blossom_servers(&relay_settings).await.map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        self.blossom_server_fetch = Some(receiver);
+    }
+
+    /// Apply the server list fetched at startup
+    fn poll_blossom_server_sync(&mut self, ctx: &egui::Context) {
+        if let Some(receiver) = &self.blossom_server_fetch {
+            match receiver.try_recv() {
+                Ok(Ok(Some(servers))) if !crate::blossom_client::normalize_server_list(servers.clone()).is_empty() => {
+                    self.blossom_server_fetch = None;
+                    self.blossom_settings.set_servers(servers);
+                    self.blossom_client.update_settings(self.blossom_settings.clone());
+                    if let Err(e) = self.storage.save_blossom_settings(&self.blossom_settings) {
+                        self.error_message = Some(format!("Failed to save Blossom settings: {}", e));
+                    } else {
+                        self.success_message = Some(format!("Loaded {} Blossom server(s) from Nostr", self.blossom_settings.servers().len()));
+                    }
+                }
+                Ok(Ok(_)) => {
+                    self.blossom_server_fetch = None;
+                }
+                Ok(Err(e)) => {
+                    self.blossom_server_fetch = None;
+                    tracing::warn!("Failed to fetch Blossom server list: {}", e);
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.blossom_server_fetch = None;
+                }
+            }
+        }
+    }
+
+    /// Ask the relays a post was published to to delete it, in the background
+    fn delete_from_relays(&mut self, post: BlogPost) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.relay_deletions.push((post.title.clone(), receiver));
+        
+        let client = self.nostr_client.clone();
+        let relay_settings = self.relay_settings.clone();
+        let activity_guard = self.activity.begin(format!("Deleting \"{}\" from relays", post.title));
+        
+        self.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = {
+                let client = client.lock().await;
+                client.delete_article(&post, &relay_settings).await
+                    .map(|report| report.successful_relays.len())
+                    .map_err(|e| format!("{:#}", e))
+            };
+            let _ = sender.send(result);
+        });
+    }
+    
+    fn poll_relay_deletions(&mut self, ctx: &egui::Context) {
+        let mut finished = Vec::new();
+        for (index, (title, receiver)) in self.relay_deletions.iter().enumerate() {
+            match receiver.try_recv() {
+                Ok(Ok(relay_count)) => {
+                    finished.push(index);
+                    self.success_message = Some(format!("Deletion of \"{}\" requested on {} relay(s)", title, relay_count));
+                }
+                Ok(Err(e)) => {
+                    finished.push(index);
+                    self.error_message = Some(format!("Failed to delete \"{}\" from relays: {}", title, e));
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    finished.push(index);
+                }
+            }
+        }
+        for index in finished.into_iter().rev() {
+            self.relay_deletions.remove(index);
+        }
+    }
+    
+    /// Publish the first scheduled post whose time has come, in the background,
+    /// through the same path as the publish dialog
+    fn publish_scheduled_posts(&mut self, ctx: &egui::Context) {
+        if self.scheduled_publish.is_some() {
+            self.poll_scheduled_publish(ctx);
+            return;
+        }
+        
+        let now = chrono::Utc::now();
+        let instant_now = std::time::Instant::now();
+        let retry_at = |id: &uuid::Uuid| self.scheduled_retries.get(id).map(|(_, retry_at)| *retry_at);
+        // Wake up in time for the next scheduled post or retry
+        let next_wait = self.posts.iter()
+            .filter_map(|p| {
+                let scheduled_at = p.scheduled_at?;
+                let wait = match retry_at(&p.id) {
+                    Some(retry_at) => retry_at.saturating_duration_since(instant_now),
+                    None => (scheduled_at - now).to_std().unwrap_or_default(),
+                };
+                (!wait.is_zero()).then_some(wait)
+            })
+            .min();
+        if let Some(wait) = next_wait {
+            ctx.request_repaint_after(wait.min(std::time::Duration::from_secs(60)));
+        }
+        
+        let Some(id) = self.posts.iter()
+            .find(|p| p.is_due(now) && !p.is_locked() && !retry_at(&p.id).is_some_and(|retry_at| retry_at > instant_now))
+            .map(|p| p.id)
+        else {
+            return;
+        };
+        // Wait until signed in
+        let npub = match self.nostr_client.try_lock() {
+            Ok(client) if client.has_credentials() => client.npub(),
+            _ => return,
+        };
+        
+        // Publish what's in the editor if the post is open there
+        let Some(post) = self.editor.get_post()
+            .filter(|p| p.id == id && !self.editor.is_read_only())
+            .or_else(|| self.posts.iter().find(|p| p.id == id))
+            .cloned()
+        else {
+            return;
+        };
+        
+        // Updating an article from another account would publish a second copy under this one
+        if let Some(published_by) = post.published_by.as_ref().filter(|published_by| npub.as_ref() != Some(*published_by)) {
+            self.error_message = Some(format!(
+                "Scheduled post \"{}\" was published with {}. Switch to that account and publish it again.",
+                post.title, published_by
+            ));
+            self.unschedule_post(id);
+            return;
+        }
+        
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.scheduled_publish = Some((post.clone(), receiver));
+        
+        let client = self.nostr_client.clone();
+        let relay_settings = self.relay_settings.clone();
+        let activity_guard = self.activity.begin_critical(format!("Publishing scheduled post \"{}\"", post.title));
+        
+        self.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = {
+                let client = client.lock().await;
+                client.publish_long_form_post(&post, &relay_settings).await
+                    .map(|report| (report, client.npub()))
+                    .map_err(|e| format!("{:#}", e))
+            };
+            match &result {
+                Ok((report, _)) => tracing::info!("Published scheduled post \"{}\" to {} relays", post.title, report.successful_relays.len()),
+                Err(e) => tracing::error!("Failed to publish scheduled post \"{}\": {}", post.title, e),
+            }
+            let _ = sender.send(result);
+        });
+    }
+    
+    /// Drop a post's schedule, on disk and in the editor
+    fn unschedule_post(&mut self, id: uuid::Uuid) {
+        self.scheduled_retries.remove(&id);
+        if let Some(post) = self.posts.iter_mut().find(|p| p.id == id) {
+            post.scheduled_at = None;
+            if let Err(e) = self.storage.save_post(post) {
+                tracing::warn!("Failed to save scheduled post: {}", e);
+            }
+        }
+        if let Some(open_post) = self.editor.get_post_mut().filter(|p| p.id == id) {
+            open_post.scheduled_at = None;
+        }
+    }
+    
+    /// Record the outcome of a scheduled publish on the post and save it
+    fn poll_scheduled_publish(&mut self, ctx: &egui::Context) {
+        let Some((_, receiver)) = &self.scheduled_publish else {
+            return;
+        };
+        
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                return;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => Err("Publishing stopped unexpectedly".to_string()),
+        };
+        let Some((mut post, _)) = self.scheduled_publish.take() else {
+            return;
+        };
+        let id = post.id;
+        
+        match result {
+            Ok((report, npub)) => {
+                self.scheduled_retries.remove(&id);
+                // Save the content that was actually published
+                post.set_published(report.event_id.to_hex(), report.successful_relays, report.published_at);
+                post.published_by = npub;
+                self.success_message = Some(format!(
+                    "Scheduled post \"{}\" published to {} relay(s)",
+                    post.title,
+                    post.published_relays.len()
+                ));
+                match self.storage.save_post(&post) {
+                    Ok(path) => post.file_path = Some(path),
+                    Err(e) => tracing::warn!("Failed to save scheduled post: {}", e),
+                }
+                if let Some(existing_post) = self.posts.iter_mut().find(|p| p.id == id) {
+                    *existing_post = post.clone();
+                }
+                
+                // Keep the open copy in sync so saving it doesn't undo the change
+                if let Some(open_post) = self.editor.get_post_mut().filter(|p| p.id == id) {
+                    open_post.status = post.status;
+                    open_post.nostr_event_id = post.nostr_event_id;
+                    open_post.published_relays = post.published_relays;
+                    open_post.published_at = post.published_at;
+                    open_post.published_by = post.published_by;
+                    open_post.file_path = post.file_path;
+                    open_post.scheduled_at = None;
+                }
+            }
+            Err(e) => {
+                // Relays are often only briefly unreachable, so keep the schedule and try again later
+                let attempts = self.scheduled_retries.get(&id).map_or(0, |(attempts, _)| *attempts) + 1;
+                if attempts < SCHEDULED_PUBLISH_ATTEMPTS {
+                    let delay = SCHEDULED_RETRY_DELAY * 2u32.pow(attempts - 1);
+                    self.scheduled_retries.insert(id, (attempts, std::time::Instant::now() + delay));
+                    self.error_message = Some(format!(
+                        "Failed to publish scheduled post \"{}\", retrying in {} min: {}",
+                        post.title,
+                        delay.as_secs() / 60,
+                        e
+                    ));
+                    return;
+                }
+                
+                self.scheduled_retries.remove(&id);
+                self.error_message = Some(format!(
+                    "Failed to publish scheduled post \"{}\" after {} attempts: {}",
+                    post.title, attempts, e
+                ));
+                if let Some(existing_post) = self.posts.iter_mut().find(|p| p.id == id) {
+                    existing_post.set_failed();
+                    if let Err(e) = self.storage.save_post(existing_post) {
+                        tracing::warn!("Failed to save scheduled post: {}", e);
+                    }
+                }
+                if let Some(open_post) = self.editor.get_post_mut().filter(|p| p.id == id) {
+                    open_post.status = PostStatus::Failed;
+                    open_post.scheduled_at = None;
+                }
+            }
+        }
+    }
+    
+    /// Save a post to relays as an encrypted NIP-37 draft, in the background
+    fn sync_draft(&mut self, post: BlogPost) {
+        if self.draft_sync.is_some() {
+            self.error_message = Some("A draft is already being synced".to_string());
+            return;
+        }
+        if post.private {
+            self.error_message = Some("Private posts aren't synced to relays".to_string());
+            return;
+        }
+        
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.draft_sync = Some((post.title.clone(), receiver));
+        
+        let client = self.nostr_client.clone();
+        let relay_settings = self.relay_settings.clone();
+        let activity_guard = self.activity.begin(format!("Syncing draft \"{}\"", post.title));
+        
+        self.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = client.lock().await.publish_draft(&post, &relay_settings).await
+                .map(|report| report.successful_relays.len())
+                .map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+    }
+    
+    /// Fetch this identity's NIP-37 drafts in the background
+    fn pull_drafts(&mut self) {
+        if self.draft_pull.is_some() {
+            return;
+        }
+        
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let client = self.nostr_client.clone();
+        let relay_settings = self.relay_settings.clone();
+        let activity_guard = self.activity.begin("Pulling drafts from relays");
+        self.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = client.lock().await.fetch_drafts(&relay_settings).await.map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        self.draft_pull = Some(receiver);
+    }
+    
+    fn poll_draft_sync(&mut self, ctx: &egui::Context) {
+        if let Some((title, receiver)) = &self.draft_sync {
+            match receiver.try_recv() {
+                Ok(Ok(relay_count)) => {
+                    self.success_message = Some(format!("Synced draft \"{}\" to {} relay(s)", title, relay_count));
+                    self.draft_sync = None;
+                }
+                Ok(Err(e)) => {
+                    self.error_message = Some(format!("Failed to sync draft \"{}\": {}", title, e));
+                    self.draft_sync = None;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.draft_sync = None;
+                }
+            }
+        }
+        
+        if let Some(receiver) = &self.draft_pull {
+            match receiver.try_recv() {
+                Ok(Ok(drafts)) => {
+                    self.draft_pull = None;
+                    self.merge_pulled_drafts(drafts);
+                }
+                Ok(Err(e)) => {
+                    self.draft_pull = None;
+                    self.error_message = Some(format!("Failed to pull drafts: {}", e));
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.draft_pull = None;
+                }
+            }
+        }
+    }
+    
+    /// Add pulled drafts to the sidebar. A draft replaces a local post with
+    /// the same id only if it was edited more recently.
+    fn merge_pulled_drafts(&mut self, drafts: Vec<BlogPost>) {
+        let mut added = 0;
+        let mut updated = 0;
+        let mut skipped_locked = 0;
+        let mut skipped_trashed = 0;
+        // Deleted posts stay deleted until they're restored from the trash
+        let trashed: Vec<BlogPost> = match self.storage.list_trash() {
+            Ok(trash) => trash.into_iter().map(|t| t.post).collect(),
+            Err(e) => {
+                self.error_message = Some(format!("Failed to read the trash: {}", e));
+                return;
+            }
+        };
+        
+        for draft in drafts {
+            if trashed.iter().any(|p| is_same_article(p, &draft)) {
+                skipped_trashed += 1;
+                continue;
+            }
+
+            // The open copy may have edits that haven't been saved yet
+            let open_post = self.editor.get_post()
+                .filter(|p| is_same_article(p, &draft) && !self.editor.is_read_only())
+                .cloned();
+            let local = open_post.clone().or_else(|| self.posts.iter().find(|p| is_same_article(p, &draft)).cloned());
+            
+            let post = match local {
+                Some(local) if local.is_locked() => {
+                    skipped_locked += 1;
+                    continue;
+                }
+                Some(mut local) => {
+                    if !local.apply_draft(&draft) {
+                        continue;
+                    }
+                    updated += 1;
+                    local
+                }
+                None => {
+                    added += 1;
+                    draft
+                }
+            };
+            
+            match self.storage.save_post(&post) {
+                Ok(file_path) => {
+                    let mut post = post;
+                    post.file_path = Some(file_path);
+                    if open_post.is_some() {
+                        self.editor.set_post(post.clone());
+                    }
+                    if let Some(existing) = self.posts.iter_mut().find(|p| p.id == post.id) {
+                        *existing = post;
+                    } else {
+                        self.posts.push(post);
+                    }
+                }
+                Err(e) => {
+                    self.error_message = Some(format!("Failed to save pulled draft \"{}\": {}", post.title, e));
+                    return;
+                }
+            }
+        }
+        
+        let mut message = format!("Pulled drafts: {} new, {} updated", added, updated);
+        if skipped_locked > 0 {
+            message.push_str(&format!(", {} private post(s) skipped until unlocked", skipped_locked));
+        }
+        if skipped_trashed > 0 {
+            message.push_str(&format!(", {} in the trash skipped", skipped_trashed));
+        }
+        self.success_message = Some(message);
+    }
+    
+    /// Send always-on-top to the window and fade its background to the chosen opacity
+    fn apply_window_options(&self, ctx: &egui::Context) {
+        let level = if self.app_settings.always_on_top {
+            egui::WindowLevel::AlwaysOnTop
+        } else {
+            egui::WindowLevel::Normal
+        };
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+
+        // Start from the theme's opaque colors so opacity changes don't compound
+        self.shown_theme().apply_with(ctx, &self.custom_colors, self.system_dark);
+        apply_window_opacity(ctx, self.app_settings.window_opacity);
+    }
+
+    /// The chosen theme, with `Theme::System` resolved to a dark or light one
+    fn shown_theme(&self) -> Theme {
+        self.current_theme.resolve(self.system_dark)
+    }
+
     fn theme_colors(&self) -> crate::theme::ThemeColors {
-        self.current_theme.colors(Some(&self.custom_colors))
+        self.shown_theme().colors(Some(&self.custom_colors), self.system_dark)
+    }
+
+    /// While following the OS theme, check its light/dark preference every
+    /// few seconds and switch themes when it changes
+    fn poll_system_theme(&mut self, ctx: &egui::Context) {
+        if self.current_theme != Theme::System {
+            return;
+        }
+
+        if let Some(receiver) = &self.system_theme_check {
+            match receiver.try_recv() {
+                Ok(dark) => {
+                    self.system_theme_check = None;
+                    if dark != self.system_dark {
+                        tracing::info!("OS switched to {} mode", if dark { "dark" } else { "light" });
+                        self.system_dark = dark;
+                        self.window_options_changed = true;
+                        ctx.request_repaint();
+                    }
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                    return;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.system_theme_check = None;
+                }
+            }
+        } else if self.last_system_theme_check.elapsed() >= SYSTEM_THEME_CHECK_INTERVAL {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            self.runtime.spawn_blocking(move || {
+                let _ = sender.send(system_prefers_dark());
+            });
+            self.system_theme_check = Some(receiver);
+            self.last_system_theme_check = std::time::Instant::now();
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            return;
+        }
+        ctx.request_repaint_after(SYSTEM_THEME_CHECK_INTERVAL);
     }
     
     fn show_top_panel(&mut self, ctx: &egui::Context) {
@@ -145,6 +874,11 @@ impl BlogsterApp {
                             self.settings_dialog.open(self.current_theme, &self.custom_colors);
                             ui.close_menu();
                         }
+
+                        if ui.button("🛠 Preferences").clicked() {
+                            self.preferences_dialog.open(&self.app_settings, self.all_tags());
+                            ui.close_menu();
+                        }
                         
                         ui.separator();
                         
@@ -153,22 +887,83 @@ impl BlogsterApp {
                             ui.close_menu();
                         }
 
-                        if ui.button("� Relay Settings").clicked() {
+                        if ui.button("📱 Identity QR Code").clicked() {
+                            let keys = self.nostr_client
+                                .try_lock()
+                                .ok()
+                                .and_then(|client| client.npub().map(|npub| (npub, client.nsec())));
+                            match keys {
+                                Some((npub, nsec)) => self.qr_dialog.open(npub, nsec),
+                                None => self.error_message = Some("Set up your Nostr credentials first".to_string()),
+                            }
+                            ui.close_menu();
+                        }
+
+                        if ui.button("🌐 Relay Settings").clicked() {
                             self.relay_dialog.open(&self.relay_settings);
                             ui.close_menu();
                         }
 
-                        if ui.button("�🌸 Blossom Settings").clicked() {
-                            self.show_settings = true;
+                        if ui.button("🌸 Blossom Settings").clicked() {
+                            self.blossom_dialog.open(&self.blossom_settings);
+                            ui.close_menu();
+                        }
+
+                        if ui.button("🗂 Manage Uploads").on_hover_text("List and delete images on your Blossom servers").clicked() {
+                            let public_key = self.nostr_client
+                                .try_lock()
+                                .ok()
+                                .and_then(|client| client.get_credentials().map(|c| c.public_key.clone()));
+                            match public_key {
+                                Some(public_key) => self.blossom_uploads_dialog.open(public_key, &self.blossom_client, &self.runtime, &self.activity),
+                                None => self.error_message = Some("Set up your Nostr credentials first".to_string()),
+                            }
+                            ui.close_menu();
+                        }
+
+                        if ui.button("🌍 Export static site…").on_hover_text("Write your published posts as a browsable folder of web pages").clicked() {
+                            self.export_site();
+                            ui.close_menu();
+                        }
+
+                        if ui.button("📣 Republish Profile").on_hover_text("Send your profile to all active relays").clicked() {
+                            self.profile_broadcast_dialog.start(&self.nostr_client, &self.relay_settings, &self.runtime, &self.activity);
+                            ui.close_menu();
+                        }
+                        
+                        if ui.button("📊 Writing Stats").clicked() {
+                            self.writing_stats_dialog.open();
+                            ui.close_menu();
+                        }
+                        
+                        if ui.button("👋 Setup Guide").clicked() {
+                            self.onboarding_dialog.open();
+                            ui.close_menu();
+                        }
+                        
+                        if ui.button("⌨ Keyboard Shortcuts").on_hover_text("F1").clicked() {
+                            self.shortcuts_dialog.open();
+                            ui.close_menu();
+                        }
+                        
+                        if ui.button("🔄 Reconnect Relays").clicked() {
+                            self.reset_relay_connections(true);
+                            ui.close_menu();
+                        }
+                        
+                        if ui.button("🧹 Clear Preview Cache")
+                            .on_hover_text("Free memory used by rendered previews and loaded images")
+                            .clicked()
+                        {
+                            self.editor.clear_preview_cache();
+                            self.success_message = Some("Preview cache cleared".to_string());
                             ui.close_menu();
                         }
                         
                         ui.separator();
                         
                         if ui.button("📁 Open Posts Folder").clicked() {
-                            if let Err(e) = opener::open(self.storage.posts_dir()) {
-                                self.error_message = Some(format!("Failed to open folder: {}", e));
-                            }
+                            self.open_posts_folder();
                             ui.close_menu();
                         }
                         
@@ -176,6 +971,76 @@ impl BlogsterApp {
                             self.import_post();
                             ui.close_menu();
                         }
+
+                        if ui.button("🛟 Recover Published Post").clicked() {
+                            self.recover_dialog.open();
+                            ui.close_menu();
+                        }
+                    });
+                    
+                    ui.menu_button("🧰 Tools", |ui| {
+                        if ui.button("🔎 Look Up Event")
+                            .on_hover_text("Fetch an naddr, nevent, note or event id from your relays")
+                            .clicked()
+                        {
+                            self.event_lookup_dialog.open();
+                            ui.close_menu();
+                        }
+                        
+                        if ui.add_enabled(self.draft_pull.is_none(), egui::Button::new("📥 Pull Drafts"))
+                            .on_hover_text("Fetch drafts synced from your other devices (NIP-37)")
+                            .clicked()
+                        {
+                            self.pull_drafts();
+                            ui.close_menu();
+                        }
+                        
+                        if ui.button("🗑 Trash").on_hover_text("Restore or permanently delete deleted posts").clicked() {
+                            self.trash_dialog.open(&self.storage);
+                            ui.close_menu();
+                        }
+                        
+                        if self.app_settings.developer_tools {
+                            ui.separator();
+                            let current_post = self.editor.get_post().filter(|_| !self.editor.is_read_only()).cloned();
+                            if ui.add_enabled(current_post.is_some(), egui::Button::new("🧪 Test Publish to One Relay"))
+                                .on_hover_text("Publish the open post to a single relay and show its exact response")
+                                .on_disabled_hover_text("Open a post to test it")
+                                .clicked()
+                            {
+                                if let Some(post) = current_post {
+                                    self.relay_test_dialog.open(post);
+                                }
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    
+                    if ui.button("🔎 Search").on_hover_text("Search all posts (Ctrl+Shift+F)").clicked() {
+                        self.search_dialog.open();
+                    }
+                    
+                    // Recently edited posts
+                    ui.menu_button("🕘 Recent", |ui| {
+                        let recent: Vec<(uuid::Uuid, String)> = self.recent_posts
+                            .iter()
+                            .filter_map(|id| self.posts.iter().find(|p| p.id == *id))
+                            .map(|p| (p.id, if p.title.is_empty() { "Untitled".to_string() } else { p.title.clone() }))
+                            .collect();
+                        
+                        if recent.is_empty() {
+                            ui.label(RichText::new("No recent posts").color(self.theme_colors().text_muted));
+                        }
+                        
+                        for (id, title) in recent {
+                            if ui.button(title).clicked() {
+                                self.open_post(id);
+                                ui.close_menu();
+                            }
+                        }
+                        
+                        ui.separator();
+                        ui.label(RichText::new("Ctrl+Tab switches to the previous post").small().color(self.theme_colors().text_muted));
                     });
                     
                     // Status indicators
@@ -192,7 +1057,7 @@ impl BlogsterApp {
                         let creds_text = if has_credentials {
                             "🔑 Signed In"
                         } else {
-                            "🔓 No Credentials"
+                            "🔓 Signed Out"
                         };
                         
                         let colors = self.theme_colors();
@@ -202,20 +1067,44 @@ impl BlogsterApp {
                             } else {
                                 colors.warning
                             }
-                        ));
+                        )).on_hover_text(if has_credentials {
+                            "Posts are signed with your Nostr identity"
+                        } else {
+                            "No Nostr identity on this device. Add one under Settings → Credentials."
+                        });
+                        
+                        // Account picker, once there is more than one identity to choose from
+                        if self.accounts.len() > 1 {
+                            let mut switch_to = None;
+                            egui::ComboBox::from_id_source("account_picker")
+                                .selected_text(format!("👤 {}", self.active_account))
+                                .show_ui(ui, |ui| {
+                                    for account in &self.accounts {
+                                        if ui.selectable_label(account == &self.active_account, account).clicked() {
+                                            switch_to = Some(account.clone());
+                                        }
+                                    }
+                                })
+                                .response
+                                .on_hover_text("Switch the identity posts are signed and published with");
+                            if let Some(account) = switch_to.filter(|a| a != &self.active_account) {
+                                self.switch_account(account);
+                            }
+                        }
                         
                         ui.separator();
                         
                         // Loading status
-                        let status_text = if self.is_loading {
-                            "⏳ Loading..."
+                        let is_busy = self.activity.is_busy();
+                        let status_text = if is_busy {
+                            "⏳ Working..."
                         } else {
                             "✅ Ready"
                         };
                         
                         let colors = self.theme_colors();
                         ui.label(RichText::new(status_text).color(
-                            if self.is_loading {
+                            if is_busy {
                                 colors.warning
                             } else {
                                 colors.success
@@ -227,6 +1116,44 @@ impl BlogsterApp {
         });
     }
     
+    /// Clear status bar messages once they've been visible for the configured time
+    fn expire_messages(&mut self, ctx: &egui::Context) {
+        let (current, timeout) = match (&self.error_message, &self.success_message) {
+            (Some(error), _) => (error.clone(), self.app_settings.error_dismiss_secs),
+            (None, Some(success)) => (success.clone(), Some(self.app_settings.success_dismiss_secs)),
+            (None, None) => {
+                self.message_shown = None;
+                return;
+            }
+        };
+        
+        // Restart the timer whenever a different message is shown
+        let shown_at = match &self.message_shown {
+            Some((text, shown_at)) if *text == current => *shown_at,
+            _ => {
+                let now = std::time::Instant::now();
+                self.message_shown = Some((current, now));
+                now
+            }
+        };
+        
+        let Some(timeout) = timeout.map(std::time::Duration::from_secs) else {
+            return;
+        };
+        
+        let elapsed = shown_at.elapsed();
+        if elapsed >= timeout {
+            if self.error_message.is_some() {
+                self.error_message = None;
+            } else {
+                self.success_message = None;
+            }
+            self.message_shown = None;
+        } else {
+            ctx.request_repaint_after(timeout - elapsed);
+        }
+    }
+    
     fn show_bottom_panel(&mut self, ctx: &egui::Context) {
         TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
@@ -245,31 +1172,109 @@ impl BlogsterApp {
                 } else {
                     ui.label(RichText::new(format!("{} posts", self.posts.len())).color(colors.text_secondary));
                 }
+
+                // Image upload progress
+                let mut cancel_upload = false;
+                if let Some(upload) = &self.image_upload {
+                    ui.separator();
+                    let fraction = if upload.total > 0 { upload.sent as f32 / upload.total as f32 } else { 0.0 };
+                    ui.add(
+                        egui::ProgressBar::new(fraction)
+                            .desired_width(160.0)
+                            .text(format!("⬆ {:.1} / {:.1} MB", upload.sent as f64 / 1_000_000.0, upload.total as f64 / 1_000_000.0)),
+                    );
+                    cancel_upload = ui.small_button("Cancel").on_hover_text("Stop uploading this image").clicked();
+                }
+                if cancel_upload {
+                    self.cancel_image_upload();
+                }
+
+                // Background activity indicator
+                let activities = self.activity.snapshot();
+                if !activities.is_empty() {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let tooltip = activities
+                            .iter()
+                            .map(|a| format!("• {} ({}s)", a.label, a.started_at.elapsed().as_secs()))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+
+                        let response = ui
+                            .add(
+                                egui::Label::new(
+                                    RichText::new(format!("{} active", activities.len()))
+                                        .color(colors.warning),
+                                )
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text(tooltip);
+                        ui.spinner();
+
+                        if response.clicked() {
+                            self.show_activity_list = !self.show_activity_list;
+                        }
+                    });
+                }
             });
         });
+
+        self.show_activity_window(ctx);
+    }
+
+    fn show_activity_window(&mut self, ctx: &egui::Context) {
+        if !self.show_activity_list {
+            return;
+        }
+
+        let colors = self.theme_colors();
+        let activities = self.activity.snapshot();
+
+        egui::Window::new("⏳ Background Tasks")
+            .open(&mut self.show_activity_list)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if activities.is_empty() {
+                    ui.label(RichText::new("No background tasks running").color(colors.text_muted));
+                }
+
+                for activity in &activities {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(RichText::new(&activity.label).color(colors.text));
+                        ui.label(
+                            RichText::new(format!("{}s", activity.started_at.elapsed().as_secs()))
+                                .small()
+                                .color(colors.text_secondary),
+                        );
+                    });
+                }
+            });
+
+        // Keep elapsed times ticking while the list is visible
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
     }
     
     fn handle_sidebar_action(&mut self, action: SidebarAction) {
         match action {
             SidebarAction::NewPost => {
-                let mut new_post = BlogPost::new();
-                new_post.title = "New Post".to_string();
-                self.sidebar.set_selected_post_id(Some(new_post.id));
-                self.editor.set_post(new_post);
+                self.create_new_post();
             }
             SidebarAction::SelectPost(id) => {
-                if let Some(post) = self.posts.iter().find(|p| p.id == id).cloned() {
-                    self.editor.set_post(post);
-                }
+                self.open_post(id);
             }
-            SidebarAction::DeletePost(id) => {
+            SidebarAction::DeletePost { id, from_relays } => {
                 if let Some(index) = self.posts.iter().position(|p| p.id == id) {
+                    if from_relays {
+                        self.delete_from_relays(self.posts[index].clone());
+                    }
                     let post = &self.posts[index];
                     if let Err(e) = self.storage.delete_post(post) {
                         self.error_message = Some(format!("Failed to delete post: {}", e));
                     } else {
                         self.posts.remove(index);
-                        self.success_message = Some("Post deleted successfully".to_string());
+                        self.forget_recent(id);
+                        self.success_message = Some("Post moved to the trash".to_string());
                         
                         // Clear editor if this post was selected
                         if self.sidebar.selected_post_id() == Some(id) {
@@ -279,20 +1284,411 @@ impl BlogsterApp {
                     }
                 }
             }
+            SidebarAction::DuplicatePost(id)
+            | SidebarAction::ExportPost(id)
+            | SidebarAction::ExportPostHtml(id)
+            | SidebarAction::PublishPost(id)
+            | SidebarAction::SyncDraft(id)
+                if self.posts.iter().any(|p| p.id == id && p.is_locked()) =>
+            {
+                self.error_message = Some("Open the private post to unlock it first".to_string());
+            }
+            SidebarAction::DuplicatePost(id) => {
+                // Start from the editor's copy if the post is open there
+                let post = self.editor.get_post()
+                    .filter(|p| p.id == id && !self.editor.is_read_only())
+                    .or_else(|| self.posts.iter().find(|p| p.id == id))
+                    .map(BlogPost::duplicate);
+                if let Some(mut copy) = post {
+                    if copy.private && !self.storage.has_private_passphrase() {
+                        self.error_message = Some("Enter the private posts passphrase before duplicating a private post".to_string());
+                        return;
+                    }
+                    // The copy replaces the open post in the editor, so keep its edits first
+                    if let Err(e) = self.save_unsaved_edits() {
+                        self.error_message = Some(format!("Failed to save the open post: {:#}", e));
+                        return;
+                    }
+                    self.writing_stats.seed([(copy.id, copy.word_count())]);
+                    match self.write_post(&mut copy) {
+                        Ok(()) => {
+                            self.success_message = Some(format!("Created \"{}\"", copy.title));
+                            self.sidebar.set_selected_post_id(Some(copy.id));
+                            self.editor.set_post(copy);
+                            debug_assert_eq!(self.sidebar.selected_post_id(), self.editor.get_post().map(|p| p.id));
+                        }
+                        Err(e) => self.error_message = Some(format!("Failed to duplicate post: {}", e)),
+                    }
+                }
+            }
             SidebarAction::ExportPost(id) => {
                 if let Some(post) = self.posts.iter().find(|p| p.id == id).cloned() {
                     self.export_post(&post);
                 }
             }
+            SidebarAction::ExportPostHtml(id) => {
+                if let Some(post) = self.posts.iter().find(|p| p.id == id).cloned() {
+                    self.export_post_html(&post);
+                }
+            }
             SidebarAction::PublishPost(id) => {
                 if let Some(post) = self.posts.iter().find(|p| p.id == id).cloned() {
                     self.publish_dialog.open(post);
                 }
             }
+            SidebarAction::SyncDraft(id) => {
+                // Sync what's in the editor if the post is open there
+                let post = self.editor.get_post()
+                    .filter(|p| p.id == id && !self.editor.is_read_only())
+                    .or_else(|| self.posts.iter().find(|p| p.id == id))
+                    .cloned();
+                if let Some(post) = post {
+                    self.sync_draft(post);
+                }
+            }
+            SidebarAction::TogglePin(id) => {
+                if let Some(post) = self.posts.iter_mut().find(|p| p.id == id) {
+                    post.pinned = !post.pinned;
+                    let pinned = post.pinned;
+                    if let Err(e) = self.storage.save_post(post) {
+                        post.pinned = !pinned;
+                        self.error_message = Some(format!("Failed to update post: {}", e));
+                        return;
+                    }
+                    
+                    // Keep the open copy in sync so saving it doesn't undo the change
+                    if let Some(open_post) = self.editor.get_post_mut().filter(|p| p.id == id) {
+                        open_post.pinned = pinned;
+                    }
+                }
+            }
+            SidebarAction::ShowAddress(id) => {
+                let Some(post) = self.posts.iter().find(|p| p.id == id) else {
+                    return;
+                };
+                // Articles are addressed by their author, which is the identity that published them
+                let public_key = match &post.published_by {
+                    Some(npub) => crate::nostr_client::public_key_hex(npub),
+                    // Posts published before the author was recorded went out with the active account
+                    None => match self.nostr_client.try_lock() {
+                        Ok(client) => match client.get_credentials() {
+                            Some(credentials) => Ok(credentials.public_key.clone()),
+                            None => {
+                                self.error_message = Some("Set up your Nostr credentials first".to_string());
+                                return;
+                            }
+                        },
+                        Err(_) => {
+                            self.error_message = Some("Still talking to relays, try again in a moment".to_string());
+                            return;
+                        }
+                    },
+                };
+                let address = public_key
+                    .and_then(|public_key| crate::nostr_client::article_address(post, &public_key, &post.published_relays));
+                match address {
+                    Ok(address) => self.naddr_dialog.open(post.title.clone(), address),
+                    Err(e) => self.error_message = Some(format!("Failed to build article address: {}", e)),
+                }
+            }
+            SidebarAction::SortChanged(sort) => {
+                self.app_settings.post_sort = sort;
+                if let Err(e) = self.storage.save_app_settings(&self.app_settings) {
+                    tracing::warn!("Failed to save post sort order: {}", e);
+                }
+            }
             SidebarAction::None => {}
         }
     }
     
+    /// Re-read the saved accounts after they changed in the credentials dialog
+    fn refresh_accounts(&mut self) {
+        self.accounts = self.storage.list_accounts();
+        self.active_account = self.storage.active_account();
+    }
+    
+    /// Sign in as another saved account and reconnect to the relays with it
+    fn switch_account(&mut self, account: String) {
+        if self.account_switch.is_some() {
+            self.error_message = Some("Already switching accounts".to_string());
+            return;
+        }
+        let credentials = match self.storage.load_credentials(&account) {
+            Ok(Some(credentials)) => credentials,
+            Ok(None) => {
+                self.error_message = Some(format!("No credentials saved for account \"{}\"", account));
+                return;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load credentials: {:#}", e));
+                return;
+            }
+        };
+        
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let client = self.nostr_client.clone();
+        let relay_settings = self.relay_settings.clone();
+        let activity_guard = self.activity.begin(format!("Switching to account \"{}\"", account));
+        
+        self.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            // Reach a remote signer before locking, so the app keeps working meanwhile
+            let signer = NostrClient::connect_signer(credentials).await;
+            let mut client = client.lock().await;
+            // Drop connections made with the previous identity
+            if let Err(e) = client.reset_connections().await {
+                tracing::warn!("Failed to reset relay connections: {}", e);
+            }
+            // A failed switch leaves the previous identity in place; reconnect it either way
+            let result = signer.map(|signer| client.use_signer(signer)).map_err(|e| format!("{:#}", e));
+            if let Err(e) = client.connect_to_relays(&relay_settings).await {
+                tracing::warn!("Failed to reconnect to relays: {}", e);
+            }
+            let _ = sender.send(result);
+        });
+        self.account_switch = Some((account, receiver));
+    }
+    
+    fn poll_account_switch(&mut self, ctx: &egui::Context) {
+        let Some((account, receiver)) = &self.account_switch else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(Ok(())) => {
+                let account = account.clone();
+                self.account_switch = None;
+                if let Err(e) = self.storage.set_active_account(&account) {
+                    tracing::warn!("Failed to remember active account: {:#}", e);
+                }
+                self.success_message = Some(format!("Signed in as \"{}\"", account));
+                self.active_account = account;
+            }
+            Ok(Err(e)) => {
+                self.error_message = Some(format!("Failed to switch to account \"{}\": {}", account, e));
+                self.account_switch = None;
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.account_switch = None;
+            }
+        }
+    }
+    
+    /// Drop all relay connections in the background, optionally reconnecting
+    /// to the current relay list straight away
+    fn reset_relay_connections(&self, reconnect: bool) {
+        let client = self.nostr_client.clone();
+        let relay_settings = self.relay_settings.clone();
+        let activity_guard = self.activity.begin(if reconnect { "Reconnecting relays" } else { "Resetting relay connections" });
+        
+        self.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let client = client.lock().await;
+            if let Err(e) = client.reset_connections().await {
+                tracing::warn!("Failed to reset relay connections: {}", e);
+                return;
+            }
+            if reconnect {
+                if let Err(e) = client.connect_to_relays(&relay_settings).await {
+                    tracing::warn!("Failed to reconnect to relays: {}", e);
+                }
+            }
+        });
+    }
+    
+    /// Every tag used across all posts, sorted and without duplicates
+    fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.posts.iter().flat_map(|p| p.tags.iter().cloned()).collect();
+        tags.sort_by_key(|t| t.to_lowercase());
+        tags.dedup_by(|a, b| a.to_lowercase() == b.to_lowercase());
+        tags
+    }
+    
+    fn create_new_post(&mut self) {
+        let (title, content) = self.app_settings.new_post_defaults(chrono::Local::now().date_naive());
+        let mut new_post = BlogPost::new();
+        new_post.title = title;
+        new_post.content = content;
+        // The boilerplate isn't writing progress
+        self.writing_stats.seed([(new_post.id, new_post.word_count())]);
+        self.sidebar.set_selected_post_id(Some(new_post.id));
+        self.editor.set_post(new_post);
+    }
+    
+    /// Open a post in the editor and mark it as recently used
+    fn open_post(&mut self, id: uuid::Uuid) {
+        if self.posts.iter().any(|p| p.id == id && p.is_locked()) {
+            if !self.storage.has_private_passphrase() {
+                self.passphrase_dialog.open(PassphrasePurpose::Unlock(id));
+                return;
+            }
+            if let Err(e) = self.unlock_private_post(id) {
+                self.error_message = Some(format!("{:#}", e));
+                self.passphrase_dialog.open(PassphrasePurpose::Unlock(id));
+                return;
+            }
+        }
+        
+        if let Some(post) = self.posts.iter().find(|p| p.id == id).cloned() {
+            self.sidebar.set_selected_post_id(Some(id));
+            self.editor.set_post(post);
+            self.touch_recent(id);
+        }
+    }
+    
+    /// Decrypt a private post, then any others the same passphrase opens
+    fn unlock_private_post(&mut self, id: uuid::Uuid) -> anyhow::Result<()> {
+        if let Some(post) = self.posts.iter_mut().find(|p| p.id == id) {
+            self.storage.unlock_post(post)?;
+        }
+        self.unlock_remaining_private_posts();
+        Ok(())
+    }
+    
+    /// Decrypt every locked post with the session passphrase, reporting any it
+    /// doesn't open. Keys are remembered per salt, so only posts saved in other
+    /// sessions cost a key derivation.
+    fn unlock_remaining_private_posts(&mut self) {
+        let mut still_locked = Vec::new();
+        for post in self.posts.iter_mut().filter(|p| p.is_locked()) {
+            if let Err(e) = self.storage.unlock_post(post) {
+                tracing::warn!("{:#}", e);
+                still_locked.push(post.title.clone());
+            }
+        }
+        if !still_locked.is_empty() {
+            self.error_message = Some(format!(
+                "{} private post(s) use a different passphrase and stay locked: {}",
+                still_locked.len(),
+                still_locked.join(", ")
+            ));
+        }
+        self.writing_stats.seed(self.posts.iter().filter(|p| !p.is_locked()).map(|p| (p.id, p.word_count())));
+    }
+    
+    /// Move a post to the front of the recent posts list
+    fn touch_recent(&mut self, id: uuid::Uuid) {
+        if self.recent_posts.first() == Some(&id) {
+            return;
+        }
+        
+        self.recent_posts.retain(|recent| *recent != id);
+        self.recent_posts.insert(0, id);
+        self.recent_posts.truncate(MAX_RECENT_POSTS);
+        
+        if let Err(e) = self.storage.save_recent_posts(&self.recent_posts) {
+            tracing::warn!("Failed to save recent posts: {}", e);
+        }
+    }
+    
+    fn forget_recent(&mut self, id: uuid::Uuid) {
+        self.recent_posts.retain(|recent| *recent != id);
+        if let Err(e) = self.storage.save_recent_posts(&self.recent_posts) {
+            tracing::warn!("Failed to save recent posts: {}", e);
+        }
+    }
+    
+    /// Handle global keyboard shortcuts
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Ctrl+N (Cmd+N on macOS) creates a new post, same as the ➕ button.
+        // A plain N never matches, so typing in the editor is unaffected.
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::N)) {
+            self.handle_sidebar_action(SidebarAction::NewPost);
+        }
+        
+        // Ctrl+Shift+F searches all posts
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::CTRL | egui::Modifiers::SHIFT, egui::Key::F)) {
+            self.search_dialog.open();
+        }
+        
+        // Ctrl+Shift+P opens the command palette, before Ctrl+P can take it
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::P)) {
+            self.command_palette.open();
+        }
+        
+        // Ctrl+S, Ctrl+P and Ctrl+E act on the open post like the editor's
+        // buttons. Text fields don't use these, so they work while typing.
+        let editable = self.editor.get_post().is_some() && !self.editor.is_read_only();
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::S)) && editable {
+            self.handle_editor_action(EditorAction::Save);
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::P)) && editable && !self.publish_dialog.is_open() {
+            self.publish_open_post();
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::E)) {
+            self.editor.toggle_preview();
+        }
+        
+        // F1 lists the shortcuts
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F1)) {
+            self.shortcuts_dialog.open();
+        }
+        
+        // Ctrl+Tab jumps to the previously used post
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::CTRL, egui::Key::Tab)) {
+            let current = self.editor.get_post().map(|p| p.id);
+            let next = self.recent_posts
+                .iter()
+                .copied()
+                .find(|id| Some(*id) != current && self.posts.iter().any(|p| p.id == *id));
+            if let Some(id) = next {
+                self.open_post(id);
+            }
+        }
+    }
+    
+    /// Open the publish dialog for the post in the editor once it has a title and content
+    fn publish_open_post(&mut self) {
+        if self.editor.get_post().is_some_and(|p| p.is_ready_to_publish()) {
+            self.handle_editor_action(EditorAction::Publish);
+        } else {
+            self.error_message = Some("Add a title and some content before publishing".to_string());
+        }
+    }
+    
+    fn open_posts_folder(&mut self) {
+        if let Err(e) = opener::open(self.storage.posts_dir()) {
+            self.error_message = Some(format!("Failed to open folder: {}", e));
+        }
+    }
+    
+    /// Run a command picked in the command palette through the same actions
+    /// as the menus, sidebar and editor
+    fn run_command(&mut self, command: PaletteCommand) {
+        // Post commands act on the post open in the editor
+        let post_id = self.editor.get_post().filter(|_| !self.editor.is_read_only()).map(|p| p.id);
+        match (command, post_id) {
+            (PaletteCommand::NewPost, _) => self.handle_sidebar_action(SidebarAction::NewPost),
+            (PaletteCommand::SavePost, Some(_)) => self.handle_editor_action(EditorAction::Save),
+            (PaletteCommand::PublishPost, Some(_)) => self.publish_open_post(),
+            (PaletteCommand::TogglePreview, Some(_)) => self.editor.toggle_preview(),
+            (PaletteCommand::DuplicatePost, Some(id)) => self.handle_sidebar_action(SidebarAction::DuplicatePost(id)),
+            (PaletteCommand::DeletePost, Some(id)) => {
+                self.handle_sidebar_action(SidebarAction::DeletePost { id, from_relays: false });
+            }
+            (PaletteCommand::ExportPost, Some(id)) => self.handle_sidebar_action(SidebarAction::ExportPost(id)),
+            (PaletteCommand::ExportPostHtml, Some(id)) => self.handle_sidebar_action(SidebarAction::ExportPostHtml(id)),
+            (PaletteCommand::ExportSite, _) => self.export_site(),
+            (PaletteCommand::ImportPost, _) => self.import_post(),
+            (PaletteCommand::OpenPostsFolder, _) => self.open_posts_folder(),
+            (PaletteCommand::SearchPosts, _) => self.search_dialog.open(),
+            (PaletteCommand::Trash, _) => self.trash_dialog.open(&self.storage),
+            (PaletteCommand::Appearance, _) => self.settings_dialog.open(self.current_theme, &self.custom_colors),
+            (PaletteCommand::Preferences, _) => self.preferences_dialog.open(&self.app_settings, self.all_tags()),
+            (PaletteCommand::Credentials, _) => self.credentials_dialog.open_with_storage(&self.storage),
+            (PaletteCommand::RelaySettings, _) => self.relay_dialog.open(&self.relay_settings),
+            (PaletteCommand::BlossomSettings, _) => self.blossom_dialog.open(&self.blossom_settings),
+            (PaletteCommand::ReconnectRelays, _) => self.reset_relay_connections(true),
+            (PaletteCommand::LookUpEvent, _) => self.event_lookup_dialog.open(),
+            (PaletteCommand::RecoverPost, _) => self.recover_dialog.open(),
+            (PaletteCommand::WritingStats, _) => self.writing_stats_dialog.open(),
+            (PaletteCommand::KeyboardShortcuts, _) => self.shortcuts_dialog.open(),
+            (_, None) => self.error_message = Some("Open a post first".to_string()),
+        }
+    }
+    
     fn handle_editor_action(&mut self, action: EditorAction) {
         match action {
             EditorAction::Save => {
@@ -300,6 +1696,11 @@ impl BlogsterApp {
                     self.save_post(post.clone());
                 }
             }
+            EditorAction::SaveCopy { include_frontmatter } => {
+                if let Some(post) = self.editor.get_post().cloned() {
+                    self.save_post_copy(&post, include_frontmatter);
+                }
+            }
             EditorAction::Publish => {
                 if let Some(post) = self.editor.get_post().cloned() {
                     self.publish_dialog.open(post);
@@ -311,26 +1712,37 @@ impl BlogsterApp {
             EditorAction::UploadFeaturedImage => {
                 self.upload_featured_image();
             }
+            EditorAction::EditLocalCopy => {
+                if let Some(post) = self.editor.get_post().cloned() {
+                    // Ask before the fetched version replaces a local post
+                    if self.posts.iter().any(|p| p.id == post.id) {
+                        self.local_copy_conflict = Some(post);
+                    } else {
+                        self.save_local_copy(post);
+                    }
+                }
+            }
+            EditorAction::PreviewWidthChanged(preview_width) => {
+                self.app_settings.preview_width = preview_width;
+                if let Err(e) = self.storage.save_app_settings(&self.app_settings) {
+                    tracing::warn!("Failed to save preview width: {}", e);
+                }
+            }
             EditorAction::Changed => {
-                // Auto-save on changes (optional)
-                // self.save_current_post();
+                self.last_edit = Some(std::time::Instant::now());
             }
             EditorAction::None => {}
         }
     }
     
     fn save_post(&mut self, mut post: BlogPost) {
-        match self.storage.save_post(&post) {
-            Ok(file_path) => {
-                post.file_path = Some(file_path);
-                
-                // Update or add to posts list
-                if let Some(existing_post) = self.posts.iter_mut().find(|p| p.id == post.id) {
-                    *existing_post = post.clone();
-                } else {
-                    self.posts.push(post.clone());
-                }
-                
+        if post.private && !self.storage.has_private_passphrase() {
+            self.passphrase_dialog.open(PassphrasePurpose::Encrypt);
+            return;
+        }
+        
+        match self.write_post(&mut post) {
+            Ok(()) => {
                 // Update editor
                 self.editor.set_post(post);
                 
@@ -342,13 +1754,248 @@ impl BlogsterApp {
         }
     }
     
+    /// Write a post to disk and record it in the posts list, writing stats
+    /// and recent posts. The editor is left alone.
+    fn write_post(&mut self, post: &mut BlogPost) -> anyhow::Result<()> {
+        let file_path = self.storage.save_post(post)?;
+        post.file_path = Some(file_path);
+        
+        // Update or add to posts list
+        if let Some(existing_post) = self.posts.iter_mut().find(|p| p.id == post.id) {
+            *existing_post = post.clone();
+        } else {
+            self.posts.push(post.clone());
+        }
+        self.touch_recent(post.id);
+        
+        let today = chrono::Local::now().date_naive();
+        self.writing_stats.record_save(post.id, post.word_count(), today);
+        if let Err(e) = self.storage.save_writing_stats(&self.writing_stats) {
+            tracing::warn!("Failed to save writing stats: {}", e);
+        }
+        
+        if let Err(e) = self.storage.clear_recovery_snapshot(post.id) {
+            tracing::warn!("Failed to clear recovery snapshot: {}", e);
+        }
+        Ok(())
+    }
+    
+    /// Quietly save the post being edited once it has gone unchanged for the
+    /// autosave interval
+    fn autosave(&mut self, ctx: &egui::Context) {
+        let Some(last_edit) = self.last_edit else {
+            return;
+        };
+        if !self.app_settings.autosave {
+            self.last_edit = None;
+            return;
+        }
+        
+        let interval = std::time::Duration::from_secs(self.app_settings.autosave_interval_secs);
+        let elapsed = last_edit.elapsed();
+        if elapsed < interval {
+            ctx.request_repaint_after(interval - elapsed);
+            return;
+        }
+        // The finished upload edits the post; save after it lands
+        if self.image_upload.is_some() {
+            return;
+        }
+        self.last_edit = None;
+        
+        // Private posts wait for a manual save, which asks for the passphrase
+        if self.editor.get_post().is_some_and(|p| p.private) && !self.storage.has_private_passphrase() {
+            return;
+        }
+        if let Err(e) = self.save_unsaved_edits() {
+            tracing::warn!("Autosave failed: {:#}", e);
+            self.error_message = Some(format!("Autosave failed: {:#}", e));
+        }
+    }
+    
+    /// Save the post being edited if it has changes that aren't on disk yet
+    fn save_unsaved_edits(&mut self) -> anyhow::Result<()> {
+        let Some(mut post) = self.editor.get_post().cloned() else {
+            return Ok(());
+        };
+        if self.editor.is_read_only() {
+            return Ok(());
+        }
+        let has_unsaved_changes = self.posts
+            .iter()
+            .find(|p| p.id == post.id)
+            .map_or(true, |saved| saved.updated_at != post.updated_at);
+        if !has_unsaved_changes {
+            return Ok(());
+        }
+        
+        self.write_post(&mut post)?;
+        tracing::debug!("Saved \"{}\"", post.title);
+        // Keep the editor's view and selection; only the file location can change
+        if let Some(editing) = self.editor.get_post_mut().filter(|p| p.id == post.id) {
+            editing.file_path = post.file_path;
+        }
+        Ok(())
+    }
+    
+    /// Periodically copy the post being edited to the crash-recovery buffer
+    /// if it has changes that haven't been saved yet
+    fn snapshot_for_recovery(&mut self, ctx: &egui::Context) {
+        // Make sure the last edit before going idle still gets captured
+        ctx.request_repaint_after(self.recovery_interval);
+        
+        if self.last_recovery_snapshot.elapsed() < self.recovery_interval {
+            return;
+        }
+        self.last_recovery_snapshot = std::time::Instant::now();
+        
+        let Some(post) = self.editor.get_post() else {
+            return;
+        };
+        if self.editor.is_read_only() {
+            return;
+        }
+        
+        let has_unsaved_changes = self.posts
+            .iter()
+            .find(|p| p.id == post.id)
+            .map_or(true, |saved| saved.updated_at != post.updated_at);
+        let version = Some((post.id, post.updated_at));
+        
+        if has_unsaved_changes && version != self.last_snapshot_version {
+            if let Err(e) = self.storage.save_recovery_snapshot(post) {
+                tracing::warn!("Failed to write recovery snapshot: {}", e);
+            }
+            self.last_snapshot_version = version;
+        }
+    }
+    
+    fn save_local_copy(&mut self, post: BlogPost) {
+        let title = post.title.clone();
+        self.sidebar.set_selected_post_id(Some(post.id));
+        self.save_post(post);
+        self.success_message = Some(format!("Saved a local copy of \"{}\"", title));
+    }
+    
+    /// Ask whether a fetched article replaces the local post with its id or
+    /// is saved alongside it
+    fn show_local_copy_conflict(&mut self, ctx: &egui::Context) {
+        let Some(post) = &self.local_copy_conflict else {
+            return;
+        };
+        
+        let colors = self.theme_colors();
+        let mut open = true;
+        let mut replace = false;
+        let mut keep_both = false;
+        
+        egui::Window::new("📝 Post Already Exists")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(RichText::new(format!("You already have a local copy of \"{}\".", post.title)).color(colors.text));
+                ui.label(RichText::new("Replacing it loses any local changes that were never published.").small().color(colors.text_muted));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(RichText::new("♻ Replace local post").color(colors.error)).clicked() {
+                        replace = true;
+                    }
+                    if ui.button("📑 Keep both").on_hover_text("Save the fetched version as a new draft").clicked() {
+                        keep_both = true;
+                    }
+                });
+            });
+        
+        if !open {
+            self.local_copy_conflict = None;
+        } else if replace || keep_both {
+            let Some(mut post) = self.local_copy_conflict.take() else {
+                return;
+            };
+            if replace {
+                // Save over the local post's file, even if the title changed
+                if let Some(local) = self.posts.iter().find(|p| p.id == post.id) {
+                    post.file_path = local.file_path.clone();
+                    post.pinned = local.pinned;
+                    post.private = local.private;
+                }
+            } else {
+                // The copy is a new draft, not another record of the published article
+                post = BlogPost { title: post.title.clone(), ..post.duplicate() };
+            }
+            self.save_local_copy(post);
+        }
+    }
+    
+    /// Offer to restore edits recovered from a previous crash
+    fn show_recovery_prompt(&mut self, ctx: &egui::Context) {
+        if self.pending_recoveries.is_empty() {
+            return;
+        }
+        
+        let colors = self.theme_colors();
+        let mut restore = None;
+        let mut discard = None;
+        
+        egui::Window::new("🩹 Unsaved Changes Found")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(RichText::new("Blogster didn't close cleanly last time. These posts had unsaved changes:").color(colors.text));
+                ui.add_space(8.0);
+                
+                for (index, post) in self.pending_recoveries.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let title = if post.title.is_empty() { "Untitled" } else { &post.title };
+                        ui.label(RichText::new(title).strong().color(colors.text));
+                        ui.label(RichText::new(post.updated_at.format("%Y-%m-%d %H:%M").to_string()).small().color(colors.text_muted));
+                        
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.button(RichText::new("🗑 Discard").color(colors.error)).clicked() {
+                                discard = Some(index);
+                            }
+                            if ui.button(RichText::new("♻ Restore").color(colors.success)).clicked() {
+                                restore = Some(index);
+                            }
+                        });
+                    });
+                }
+            });
+        
+        if let Some(index) = restore {
+            let post = self.pending_recoveries.remove(index);
+            self.sidebar.set_selected_post_id(Some(post.id));
+            self.editor.set_post(post);
+            self.success_message = Some("Restored unsaved changes. Save the post to keep them.".to_string());
+        } else if let Some(index) = discard {
+            let post = self.pending_recoveries.remove(index);
+            if let Err(e) = self.storage.clear_recovery_snapshot(post.id) {
+                tracing::warn!("Failed to remove recovery snapshot: {}", e);
+            }
+        }
+    }
+    
     fn export_post(&mut self, post: &BlogPost) {
         if let Some(path) = rfd::FileDialog::new()
             .set_file_name(&post.generate_filename())
             .add_filter("Markdown", &["md"])
+            .add_filter("HTML", &["html", "htm"])
             .save_file()
         {
-            if let Err(e) = self.storage.export_post(post, &path) {
+            let is_html = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+            let result = if is_html {
+                let options = HtmlExportOptions { include_stats: self.app_settings.html_export_stats };
+                self.storage.export_post_html(post, &path, options)
+            } else {
+                self.storage.export_post(post, &path)
+            };
+            
+            if let Err(e) = result {
                 self.error_message = Some(format!("Failed to export post: {}", e));
             } else {
                 self.success_message = Some("Post exported successfully".to_string());
@@ -356,13 +2003,72 @@ impl BlogsterApp {
         }
     }
     
+    fn export_post_html(&mut self, post: &BlogPost) {
+        let file_name = std::path::Path::new(&post.generate_filename()).with_extension("html");
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name(file_name.to_string_lossy())
+            .add_filter("HTML", &["html", "htm"])
+            .save_file()
+        {
+            let options = HtmlExportOptions { include_stats: self.app_settings.html_export_stats };
+            if let Err(e) = self.storage.export_post_html(post, &path, options) {
+                self.error_message = Some(format!("Failed to export post: {}", e));
+            } else {
+                self.success_message = Some(format!("Exported \"{}\" as HTML", post.title));
+            }
+        }
+    }
+    
+    /// Export every published post as a static site in a folder the user picks
+    fn export_site(&mut self) {
+        let published: Vec<BlogPost> = self.posts
+            .iter()
+            .filter(|p| p.status == PostStatus::Published && !p.is_locked())
+            .cloned()
+            .collect();
+        if published.is_empty() {
+            self.error_message = Some("There are no published posts to export".to_string());
+            return;
+        }
+        
+        if let Some(dir) = rfd::FileDialog::new().set_title("Choose a folder for the site").pick_folder() {
+            let options = HtmlExportOptions { include_stats: self.app_settings.html_export_stats };
+            match self.storage.export_site(&published, &dir, &self.theme_colors(), options) {
+                Ok(()) => {
+                    let locked = self.posts.iter().filter(|p| p.status == PostStatus::Published && p.is_locked()).count();
+                    let mut message = format!("Exported {} posts to {}", published.len(), dir.display());
+                    if locked > 0 {
+                        message.push_str(&format!(" ({} locked private posts skipped)", locked));
+                    }
+                    self.success_message = Some(message);
+                }
+                Err(e) => self.error_message = Some(format!("Failed to export site: {:#}", e)),
+            }
+        }
+    }
+    
+    fn save_post_copy(&mut self, post: &BlogPost, include_frontmatter: bool) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&post.generate_filename())
+            .add_filter("Markdown", &["md"])
+            .save_file()
+        {
+            if let Err(e) = self.storage.save_post_copy(post, &path, include_frontmatter) {
+                self.error_message = Some(format!("Failed to save copy: {}", e));
+            } else {
+                self.success_message = Some(format!("Copy saved to {}", path.display()));
+            }
+        }
+    }
+    
     fn import_post(&mut self) {
         if let Some(path) = rfd::FileDialog::new()
             .add_filter("Markdown", &["md"])
             .pick_file()
         {
             match self.storage.import_post(&path) {
-                Ok(post) => {
+                Ok((post, report)) => {
+                    self.import_report_dialog.open(post.title.clone(), report);
                     self.posts.push(post);
                     self.success_message = Some("Post imported successfully".to_string());
                 }
@@ -374,169 +2080,429 @@ impl BlogsterApp {
     }
     
     fn insert_image(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Images", &["png", "jpg", "jpeg", "gif", "webp", "svg"])
-            .pick_file()
-        {
-            self.is_loading = true;
-            let blossom_client = self.blossom_client.clone();
-            let path_clone = path.clone();
-            
-            // Upload image to Blossom server
-            let upload_future = async move {
-                blossom_client.upload_file(&path_clone).await
-            };
-            
-            match self.runtime.block_on(upload_future) {
-                Ok(blob_url) => {
-                    // Insert the uploaded image URL into the post
-                    if let Some(post) = self.editor.get_post_mut() {
-                        let image_markdown = format!("![Image]({})", blob_url);
-                        post.content.push_str(&format!("\n\n{}\n\n", image_markdown));
-                        post.updated_at = chrono::Utc::now();
-                    }
-                    self.success_message = Some(format!("Image uploaded to Blossom server: {}", blob_url));
-                }
-                Err(e) => {
-                    // Fallback to local file path if upload fails
-                    tracing::warn!("Failed to upload to Blossom server: {}, using local path", e);
-                    if let Some(post) = self.editor.get_post_mut() {
-                        let image_markdown = format!("![Image]({})", path.display());
-                        post.content.push_str(&format!("\n\n{}\n\n", image_markdown));
-                        post.updated_at = chrono::Utc::now();
-                    }
-                    self.error_message = Some(format!("Failed to upload image to Blossom server: {}. Using local path instead.", e));
-                }
-            }
-            
-            self.is_loading = false;
-        }
+        self.start_image_upload(ImageUploadTarget::Inline);
     }
 
     fn upload_featured_image(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
+        self.start_image_upload(ImageUploadTarget::Featured);
+    }
+
+    /// Pick an image and upload it to Blossom in the background
+    fn start_image_upload(&mut self, target: ImageUploadTarget) {
+        if self.image_upload.is_some() {
+            self.error_message = Some("An image is already uploading".to_string());
+            return;
+        }
+        let Some(post_id) = self.editor.get_post().map(|post| post.id) else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
             .add_filter("Images", &["png", "jpg", "jpeg", "gif", "webp", "svg"])
             .pick_file()
-        {
-            self.is_loading = true;
-            let blossom_client = self.blossom_client.clone();
-            let path_clone = path.clone();
-            
-            // Upload image to Blossom server
-            let upload_future = async move {
-                blossom_client.upload_file(&path_clone).await
-            };
-            
-            match self.runtime.block_on(upload_future) {
-                Ok(blob_url) => {
-                    // Set the uploaded image URL as the featured image
-                    if let Some(post) = self.editor.get_post_mut() {
-                        post.image_url = Some(blob_url.clone());
-                        post.updated_at = chrono::Utc::now();
-                    }
-                    self.success_message = Some(format!("Featured image uploaded to Blossom server: {}", blob_url));
-                }
-                Err(e) => {
-                    // Fallback to local file path if upload fails
-                    tracing::warn!("Failed to upload featured image to Blossom server: {}, using local path", e);
-                    if let Some(post) = self.editor.get_post_mut() {
-                        post.image_url = Some(format!("file://{}", path.display()));
-                        post.updated_at = chrono::Utc::now();
-                    }
-                    self.error_message = Some(format!("Failed to upload featured image to Blossom server: {}. Using local path instead.", e));
-                }
+        else {
+            return;
+        };
+        
+        let label = match target {
+            ImageUploadTarget::Inline => "Uploading image",
+            ImageUploadTarget::Featured => "Uploading featured image",
+        };
+        let activity_guard = self.activity.begin_critical(label);
+        let (progress_sender, progress) = std::sync::mpsc::channel();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let blossom_client = self.blossom_client.clone();
+        let path_clone = path.clone();
+        
+        let task = self.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = blossom_client.upload_file(&path_clone, Some(progress_sender)).await.map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        
+        self.image_upload = Some(ImageUpload {
+            post_id,
+            target,
+            path,
+            sent: 0,
+            total: 0,
+            progress,
+            receiver,
+            task,
+        });
+    }
+    
+    /// Stop the running image upload; the post is left unchanged
+    fn cancel_image_upload(&mut self) {
+        if let Some(upload) = self.image_upload.take() {
+            upload.task.abort();
+            self.success_message = Some("Image upload cancelled".to_string());
+        }
+    }
+    
+    fn poll_image_upload(&mut self, ctx: &egui::Context) {
+        let Some(upload) = &mut self.image_upload else {
+            return;
+        };
+        while let Ok((sent, total)) = upload.progress.try_recv() {
+            upload.sent = sent;
+            upload.total = total;
+        }
+        
+        let result = match upload.receiver.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                return;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.image_upload = None;
+                return;
+            }
+        };
+        let Some(ImageUpload { post_id, target, path, .. }) = self.image_upload.take() else {
+            return;
+        };
+        
+        // Only touch the post the image was picked for
+        let Some(post) = self.editor.get_post_mut().filter(|post| post.id == post_id) else {
+            match result {
+                Ok(upload) => self.success_message = Some(format!("Image uploaded, but its post is no longer open: {}", upload.url)),
+                Err(e) => self.error_message = Some(format!("Failed to upload image to Blossom server: {}", e)),
+            }
+            return;
+        };
+        
+        match (result, target) {
+            (Ok(upload), ImageUploadTarget::Inline) => {
+                // Insert the uploaded image URL into the post
+                let image_markdown = format!("![Image]({})", upload.url);
+                post.content.push_str(&format!("\n\n{}\n\n", image_markdown));
+                post.updated_at = chrono::Utc::now();
+                self.success_message = Some(format!("Image uploaded to {} ({}): {}", upload.servers_summary(), upload.size_summary(), upload.url));
+            }
+            (Ok(upload), ImageUploadTarget::Featured) => {
+                // Set the uploaded image URL as the featured image
+                post.image_url = Some(upload.url.clone());
+                post.updated_at = chrono::Utc::now();
+                self.success_message = Some(format!("Featured image uploaded to {} ({}): {}", upload.servers_summary(), upload.size_summary(), upload.url));
+            }
+            (Err(e), ImageUploadTarget::Inline) => {
+                // Fallback to local file path if upload fails
+                tracing::warn!("Failed to upload to Blossom server: {}, using local path", e);
+                let image_markdown = format!("![Image]({})", path.display());
+                post.content.push_str(&format!("\n\n{}\n\n", image_markdown));
+                post.updated_at = chrono::Utc::now();
+                self.error_message = Some(format!("Failed to upload image to Blossom server: {}. Using local path instead.", e));
+            }
+            (Err(e), ImageUploadTarget::Featured) => {
+                // Fallback to local file path if upload fails
+                tracing::warn!("Failed to upload featured image to Blossom server: {}, using local path", e);
+                post.image_url = Some(format!("file://{}", path.display()));
+                post.updated_at = chrono::Utc::now();
+                self.error_message = Some(format!("Failed to upload featured image to Blossom server: {}. Using local path instead.", e));
             }
-            
-            self.is_loading = false;
         }
+        // Every outcome edited the post; let autosave pick it up like typing would
+        self.last_edit = Some(std::time::Instant::now());
     }
 }
 
 impl eframe::App for BlogsterApp {
+    fn clear_color(&self, visuals: &egui::Visuals) -> [f32; 4] {
+        // Translucent panels only let the desktop through if the window is cleared to transparent
+        if self.app_settings.window_opacity < 1.0 {
+            [0.0; 4]
+        } else {
+            visuals.panel_fill.to_normalized_gamma_f32()
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_system_theme(ctx);
+        if self.window_options_changed {
+            self.window_options_changed = false;
+            self.apply_window_options(ctx);
+        }
+        self.handle_shortcuts(ctx);
+        self.snapshot_for_recovery(ctx);
+        self.autosave(ctx);
+        self.show_recovery_prompt(ctx);
+        self.show_local_copy_conflict(ctx);
+        
         // Handle dialogs
-        self.credentials_dialog.show(ctx, &mut self.storage, &self.nostr_client, &self.runtime);
+        if self.credentials_dialog.show(ctx, &mut self.storage, &self.nostr_client, &self.relay_settings, &self.runtime, &self.activity) {
+            self.refresh_accounts();
+        }
         
         // Handle settings dialog
         let theme_colors = self.theme_colors();
         if let Some((new_theme, custom_colors)) = self.settings_dialog.show(ctx, &self.storage, &theme_colors, &self.current_theme, &self.custom_colors) {
             self.current_theme = new_theme;
+            if new_theme == Theme::System {
+                self.system_dark = system_prefers_dark();
+                self.last_system_theme_check = std::time::Instant::now();
+            }
             if let Some(colors) = custom_colors {
                 self.custom_colors = colors;
                 if let Err(e) = self.storage.save_custom_colors(&self.custom_colors) {
                     tracing::error!("Failed to save custom colors: {}", e);
                 }
             }
-            new_theme.apply(ctx);
+            self.shown_theme().apply_with(ctx, &self.custom_colors, self.system_dark);
+            self.window_options_changed = true;
             self.success_message = Some(format!("Theme changed to {}!", new_theme.name()));
         }
         
         // Handle relay dialog
-        if let Some(new_relay_settings) = self.relay_dialog.show(ctx, &self.storage, &theme_colors) {
+        if let Some(new_relay_settings) = self.relay_dialog.show(ctx, &self.storage, &self.nostr_client, &self.runtime, &theme_colors) {
             self.relay_settings = new_relay_settings;
+            self.reset_relay_connections(true);
             self.success_message = Some("Relay settings updated!".to_string());
         }
         
-        // Show Blossom settings dialog
-        if self.show_settings {
-            egui::Window::new("🌸 Blossom Settings")
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    ui.vertical(|ui| {
-                        ui.label("Configure your Blossom server for image uploads:");
-                        ui.add_space(10.0);
-
-                        ui.horizontal(|ui| {
-                            ui.label("Server URL:");
-                            ui.text_edit_singleline(&mut self.blossom_settings.server_url);
-                        });
-
-                        ui.add_space(10.0);
-                        ui.horizontal(|ui| {
-                            if ui.button("Save").clicked() {
-                                if let Err(e) = self.storage.save_blossom_settings(&self.blossom_settings) {
-                                    self.error_message = Some(format!("Failed to save Blossom settings: {}", e));
-                                } else {
-                                    self.blossom_client.update_settings(self.blossom_settings.clone());
-                                    self.success_message = Some("Blossom settings saved!".to_string());
-                                }
-                                self.show_settings = false;
-                            }
-
-                            if ui.button("Cancel").clicked() {
-                                // Reload settings from storage to discard changes
-                                if let Ok(settings) = self.storage.load_blossom_settings() {
-                                    self.blossom_settings = settings;
-                                }
-                                self.show_settings = false;
-                            }
+        // Handle preferences dialog
+        if let Some(new_app_settings) = self.preferences_dialog.show(ctx, &self.storage, &theme_colors) {
+            self.runtime.block_on(async {
+                let mut client = self.nostr_client.lock().await;
+                client.set_include_client_tag(new_app_settings.include_client_tag);
+                client.set_publish_timeout(std::time::Duration::from_secs(new_app_settings.publish_timeout_secs));
+            });
+            self.publish_dialog.set_confirm_before_publish(new_app_settings.confirm_before_publish);
+            self.blossom_client.set_strip_metadata(new_app_settings.strip_image_metadata);
+            self.editor.set_max_line_length(new_app_settings.max_line_length);
+            self.editor.set_tag_colors(new_app_settings.tag_colors.clone());
+            self.editor.set_date_format(new_app_settings.date_format.clone());
+            self.storage.set_recovery_dir(new_app_settings.recovery_dir.clone());
+            self.recovery_interval = std::time::Duration::from_secs(new_app_settings.recovery_interval_secs);
+            self.sidebar.set_tag_colors(new_app_settings.tag_colors.clone());
+            self.window_options_changed |= new_app_settings.always_on_top != self.app_settings.always_on_top
+                || new_app_settings.window_opacity != self.app_settings.window_opacity;
+            if new_app_settings.ui_font != self.app_settings.ui_font
+                || new_app_settings.editor_font != self.app_settings.editor_font
+                || new_app_settings.font_size != self.app_settings.font_size
+            {
+                let font_errors = apply_fonts(ctx, &new_app_settings.ui_font, &new_app_settings.editor_font, new_app_settings.font_size);
+                if !font_errors.is_empty() {
+                    self.error_message = Some(format!("Failed to load font: {}", font_errors.join("; ")));
+                }
+            }
+            self.app_settings = new_app_settings;
+            self.success_message = Some("Preferences updated!".to_string());
+        }
+        
+        // Handle recovery dialog
+        if let Some(recovered_post) = self.recover_dialog.show(ctx, &self.nostr_client, &self.relay_settings, &self.runtime, &self.activity, &theme_colors) {
+            // Show the fetched article first; "Edit a local copy" saves it
+            let title = recovered_post.title.clone();
+            self.sidebar.set_selected_post_id(None);
+            self.editor.set_post_read_only(recovered_post);
+            self.success_message = Some(format!("Fetched \"{}\" from relays", title));
+        }
+        
+        // Handle private post passphrase
+        if let Some((purpose, passphrase)) = self.passphrase_dialog.show(ctx, &theme_colors) {
+            // Derive the keys once for the session, sharing the salt of a post
+            // that's already encrypted so later saves and unlocks reuse them
+            let locked = match purpose {
+                PassphrasePurpose::Unlock(id) => self.posts.iter().find(|p| p.id == id && p.is_locked()),
+                PassphrasePurpose::Encrypt => self.posts.iter().find(|p| p.is_locked()),
+            };
+            let keys = crate::private_post::PrivateKeys::new(passphrase, locked.map(|p| p.content.as_str()));
+            // A new passphrase must open the posts that are already encrypted,
+            // or they would end up split across two passphrases
+            let mismatched = purpose == PassphrasePurpose::Encrypt
+                && keys.as_ref().is_ok_and(|keys| locked.is_some_and(|locked| keys.decrypt(&locked.content).is_err()));
+            let accepted = match keys {
+                Err(e) => {
+                    self.error_message = Some(format!("{:#}", e));
+                    false
+                }
+                Ok(_) if mismatched => {
+                    self.error_message = Some("That passphrase doesn't open your other private posts. Use the same passphrase for all of them.".to_string());
+                    self.passphrase_dialog.open(PassphrasePurpose::Encrypt);
+                    false
+                }
+                Ok(keys) => {
+                    self.storage.set_private_keys(Some(keys));
+                    true
+                }
+            };
+            match purpose {
+                _ if !accepted => {}
+                PassphrasePurpose::Unlock(id) => match self.unlock_private_post(id) {
+                    Ok(()) => self.open_post(id),
+                    Err(e) => {
+                        self.storage.set_private_keys(None);
+                        self.error_message = Some(format!("{:#}", e));
+                    }
+                },
+                PassphrasePurpose::Encrypt => {
+                    self.unlock_remaining_private_posts();
+                    if let Some(post) = self.editor.get_post().cloned() {
+                        self.save_post(post);
+                    }
+                }
+            }
+        }
+        
+        // Handle event lookup
+        if let Some(article) = self.event_lookup_dialog.show(ctx, &self.nostr_client, &self.relay_settings, &self.runtime, &self.activity, &theme_colors) {
+            let title = article.title.clone();
+            self.sidebar.set_selected_post_id(None);
+            self.editor.set_post_read_only(article);
+            self.success_message = Some(format!("Opened \"{}\" from relays", title));
+        }
+        
+        self.relay_test_dialog.show(ctx, &self.nostr_client, &self.relay_settings, &self.runtime, &self.activity, &theme_colors);
+        self.qr_dialog.show(ctx, &theme_colors);
+        self.profile_broadcast_dialog.show(ctx, &theme_colors);
+        self.blossom_uploads_dialog.show(ctx, &self.blossom_client, &self.runtime, &self.activity, &theme_colors);
+        self.naddr_dialog.show(ctx, &theme_colors);
+        self.import_report_dialog.show(ctx, &theme_colors);
+        self.shortcuts_dialog.show(ctx, &theme_colors);
+        
+        if let Some(post) = self.trash_dialog.show(ctx, &self.storage, &theme_colors) {
+            if !post.is_locked() {
+                self.writing_stats.seed([(post.id, post.word_count())]);
+            }
+            self.posts.push(post);
+        }
+        
+        if self.writing_stats_dialog.show(ctx, &mut self.writing_stats, &theme_colors) {
+            if let Err(e) = self.storage.save_writing_stats(&self.writing_stats) {
+                self.error_message = Some(format!("Failed to save writing stats: {}", e));
+            }
+        }
+        
+        let has_editable_post = self.editor.get_post().is_some() && !self.editor.is_read_only();
+        if let Some(command) = self.command_palette.show(ctx, has_editable_post, &theme_colors) {
+            self.run_command(command);
+        }
+        
+        // Handle search across all posts
+        if let Some((post_id, char_offset)) = self.search_dialog.show(ctx, &self.posts, &theme_colors) {
+            self.open_post(post_id);
+            if let Some(char_offset) = char_offset {
+                self.editor.jump_to(ctx, char_offset);
+            }
+        }
+        
+        // Handle first-run onboarding
+        if self.onboarding_dialog.is_open() {
+            let has_credentials = self.nostr_client
+                .try_lock()
+                .map(|client| client.has_credentials())
+                .unwrap_or(false);
+            
+            match self.onboarding_dialog.show(ctx, has_credentials, &theme_colors) {
+                OnboardingAction::None => {}
+                OnboardingAction::OpenCredentials => {
+                    self.credentials_dialog.open_with_storage(&self.storage);
+                }
+                OnboardingAction::OpenRelaySettings => {
+                    self.relay_dialog.open(&self.relay_settings);
+                }
+                OnboardingAction::OpenBlossomSettings => {
+                    self.blossom_dialog.open(&self.blossom_settings);
+                }
+                action @ (OnboardingAction::CreateFirstPost | OnboardingAction::Finished) => {
+                    if action == OnboardingAction::CreateFirstPost {
+                        self.create_new_post();
+                    }
+                    if let Err(e) = self.storage.mark_onboarding_complete() {
+                        tracing::warn!("Failed to save onboarding state: {}", e);
+                    }
+                }
+            }
+        }
+        
+        self.poll_blossom_server_sync(ctx);
+        self.poll_relay_deletions(ctx);
+        self.poll_draft_sync(ctx);
+        self.poll_account_switch(ctx);
+        self.publish_scheduled_posts(ctx);
+        self.poll_image_upload(ctx);
+        
+        // Handle Blossom settings dialog
+        let blossom_services = BlossomDialogServices {
+            nostr_client: &self.nostr_client,
+            relay_settings: &self.relay_settings,
+            runtime: &self.runtime,
+            activity: &self.activity,
+        };
+        if let Some(new_blossom_settings) = self.blossom_dialog.show(ctx, &self.storage, blossom_services, &theme_colors) {
+            self.blossom_client.update_settings(new_blossom_settings.clone());
+            self.blossom_settings = new_blossom_settings;
+            self.success_message = Some("Blossom settings saved!".to_string());
+        }
+        
+        let published = self.publish_dialog.show(ctx, &self.nostr_client, &self.relay_settings, &self.runtime, &self.activity);
+        
+        // Save a post's new publishing time
+        if let Some((post_id, scheduled_at)) = self.publish_dialog.take_schedule() {
+            self.scheduled_retries.remove(&post_id);
+            if let Some(post) = self.editor.get_post_mut().filter(|p| p.id == post_id) {
+                post.scheduled_at = scheduled_at;
+            }
+            if let Some(existing_post) = self.posts.iter_mut().find(|p| p.id == post_id) {
+                existing_post.scheduled_at = scheduled_at;
+                match self.storage.save_post(existing_post) {
+                    Ok(_) => {
+                        self.success_message = Some(match scheduled_at {
+                            Some(time) => format!("Scheduled for {}", time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")),
+                            None => "Schedule cancelled".to_string(),
                         });
-
-                        ui.add_space(10.0);
-                        ui.label("Default server: https://blossom.band");
-                        ui.label("You can use any Blossom-compatible server for image hosting.");
-                    });
-                });
+                    }
+                    Err(e) => self.error_message = Some(format!("Failed to save schedule: {}", e)),
+                }
+            } else if let Some(post) = self.editor.get_post().filter(|p| p.id == post_id).cloned() {
+                // Posts scheduled before their first save
+                self.save_post(post);
+            }
+        }
+        
+        // Remember per-post relay opt-outs chosen in the publish dialog
+        if let Some((post_id, excluded_relays, extra_relays)) = self.publish_dialog.take_relay_selection() {
+            if let Some(post) = self.editor.get_post_mut().filter(|p| p.id == post_id) {
+                post.excluded_relays = excluded_relays.clone();
+                post.extra_relays = extra_relays.clone();
+            }
+            if let Some(existing_post) = self.posts.iter_mut().find(|p| p.id == post_id) {
+                existing_post.excluded_relays = excluded_relays;
+                existing_post.extra_relays = extra_relays;
+                if let Err(e) = self.storage.save_post(existing_post) {
+                    tracing::warn!("Failed to save relay selection: {}", e);
+                }
+            }
         }
         
-        if let Some(published_post) = self.publish_dialog.show(ctx, &self.nostr_client, &self.relay_settings, &self.runtime) {
+        if let Some(mut published_post) = published {
+            // Save the new status, including posts published before their first save
+            match self.storage.save_post(&published_post) {
+                Ok(file_path) => {
+                    published_post.file_path = Some(file_path);
+                    self.success_message = Some(format!(
+                        "Post published to {} relay{}!",
+                        published_post.published_relays.len(),
+                        if published_post.published_relays.len() == 1 { "" } else { "s" }
+                    ));
+                }
+                Err(e) => {
+                    self.error_message = Some(format!("Failed to save published post: {}", e));
+                }
+            }
+            
             // Update the post in our list
             if let Some(existing_post) = self.posts.iter_mut().find(|p| p.id == published_post.id) {
                 *existing_post = published_post.clone();
-                
-                // Save the updated post
-                if let Err(e) = self.storage.save_post(&published_post) {
-                    self.error_message = Some(format!("Failed to save published post: {}", e));
-                } else {
-                    self.success_message = Some("Post published successfully!".to_string());
-                }
-                
-                // Update editor if this post is currently being edited
-                if let Some(current_post) = self.editor.get_post() {
-                    if current_post.id == published_post.id {
-                        self.editor.set_post(published_post);
-                    }
+            } else {
+                self.posts.push(published_post.clone());
+            }
+            
+            // Update editor if this post is currently being edited
+            if let Some(current_post) = self.editor.get_post() {
+                if current_post.id == published_post.id {
+                    self.editor.set_post(published_post);
                 }
             }
         }
@@ -545,6 +2511,7 @@ impl eframe::App for BlogsterApp {
         self.show_top_panel(ctx);
         
         // Bottom panel
+        self.expire_messages(ctx);
         self.show_bottom_panel(ctx);
         
         // Main content
@@ -560,4 +2527,36 @@ impl eframe::App for BlogsterApp {
             self.handle_editor_action(action);
         });
     }
+    
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Let publishes and uploads finish instead of cutting them off mid-write.
+        // Anything else is cancelled when the runtime is dropped with the app.
+        if self.activity.has_critical() {
+            tracing::info!("Waiting for in-flight publishes and uploads before exiting");
+            let activity = self.activity.clone();
+            let finished = self.runtime.block_on(async move {
+                tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, async {
+                    while activity.has_critical() {
+                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    }
+                })
+                .await
+                .is_ok()
+            });
+            if !finished {
+                tracing::warn!("Gave up waiting for background tasks after {:?}", SHUTDOWN_GRACE_PERIOD);
+            }
+        }
+        
+        // A clean exit means there is nothing to recover next time, except
+        // for snapshots the user hasn't decided about yet
+        for snapshot in self.storage.load_recovery_snapshots().unwrap_or_default() {
+            if self.pending_recoveries.iter().any(|p| p.id == snapshot.id) {
+                continue;
+            }
+            if let Err(e) = self.storage.clear_recovery_snapshot(snapshot.id) {
+                tracing::warn!("Failed to clear recovery snapshot: {}", e);
+            }
+        }
+    }
 }
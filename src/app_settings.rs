@@ -0,0 +1,192 @@
+use crate::fonts::{FontChoice, DEFAULT_FONT_SIZE};
+use crate::post::PostSort;
+use crate::preview::PreviewWidth;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// strftime pattern used when the configured date format is invalid
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Lowest window opacity offered, so the window never becomes invisible
+pub const MIN_WINDOW_OPACITY: f32 = 0.3;
+
+/// General application preferences that don't belong to a more specific
+/// settings file (theme, relays, Blossom).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Attach a NIP-89 `client` tag identifying Blogster to published events
+    pub include_client_tag: bool,
+    /// Ask for a final confirmation before broadcasting a post
+    pub confirm_before_publish: bool,
+    /// Overall time to wait for relays to acknowledge a publish
+    pub publish_timeout_secs: u64,
+    /// Remove EXIF/GPS metadata from images before uploading them
+    pub strip_image_metadata: bool,
+    /// Lines longer than this many characters are flagged in the editor
+    pub max_line_length: usize,
+    /// Colors picked for tags, keyed by lowercase tag name
+    pub tag_colors: HashMap<String, [u8; 3]>,
+    /// Seconds a success message stays in the status bar
+    pub success_dismiss_secs: u64,
+    /// Seconds an error message stays in the status bar; `None` keeps it until dismissed
+    pub error_dismiss_secs: Option<u64>,
+    /// strftime pattern for inserted dates (Ctrl+Shift+D and `{{date}}`)
+    pub date_format: String,
+    /// Title given to new posts; `{{date}}` is replaced with today's date
+    pub new_post_title: String,
+    /// Starting content for new posts; also supports `{{date}}`
+    pub new_post_content: String,
+    /// Last width chosen for previewing posts
+    pub preview_width: PreviewWidth,
+    /// Order of the posts in the sidebar
+    pub post_sort: PostSort,
+    /// Where crash-recovery snapshots are written; `None` uses `config_dir/recovery/`
+    pub recovery_dir: Option<PathBuf>,
+    /// Seconds between crash-recovery snapshots of unsaved edits
+    pub recovery_interval_secs: u64,
+    /// Save the open post automatically once editing pauses
+    pub autosave: bool,
+    /// Seconds without edits before the open post is autosaved
+    pub autosave_interval_secs: u64,
+    /// Add date, word count, reading time and tags to HTML exports
+    pub html_export_stats: bool,
+    /// Show troubleshooting actions such as test-publishing to a single relay
+    pub developer_tools: bool,
+    /// Keep the main window above other windows
+    pub always_on_top: bool,
+    /// Opacity of the main window, from `MIN_WINDOW_OPACITY` to 1.0
+    pub window_opacity: f32,
+    /// Font for the interface
+    pub ui_font: FontChoice,
+    /// Monospace font for the editor and code
+    pub editor_font: FontChoice,
+    /// Body text size in points; other text is scaled to match
+    pub font_size: f32,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            include_client_tag: true,
+            confirm_before_publish: true,
+            publish_timeout_secs: 30,
+            strip_image_metadata: true,
+            max_line_length: 1000,
+            tag_colors: HashMap::new(),
+            success_dismiss_secs: 4,
+            error_dismiss_secs: None,
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            new_post_title: "New Post".to_string(),
+            new_post_content: String::new(),
+            preview_width: PreviewWidth::Full,
+            post_sort: PostSort::default(),
+            recovery_dir: None,
+            recovery_interval_secs: 10,
+            autosave: true,
+            autosave_interval_secs: 30,
+            html_export_stats: true,
+            developer_tools: false,
+            always_on_top: false,
+            window_opacity: 1.0,
+            ui_font: FontChoice::Default,
+            editor_font: FontChoice::Default,
+            font_size: DEFAULT_FONT_SIZE,
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Title and content for a post created on `date`
+    pub fn new_post_defaults(&self, date: chrono::NaiveDate) -> (String, String) {
+        let date = format_date(&self.date_format, date);
+        (
+            self.new_post_title.replace("{{date}}", &date),
+            self.new_post_content.replace("{{date}}", &date),
+        )
+    }
+}
+
+/// Render `date` with `format`, or `None` if the pattern is invalid or asks
+/// for something a date doesn't have (like `%H`)
+fn try_format_date(format: &str, date: chrono::NaiveDate) -> Option<String> {
+    use std::fmt::Write;
+
+    if format.trim().is_empty() {
+        return None;
+    }
+    let mut formatted = String::new();
+    write!(formatted, "{}", date.format(format)).ok()?;
+    Some(formatted)
+}
+
+/// Whether `format` is a strftime pattern that can render a date
+pub fn is_valid_date_format(format: &str) -> bool {
+    try_format_date(format, chrono::Local::now().date_naive()).is_some()
+}
+
+/// Format `date` with `format`, falling back to ISO dates for invalid patterns
+pub fn format_date(format: &str, date: chrono::NaiveDate) -> String {
+    try_format_date(format, date).unwrap_or_else(|| date.format(DEFAULT_DATE_FORMAT).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings: AppSettings = serde_json::from_str("{}").unwrap();
+        assert!(settings.include_client_tag);
+        assert!(settings.confirm_before_publish);
+        assert_eq!(settings.publish_timeout_secs, 30);
+        assert!(settings.strip_image_metadata);
+        assert_eq!(settings.max_line_length, 1000);
+        assert!(settings.tag_colors.is_empty());
+        assert_eq!(settings.success_dismiss_secs, 4);
+        assert_eq!(settings.error_dismiss_secs, None);
+        assert_eq!(settings.date_format, DEFAULT_DATE_FORMAT);
+        assert_eq!(settings.new_post_title, "New Post");
+        assert!(settings.new_post_content.is_empty());
+        assert_eq!(settings.preview_width, PreviewWidth::Full);
+        assert_eq!(settings.post_sort, PostSort::default());
+        assert_eq!(settings.recovery_dir, None);
+        assert_eq!(settings.recovery_interval_secs, 10);
+        assert!(settings.autosave);
+        assert_eq!(settings.autosave_interval_secs, 30);
+        assert!(settings.html_export_stats);
+        assert!(!settings.developer_tools);
+        assert_eq!(settings.ui_font, FontChoice::Default);
+        assert_eq!(settings.editor_font, FontChoice::Default);
+        assert_eq!(settings.font_size, DEFAULT_FONT_SIZE);
+    }
+
+    #[test]
+    fn test_new_post_defaults_expand_date() {
+        let settings = AppSettings {
+            new_post_title: "Notes {{date}}".to_string(),
+            new_post_content: "# Draft from {{date}}\n\n".to_string(),
+            ..AppSettings::default()
+        };
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+
+        let (title, content) = settings.new_post_defaults(date);
+        assert_eq!(title, "Notes 2024-05-01");
+        assert_eq!(content, "# Draft from 2024-05-01\n\n");
+    }
+
+    #[test]
+    fn test_format_date_falls_back_on_invalid_pattern() {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        assert_eq!(format_date("%d/%m/%Y", date), "01/05/2024");
+        assert_eq!(format_date("%B %-d, %Y", date), "May 1, 2024");
+        assert_eq!(format_date("%Q", date), "2024-05-01");
+        assert_eq!(format_date("%H:%M", date), "2024-05-01");
+        assert_eq!(format_date("", date), "2024-05-01");
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
 use base64::{engine::general_purpose, Engine as _};
+use crate::image_metadata::{downscale, strip_metadata};
 use crate::nostr_client::NostrClient;
-use nostr_sdk::{EventBuilder, Kind, Tag, Timestamp, JsonUtil};
+use nostr_sdk::{Event, EventBuilder, Kind, Tag, Timestamp, JsonUtil};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::path::Path;
@@ -9,26 +10,284 @@ use std::sync::Arc;
 use tokio::fs;
 use tokio::sync::Mutex;
 
+/// Well-known public Blossom servers offered in settings
+pub const BLOSSOM_PRESETS: &[&str] = &[
+    "https://blossom.band",
+    "https://blossom.primal.net",
+    "https://blossom.nostr.build",
+    "https://cdn.satellite.earth",
+    "https://nostr.download",
+];
+
+/// Seconds an upload authorization stays valid: long enough to reuse one
+/// signature for the upload and every mirror
+const AUTH_LIFETIME_SECS: u64 = 3600;
+
+/// Sign a new authorization rather than reuse one this close to expiring
+const AUTH_EXPIRY_MARGIN_SECS: u64 = 60;
+
+/// Upload bodies are streamed in pieces this size so progress can be reported
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Receives `(bytes sent, total bytes)` while an upload is in flight
+pub type UploadProgress = std::sync::mpsc::Sender<(u64, u64)>;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "StoredBlossomSettings")]
 pub struct BlossomSettings {
-    pub server_url: String,
+    /// Upload servers in order of preference; later ones are fallbacks
+    pub servers: Vec<String>,
+    /// Replaces the built-in preset list when not empty
+    pub preset_servers: Vec<String>,
+    /// Copy each upload to the remaining servers (BUD-04 mirroring)
+    pub mirror: bool,
+    pub image_processing: ImageProcessing,
 }
 
 impl Default for BlossomSettings {
     fn default() -> Self {
         Self {
-            server_url: "https://blossom.band".to_string(),
+            servers: vec!["https://blossom.band".to_string()],
+            preset_servers: Vec::new(),
+            mirror: false,
+            image_processing: ImageProcessing::default(),
+        }
+    }
+}
+
+/// How images are shrunk before upload
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageProcessing {
+    /// Downscale images larger than `max_dimension`
+    pub enabled: bool,
+    /// Longest side, in pixels, of an uploaded image
+    pub max_dimension: u32,
+    /// JPEG quality (1-100) for downscaled photos
+    pub jpeg_quality: u8,
+}
+
+impl Default for ImageProcessing {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_dimension: 2048,
+            jpeg_quality: 85,
+        }
+    }
+}
+
+/// Blossom settings as saved on disk, including the older layout with a
+/// single upload server and a separate mirror list
+#[derive(Deserialize)]
+struct StoredBlossomSettings {
+    #[serde(default)]
+    servers: Vec<String>,
+    #[serde(default)]
+    preset_servers: Vec<String>,
+    #[serde(default)]
+    mirror: Option<bool>,
+    #[serde(default)]
+    image_processing: ImageProcessing,
+    #[serde(default)]
+    server_url: Option<String>,
+    #[serde(default)]
+    mirror_servers: Vec<String>,
+}
+
+impl From<StoredBlossomSettings> for BlossomSettings {
+    fn from(stored: StoredBlossomSettings) -> Self {
+        let mirror = stored.mirror.unwrap_or(!stored.mirror_servers.is_empty());
+        let mut servers = stored.servers;
+        if servers.is_empty() {
+            servers = stored.server_url.into_iter().chain(stored.mirror_servers).collect();
+        }
+        if servers.is_empty() {
+            servers = BlossomSettings::default().servers;
+        }
+
+        Self {
+            servers,
+            preset_servers: stored.preset_servers,
+            mirror,
+            image_processing: stored.image_processing,
+        }
+    }
+}
+
+impl BlossomSettings {
+    /// Servers to offer in the presets dropdown
+    pub fn presets(&self) -> Vec<String> {
+        if self.preset_servers.is_empty() {
+            BLOSSOM_PRESETS.iter().map(|s| s.to_string()).collect()
+        } else {
+            self.preset_servers.clone()
+        }
+    }
+
+    /// The servers in upload order without blanks, duplicates or invalid
+    /// URLs, as published in the BUD-03 user server list
+    pub fn servers(&self) -> Vec<String> {
+        normalize_server_list(self.servers.iter().cloned())
+    }
+
+    /// Replace the server list; an empty or entirely invalid list is ignored
+    pub fn set_servers(&mut self, servers: Vec<String>) {
+        let servers = normalize_server_list(servers);
+        if !servers.is_empty() {
+            self.servers = servers;
+        }
+    }
+}
+
+/// Trim and de-duplicate server URLs, dropping anything that isn't an
+/// http(s) URL with a host
+pub fn normalize_server_list(servers: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for server in servers {
+        let server = server.trim().trim_end_matches('/').to_string();
+        let valid = reqwest::Url::parse(&server)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some());
+        if !valid {
+            if !server.is_empty() {
+                tracing::warn!("Ignoring invalid Blossom server URL: {}", server);
+            }
+            continue;
+        }
+        if !normalized.contains(&server) {
+            normalized.push(server);
         }
     }
+    normalized
+}
+
+/// A signed BUD-02 upload authorization for one blob.
+///
+/// Servers check the kind, the `upload` verb, the `x` hash and the
+/// expiration, none of which depend on the server, so one signature can be
+/// sent to the upload server and every mirror. Servers that scope tokens
+/// with `server` tags only accept tokens listing their domain, so every
+/// target is listed when signing. A server that still answers 401 gets an
+/// authorization signed for it alone.
+#[derive(Debug, Clone)]
+pub struct UploadAuth {
+    /// Value for the `Authorization` header
+    pub header: String,
+    pub sha256: String,
+    pub expiration: u64,
+}
+
+impl UploadAuth {
+    /// Whether the authorization expires too soon to send again
+    pub fn is_expired(&self, now: u64) -> bool {
+        now + AUTH_EXPIRY_MARGIN_SECS >= self.expiration
+    }
+}
+
+/// Lowercase domain of a server URL, as used in `server` tags
+fn server_domain(server_url: &str) -> Option<String> {
+    reqwest::Url::parse(server_url)
+        .ok()?
+        .host_str()
+        .map(|host| host.to_lowercase())
 }
 
-#[derive(Debug, Deserialize)]
-pub struct BlossomUploadResponse {
+/// Unsigned kind 24242 authorization to `verb` (`upload`, `delete`) the
+/// blob `sha256_hex` on `servers`
+fn auth_builder(verb: &str, sha256_hex: &str, content: String, expiration: u64, servers: &[String]) -> Result<EventBuilder> {
+    let mut tags = vec![
+        Tag::parse(&["t", verb])?,
+        Tag::parse(&["x", sha256_hex])?,
+        Tag::parse(&["expiration", &expiration.to_string()])?,
+    ];
+    for domain in servers.iter().filter_map(|server| server_domain(server)) {
+        tags.push(Tag::parse(&["server", &domain])?);
+    }
+
+    Ok(EventBuilder::new(Kind::Custom(24242), content, tags))
+}
+
+/// Unsigned kind 24242 authorization to upload the blob `sha256_hex` to `servers`
+fn upload_auth_builder(sha256_hex: &str, filename: &str, expiration: u64, servers: &[String]) -> Result<EventBuilder> {
+    auth_builder("upload", sha256_hex, format!("Upload {}", filename), expiration, servers)
+}
+
+/// Request body that streams `content` and reports each piece as it is sent
+fn progress_body(content: &[u8], progress: Option<UploadProgress>) -> reqwest::Body {
+    let total = content.len() as u64;
+    let mut sent = 0u64;
+    let chunks: Vec<Vec<u8>> = content.chunks(UPLOAD_CHUNK_SIZE).map(<[u8]>::to_vec).collect();
+    let stream = futures_util::stream::iter(chunks.into_iter().map(move |chunk| {
+        sent += chunk.len() as u64;
+        if let Some(progress) = &progress {
+            let _ = progress.send((sent, total));
+        }
+        Ok::<_, std::io::Error>(chunk)
+    }));
+    reqwest::Body::wrap_stream(stream)
+}
+
+/// `Authorization` header value carrying a signed authorization event
+fn encode_auth_header(event: &Event) -> String {
+    format!("Nostr {}", general_purpose::STANDARD.encode(event.as_json()))
+}
+
+/// Where an uploaded blob ended up
+#[derive(Debug, Clone)]
+pub struct BlossomUpload {
+    /// URL of the blob on the first server that accepted it
+    pub url: String,
+    pub server: String,
+    /// Servers that also hold a copy
+    pub mirrored_to: Vec<String>,
+    /// Size of the file as picked, in bytes
+    pub original_size: u64,
+    /// Size of what was uploaded after stripping and downscaling, in bytes
+    pub uploaded_size: u64,
+}
+
+impl BlossomUpload {
+    /// Which servers hold the blob, for status messages
+    pub fn servers_summary(&self) -> String {
+        if self.mirrored_to.is_empty() {
+            self.server.clone()
+        } else {
+            format!("{} (mirrored to {})", self.server, self.mirrored_to.join(", "))
+        }
+    }
+
+    /// The upload size, and the original size if processing shrank the file
+    pub fn size_summary(&self) -> String {
+        if self.uploaded_size < self.original_size {
+            format!("{} → {}", format_size(self.original_size), format_size(self.uploaded_size))
+        } else {
+            format_size(self.uploaded_size)
+        }
+    }
+}
+
+/// Human-readable file size
+pub fn format_size(bytes: u64) -> String {
+    if bytes >= 1_000_000 {
+        format!("{:.1} MB", bytes as f64 / 1_000_000.0)
+    } else if bytes >= 1_000 {
+        format!("{} KB", bytes / 1_000)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// A blob as described by a Blossom server (BUD-02)
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlobDescriptor {
     pub url: String,
     pub sha256: String,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub content_type: String,
     pub size: u64,
+    /// Unix time the blob was uploaded
+    #[serde(default)]
+    pub uploaded: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -36,6 +295,7 @@ pub struct BlossomClient {
     settings: BlossomSettings,
     client: reqwest::Client,
     nostr_client: Option<Arc<Mutex<NostrClient>>>,
+    strip_metadata: bool,
 }
 
 impl BlossomClient {
@@ -44,47 +304,103 @@ impl BlossomClient {
             settings,
             client: reqwest::Client::new(),
             nostr_client: None,
+            strip_metadata: true,
         }
     }
 
+    /// Remove EXIF and similar metadata from images before uploading them
+    pub fn set_strip_metadata(&mut self, enabled: bool) {
+        self.strip_metadata = enabled;
+    }
+
     pub fn set_nostr_client(&mut self, nostr_client: Arc<Mutex<NostrClient>>) {
         self.nostr_client = Some(nostr_client);
     }
 
-    async fn create_auth_header(&self, file_content: &[u8], filename: &str) -> Result<String> {
-        let hash = Sha256::digest(file_content);
-        let hash_hex = format!("{:x}", hash);
-        
-        let content = format!("Upload {}", filename);
-        
-        // Create authorization event according to BUD-02 specification
-        // Must use Kind 24242 for Blossom authorization with expiration
-        let expiration = Timestamp::now().as_u64() + 600; // 10 minutes from now
-        let tags = vec![
-            Tag::parse(&["t", "upload"])?,
-            Tag::parse(&["x", &hash_hex])?,
-            Tag::parse(&["expiration", &expiration.to_string()])?,
-        ];
-        
-        let event_builder = EventBuilder::new(Kind::Custom(24242), content, tags);
-        
-        if let Some(client) = &self.nostr_client {
-            let client_guard = client.lock().await;
-            let event = client_guard.sign_event_builder(event_builder).await?;
-            let event_json = event.as_json();
-            let encoded = general_purpose::STANDARD.encode(&event_json);
-            Ok(format!("Nostr {}", encoded))
-        } else {
+    /// Sign an upload authorization for the blob `sha256_hex`, valid on all of `servers`
+    async fn create_auth_header(&self, sha256_hex: &str, filename: &str, servers: &[String]) -> Result<UploadAuth> {
+        let expiration = Timestamp::now().as_u64() + AUTH_LIFETIME_SECS;
+        let event_builder = upload_auth_builder(sha256_hex, filename, expiration, servers)?;
+        self.sign_auth(event_builder, sha256_hex, expiration).await
+    }
+
+    /// Sign an authorization to delete the blob `sha256_hex` from all of `servers`
+    async fn create_delete_auth(&self, sha256_hex: &str, servers: &[String]) -> Result<UploadAuth> {
+        let expiration = Timestamp::now().as_u64() + AUTH_LIFETIME_SECS;
+        let event_builder = auth_builder("delete", sha256_hex, format!("Delete {}", sha256_hex), expiration, servers)?;
+        self.sign_auth(event_builder, sha256_hex, expiration).await
+    }
+
+    async fn sign_auth(&self, event_builder: EventBuilder, sha256_hex: &str, expiration: u64) -> Result<UploadAuth> {
+        let Some(client) = &self.nostr_client else {
             anyhow::bail!("No Nostr client available for authorization")
-        }
+        };
+        let event = client.lock().await.sign_event_builder(event_builder).await?;
+
+        Ok(UploadAuth {
+            header: encode_auth_header(&event),
+            sha256: sha256_hex.to_string(),
+            expiration,
+        })
     }
 
-    pub async fn upload_file(&self, file_path: &Path) -> Result<String> {
+    /// Upload a file to the first server that accepts it, trying the
+    /// servers in order, then mirror it to the servers after that one if
+    /// mirroring is on. Bytes sent are reported to `progress`, starting
+    /// over for each server tried.
+    pub async fn upload_file(&self, file_path: &Path, progress: Option<UploadProgress>) -> Result<BlossomUpload> {
         // Read the file
-        let file_content = fs::read(file_path).await
+        let mut file_content = fs::read(file_path).await
             .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+        let original_size = file_content.len() as u64;
+
+        // Strip location and device metadata. This changes the bytes, so it
+        // has to happen before hashing.
+        if self.strip_metadata {
+            if let Ok(format) = image::ImageFormat::from_path(file_path) {
+                let stripped = strip_metadata(&file_content, format).context(
+                    "Failed to remove image metadata. Turn off metadata stripping in Preferences to upload the file as is",
+                )?;
+                if let Some(stripped) = stripped {
+                    tracing::info!("Removed metadata from {}", file_path.display());
+                    file_content = stripped;
+                }
+            }
+        }
 
-        // Calculate SHA256 hash
+        // Shrink large images. SVGs and GIFs are left as they are.
+        let processing = self.settings.image_processing;
+        if processing.enabled {
+            if let Ok(format) = image::ImageFormat::from_path(file_path) {
+                // Decoding and re-encoding a large photo takes a while; keep it off the async workers
+                let (original, downscaled) = tokio::task::spawn_blocking(move || {
+                    let downscaled = downscale(&file_content, format, processing.max_dimension, processing.jpeg_quality);
+                    (file_content, downscaled)
+                })
+                .await
+                .context("Image downscaling stopped unexpectedly")?;
+                file_content = original;
+
+                match downscaled {
+                    Ok(Some(downscaled)) => {
+                        tracing::info!(
+                            "Downscaled {} from {} to {} bytes",
+                            file_path.display(),
+                            file_content.len(),
+                            downscaled.len()
+                        );
+                        file_content = downscaled;
+                    }
+                    Ok(None) => {}
+                    // The image can still be uploaded as it is
+                    Err(e) => tracing::warn!("Uploading {} at its original size: {:#}", file_path.display(), e),
+                }
+            }
+        }
+        let uploaded_size = file_content.len() as u64;
+
+        // Calculate SHA256 hash over what is actually sent, so the
+        // authorization's x tag matches
         let mut hasher = Sha256::new();
         hasher.update(&file_content);
         let hash = hasher.finalize();
@@ -111,25 +427,92 @@ impl BlossomClient {
             _ => "application/octet-stream",
         };
 
-        // Upload to Blossom server
-        let upload_url = format!("{}/upload", self.settings.server_url);
-        
-        tracing::info!("Uploading file to Blossom server: {}", upload_url);
+        let servers = self.settings.servers();
+        if servers.is_empty() {
+            anyhow::bail!("No Blossom servers configured");
+        }
 
-        // Create authorization header according to BUD-02 spec
-        let auth_header = self.create_auth_header(&file_content, &file_name).await
+        // One authorization covers every server (BUD-02)
+        let mut auth = self.create_auth_header(&sha256_hex, &file_name, &servers).await
             .context("Failed to create authorization header")?;
 
-        tracing::debug!("Using Blossom authorization header: {}", auth_header);
+        tracing::debug!("Using Blossom authorization header: {}", auth.header);
+
+        let mut failures = Vec::new();
+        let mut uploaded = None;
+        for (index, server) in servers.iter().enumerate() {
+            match self.upload_to(server, &file_content, content_type, &auth, progress.clone()).await {
+                Ok(upload_response) => {
+                    uploaded = Some((index, upload_response));
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!("Upload to {} failed: {:#}", server, e);
+                    failures.push(format!("{}: {:#}", server, e));
+                }
+            }
+        }
+        let Some((index, upload_response)) = uploaded else {
+            anyhow::bail!("Every Blossom server failed. {}", failures.join("; "));
+        };
+
+        tracing::info!(
+            "Successfully uploaded file. URL: {}, SHA256: {}",
+            upload_response.url,
+            upload_response.sha256
+        );
+
+        // Verify SHA256 hash matches
+        if upload_response.sha256 != sha256_hex {
+            tracing::warn!(
+                "SHA256 mismatch: expected {}, got {}",
+                sha256_hex,
+                upload_response.sha256
+            );
+        }
+
+        // Mirroring is best effort; the upload itself already succeeded.
+        // Servers before the one that took the upload just failed, so skip them.
+        let mut mirrored_to = Vec::new();
+        if self.settings.mirror {
+            for mirror in &servers[index + 1..] {
+                match self.mirror_blob(mirror, &upload_response.url, &file_name, &mut auth).await {
+                    Ok(()) => mirrored_to.push(mirror.clone()),
+                    Err(e) => tracing::warn!("Failed to mirror {} to {}: {:#}", upload_response.url, mirror, e),
+                }
+            }
+        }
+
+        Ok(BlossomUpload {
+            url: upload_response.url,
+            server: servers[index].clone(),
+            mirrored_to,
+            original_size,
+            uploaded_size,
+        })
+    }
+
+    /// Send a blob to one server's upload endpoint (BUD-02)
+    async fn upload_to(
+        &self,
+        server: &str,
+        file_content: &[u8],
+        content_type: &str,
+        auth: &UploadAuth,
+        progress: Option<UploadProgress>,
+    ) -> Result<BlobDescriptor> {
+        let upload_url = format!("{}/upload", server);
+
+        tracing::info!("Uploading file to Blossom server: {}", upload_url);
 
         // Send binary data as request body according to BUD-02 specification
         let response = self
             .client
             .put(&upload_url)
-            .header("Authorization", auth_header)
+            .header("Authorization", auth.header.clone())
             .header("Content-Type", content_type)
             .header("Content-Length", file_content.len())
-            .body(file_content)
+            .body(progress_body(file_content, progress))
             .send()
             .await
             .context("Failed to upload file to Blossom server")?;
@@ -144,31 +527,146 @@ impl BlossomClient {
             ));
         }
 
-        let upload_response: BlossomUploadResponse = response
+        response
             .json()
             .await
-            .context("Failed to parse Blossom upload response")?;
+            .context("Failed to parse Blossom upload response")
+    }
 
-        tracing::info!(
-            "Successfully uploaded file. URL: {}, SHA256: {}",
-            upload_response.url,
-            upload_response.sha256
-        );
+    /// Ask `server` to copy a blob from `blob_url` (BUD-04), reusing `auth`
+    /// while it is valid and signing a new one when it isn't accepted
+    async fn mirror_blob(&self, server: &str, blob_url: &str, file_name: &str, auth: &mut UploadAuth) -> Result<()> {
+        let sha256 = auth.sha256.clone();
+        if auth.is_expired(Timestamp::now().as_u64()) {
+            *auth = self.create_auth_header(&sha256, file_name, &[server.to_string()]).await?;
+        }
 
-        // Verify SHA256 hash matches
-        if upload_response.sha256 != sha256_hex {
-            tracing::warn!(
-                "SHA256 mismatch: expected {}, got {}",
-                sha256_hex,
-                upload_response.sha256
-            );
+        let mirror_url = format!("{}/mirror", server);
+        let body = serde_json::json!({ "url": blob_url });
+
+        let mut response = self.client.put(&mirror_url).header("Authorization", auth.header.clone()).json(&body).send().await
+            .with_context(|| format!("Failed to reach {}", server))?;
+
+        // Some servers only accept authorizations scoped to them alone
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            tracing::debug!("{} rejected the shared authorization, signing one for it", server);
+            let server_auth = self.create_auth_header(&sha256, file_name, &[server.to_string()]).await?;
+            response = self.client.put(&mirror_url).header("Authorization", server_auth.header).json(&body).send().await
+                .with_context(|| format!("Failed to reach {}", server))?;
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Mirror failed with status {}: {}", status, error_text);
+        }
+
+        tracing::info!("Mirrored {} to {}", blob_url, server);
+        Ok(())
+    }
+
+    /// Blobs uploaded by `pubkey` (hex) to any of the configured servers,
+    /// newest first. A blob kept on several servers is listed once per server.
+    pub async fn list_blobs(&self, pubkey: &str) -> Result<Vec<BlobDescriptor>> {
+        let mut blobs = Vec::new();
+        let mut failures = Vec::new();
+        for server in self.settings.servers() {
+            match self.list_blobs_on(&server, pubkey).await {
+                Ok(server_blobs) => blobs.extend(server_blobs),
+                Err(e) => {
+                    tracing::warn!("Failed to list blobs on {}: {:#}", server, e);
+                    failures.push(format!("{}: {:#}", server, e));
+                }
+            }
+        }
+
+        if blobs.is_empty() && !failures.is_empty() {
+            anyhow::bail!("Could not list uploads. {}", failures.join("; "));
+        }
+        blobs.sort_by_key(|blob| std::cmp::Reverse(blob.uploaded));
+        Ok(blobs)
+    }
+
+    async fn list_blobs_on(&self, server: &str, pubkey: &str) -> Result<Vec<BlobDescriptor>> {
+        let response = self.client.get(format!("{}/list/{}", server, pubkey)).send().await
+            .with_context(|| format!("Failed to reach {}", server))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("List failed with status {}: {}", status, error_text);
+        }
+
+        response.json().await.context("Failed to parse blob list")
+    }
+
+    /// Delete the blob `sha256_hex` from every configured server that has it
+    /// (BUD-02). Returns the servers it was deleted from.
+    pub async fn delete_blob(&self, sha256_hex: &str) -> Result<Vec<String>> {
+        let servers = self.settings.servers();
+        let auth = self.create_delete_auth(sha256_hex, &servers).await
+            .context("Failed to create authorization header")?;
+
+        let mut deleted = Vec::new();
+        let mut failures = Vec::new();
+        for server in servers {
+            let result = self.client
+                .delete(format!("{}/{}", server, sha256_hex))
+                .header("Authorization", auth.header.clone())
+                .send()
+                .await;
+            match result {
+                Ok(response) if response.status().is_success() => {
+                    tracing::info!("Deleted blob {} from {}", sha256_hex, server);
+                    deleted.push(server);
+                }
+                // The blob was never on this server
+                Ok(response) if response.status() == reqwest::StatusCode::NOT_FOUND => {}
+                Ok(response) => {
+                    let status = response.status();
+                    let error_text = response.text().await.unwrap_or_default();
+                    failures.push(format!("{}: {} {}", server, status, error_text));
+                }
+                Err(e) => failures.push(format!("{}: {}", server, e)),
+            }
         }
 
-        Ok(upload_response.url)
+        if deleted.is_empty() {
+            if failures.is_empty() {
+                anyhow::bail!("None of your servers has this blob");
+            }
+            anyhow::bail!("Delete failed. {}", failures.join("; "));
+        }
+        for failure in &failures {
+            tracing::warn!("Failed to delete blob {} from {}", sha256_hex, failure);
+        }
+        Ok(deleted)
+    }
+
+    /// Check that a server answers HTTP requests at all. Blossom servers don't
+    /// have to serve anything at their root, so any response counts.
+    pub async fn check_server(server_url: &str) -> Result<()> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        let response = client
+            .head(server_url)
+            .send()
+            .await
+            .with_context(|| format!("Could not reach {}", server_url))?;
+
+        if response.status().is_server_error() {
+            anyhow::bail!("{} responded with {}", server_url, response.status());
+        }
+
+        Ok(())
     }
 
+    /// The server uploads are tried on first
     pub fn get_server_url(&self) -> &str {
-        &self.settings.server_url
+        self.settings.servers.first().map(String::as_str).unwrap_or_default()
     }
 
     pub fn update_settings(&mut self, settings: BlossomSettings) {
@@ -183,13 +681,113 @@ mod tests {
     #[test]
     fn test_default_blossom_settings() {
         let settings = BlossomSettings::default();
-        assert_eq!(settings.server_url, "https://blossom.band");
+        assert_eq!(settings.servers, vec!["https://blossom.band".to_string()]);
+        assert!(!settings.mirror);
+        assert!(settings.image_processing.enabled);
+        assert_eq!(settings.image_processing.max_dimension, 2048);
+    }
+
+    #[test]
+    fn test_preset_override() {
+        let mut settings: BlossomSettings = serde_json::from_str(r#"{"server_url":"https://blossom.band"}"#).unwrap();
+        assert_eq!(settings.presets().len(), BLOSSOM_PRESETS.len());
+
+        settings.preset_servers = vec!["https://my.blossom".to_string()];
+        assert_eq!(settings.presets(), vec!["https://my.blossom".to_string()]);
     }
 
     #[test]
     fn test_blossom_client_creation() {
         let settings = BlossomSettings::default();
         let client = BlossomClient::new(settings.clone());
-        assert_eq!(client.get_server_url(), &settings.server_url);
+        assert_eq!(client.get_server_url(), "https://blossom.band");
+    }
+
+    #[test]
+    fn test_upload_auth_header() {
+        let keys = nostr_sdk::Keys::generate();
+        let hash = "a".repeat(64);
+        let servers = vec!["https://blossom.band".to_string(), "https://CDN.Example.com/".to_string()];
+
+        let event = upload_auth_builder(&hash, "photo.png", 1_700_000_600, &servers)
+            .unwrap()
+            .to_event(&keys)
+            .unwrap();
+        let header = encode_auth_header(&event);
+
+        let encoded = header.strip_prefix("Nostr ").unwrap();
+        let decoded = Event::from_json(general_purpose::STANDARD.decode(encoded).unwrap()).unwrap();
+        assert!(decoded.verify().is_ok());
+        assert_eq!(decoded.kind.as_u16(), 24242);
+
+        let tags: Vec<Vec<String>> = decoded.tags.iter().map(|tag| tag.as_vec().to_vec()).collect();
+        assert!(tags.contains(&vec!["t".to_string(), "upload".to_string()]));
+        assert!(tags.contains(&vec!["x".to_string(), hash.clone()]));
+        assert!(tags.contains(&vec!["expiration".to_string(), "1700000600".to_string()]));
+        assert!(tags.contains(&vec!["server".to_string(), "cdn.example.com".to_string()]));
+
+        let auth = UploadAuth { header, sha256: hash, expiration: 1_700_000_600 };
+        assert!(!auth.is_expired(1_700_000_000));
+        assert!(auth.is_expired(1_700_000_590));
+    }
+
+    #[test]
+    fn test_delete_auth_and_blob_list() {
+        let keys = nostr_sdk::Keys::generate();
+        let hash = "b".repeat(64);
+        let event = auth_builder("delete", &hash, "Delete".to_string(), 1_700_000_600, &["https://blossom.band".to_string()])
+            .unwrap()
+            .to_event(&keys)
+            .unwrap();
+        let tags: Vec<Vec<String>> = event.tags.iter().map(|tag| tag.as_vec().to_vec()).collect();
+        assert!(tags.contains(&vec!["t".to_string(), "delete".to_string()]));
+        assert!(tags.contains(&vec!["x".to_string(), hash.clone()]));
+
+        let blobs: Vec<BlobDescriptor> = serde_json::from_str(&format!(
+            r#"[{{"url":"https://blossom.band/{0}.png","sha256":"{0}","size":2048,"type":"image/png","uploaded":1700000000}},
+                {{"url":"https://blossom.band/{0}","sha256":"{0}","size":10}}]"#,
+            hash
+        ))
+        .unwrap();
+        assert_eq!(blobs[0].content_type, "image/png");
+        assert_eq!(blobs[0].uploaded, Some(1_700_000_000));
+        assert_eq!(blobs[1].uploaded, None);
+    }
+
+    #[test]
+    fn test_single_server_settings_migrate() {
+        let settings: BlossomSettings = serde_json::from_str(
+            r#"{"server_url":"https://blossom.band","mirror_servers":["https://blossom.band/"," https://nostr.download ","","https://nostr.download/"]}"#,
+        )
+        .unwrap();
+        assert!(settings.mirror);
+        assert_eq!(settings.servers(), vec!["https://blossom.band".to_string(), "https://nostr.download".to_string()]);
+
+        let settings: BlossomSettings = serde_json::from_str(r#"{"server_url":"https://cdn.example.com"}"#).unwrap();
+        assert!(!settings.mirror);
+        assert_eq!(settings.servers, vec!["https://cdn.example.com".to_string()]);
+
+        let saved = serde_json::to_string(&settings).unwrap();
+        let reloaded: BlossomSettings = serde_json::from_str(&saved).unwrap();
+        assert_eq!(reloaded.servers, settings.servers);
+    }
+
+    #[test]
+    fn test_server_list_round_trip() {
+        let mut settings = BlossomSettings::default();
+        settings.set_servers(vec![
+            "https://cdn.example.com/".to_string(),
+            "not a url".to_string(),
+            "ftp://files.example.com".to_string(),
+            "https://blossom.band".to_string(),
+            "https://cdn.example.com".to_string(),
+        ]);
+
+        assert_eq!(settings.servers, vec!["https://cdn.example.com".to_string(), "https://blossom.band".to_string()]);
+        assert_eq!(settings.servers(), settings.servers);
+
+        // An empty or invalid list leaves the settings alone
+        settings.set_servers(vec!["nope".to_string()]);
+        assert_eq!(settings.servers[0], "https://cdn.example.com");
     }
 }
@@ -0,0 +1,377 @@
+use crate::activity::ActivityTracker;
+use crate::blossom_client::{normalize_server_list, BlossomClient, BlossomSettings};
+use crate::nostr_client::NostrClient;
+use crate::relay_settings::RelaySettings;
+use crate::storage::Storage;
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, Window};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// What the dialog needs to check servers and sync the server list in the
+/// background
+pub struct BlossomDialogServices<'a> {
+    pub nostr_client: &'a Arc<Mutex<NostrClient>>,
+    pub relay_settings: &'a RelaySettings,
+    pub runtime: &'a tokio::runtime::Runtime,
+    pub activity: &'a ActivityTracker,
+}
+
+/// Edits the Blossom servers used for image uploads and how images are prepared
+#[derive(Default)]
+pub struct BlossomDialog {
+    open: bool,
+    blossom_settings: BlossomSettings,
+    /// Server URL typed in, not yet added to the list
+    new_server: String,
+    error_message: Option<String>,
+    success_message: Option<String>,
+    /// Server being checked for reachability
+    server_check: Option<(String, mpsc::Receiver<Result<(), String>>)>,
+    /// BUD-03 server list being fetched from the relays
+    server_list_fetch: Option<mpsc::Receiver<Result<Option<Vec<String>>, String>>>,
+    /// BUD-03 server list being published; reports how many relays took it
+    server_list_publish: Option<mpsc::Receiver<Result<usize, String>>>,
+}
+
+impl BlossomDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, current_settings: &BlossomSettings) {
+        self.open = true;
+        self.blossom_settings = current_settings.clone();
+        self.new_server.clear();
+        self.error_message = None;
+        self.success_message = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Add the typed server after checking it is an http(s) URL
+    fn add_server(&mut self) {
+        let server = self.new_server.trim().to_string();
+        if server.is_empty() {
+            self.error_message = Some("Please enter a server URL".to_string());
+            return;
+        }
+        if !server.starts_with("http://") && !server.starts_with("https://") {
+            self.error_message = Some("Server URLs must start with http:// or https://".to_string());
+            return;
+        }
+
+        match normalize_server_list([server]).into_iter().next() {
+            Some(server) if self.blossom_settings.servers.contains(&server) => {
+                self.error_message = Some("That server is already in the list".to_string());
+            }
+            Some(server) => {
+                self.blossom_settings.servers.push(server);
+                self.new_server.clear();
+                self.error_message = None;
+            }
+            None => {
+                self.error_message = Some("Enter a valid http(s) server URL".to_string());
+            }
+        }
+    }
+
+    fn start_server_check(&mut self, server: String, runtime: &tokio::runtime::Runtime, activity: &ActivityTracker) {
+        let (sender, receiver) = mpsc::channel();
+        let activity_guard = activity.begin("Checking Blossom server");
+        let server_url = server.clone();
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = BlossomClient::check_server(&server_url).await.map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        self.server_check = Some((server, receiver));
+        self.error_message = None;
+        self.success_message = None;
+    }
+
+    fn start_server_list_fetch(&mut self, services: &BlossomDialogServices) {
+        let (sender, receiver) = mpsc::channel();
+        let client = services.nostr_client.clone();
+        let relay_settings = services.relay_settings.clone();
+        let activity_guard = services.activity.begin("Fetching Blossom server list");
+        services.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = client.lock().await.fetch_blossom_servers(&relay_settings).await.map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        self.server_list_fetch = Some(receiver);
+    }
+
+    fn start_server_list_publish(&mut self, services: &BlossomDialogServices) {
+        let servers = self.blossom_settings.servers();
+        let (sender, receiver) = mpsc::channel();
+        let client = services.nostr_client.clone();
+        let relay_settings = services.relay_settings.clone();
+        let activity_guard = services.activity.begin("Publishing Blossom server list");
+        services.runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = client.lock().await
+                .publish_blossom_servers(&servers, &relay_settings)
+                .await
+                .map(|report| report.successful_relays.len())
+                .map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        self.server_list_publish = Some(receiver);
+    }
+
+    /// Pick up finished reachability checks and server list syncs
+    fn poll_background_work(&mut self, ctx: &Context) {
+        if let Some((server, receiver)) = &self.server_check {
+            match receiver.try_recv() {
+                Ok(Ok(())) => {
+                    self.success_message = Some(format!("{} is reachable", server));
+                    self.server_check = None;
+                }
+                Ok(Err(e)) => {
+                    self.error_message = Some(e);
+                    self.server_check = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.server_check = None;
+                }
+            }
+        }
+
+        if let Some(receiver) = &self.server_list_fetch {
+            match receiver.try_recv() {
+                Ok(Ok(Some(servers))) if !normalize_server_list(servers.clone()).is_empty() => {
+                    self.server_list_fetch = None;
+                    self.blossom_settings.set_servers(servers);
+                    self.error_message = None;
+                    self.success_message = Some(format!(
+                        "Loaded {} server(s) from Nostr, save to keep them",
+                        self.blossom_settings.servers().len()
+                    ));
+                }
+                Ok(Ok(_)) => {
+                    self.server_list_fetch = None;
+                    self.error_message = Some("No valid Blossom server list found on your relays".to_string());
+                }
+                Ok(Err(e)) => {
+                    self.server_list_fetch = None;
+                    self.error_message = Some(format!("Failed to fetch Blossom server list: {}", e));
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.server_list_fetch = None;
+                }
+            }
+        }
+
+        if let Some(receiver) = &self.server_list_publish {
+            match receiver.try_recv() {
+                Ok(Ok(relay_count)) => {
+                    self.server_list_publish = None;
+                    self.error_message = None;
+                    self.success_message = Some(format!("Server list published to {} relay(s)", relay_count));
+                }
+                Ok(Err(e)) => {
+                    self.server_list_publish = None;
+                    self.error_message = Some(format!("Failed to publish Blossom server list: {}", e));
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.server_list_publish = None;
+                }
+            }
+        }
+    }
+
+    /// Returns the new Blossom settings once they have been saved
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        storage: &Storage,
+        services: BlossomDialogServices,
+        theme_colors: &ThemeColors,
+    ) -> Option<BlossomSettings> {
+        if !self.open {
+            return None;
+        }
+
+        self.poll_background_work(ctx);
+
+        let mut result = None;
+        let mut should_close = false;
+        let mut add_clicked = false;
+        let mut to_check = None;
+        let mut fetch_clicked = false;
+        let mut publish_clicked = false;
+        let mut window_open = self.open;
+
+        Window::new("🌸 Blossom Settings")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.label("Blossom servers for image uploads, tried in order until one works:");
+                    ui.add_space(10.0);
+
+                    let mut move_up = None;
+                    let mut to_remove = None;
+                    let server_count = self.blossom_settings.servers.len();
+                    for (index, server) in self.blossom_settings.servers.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(format!("{}.", index + 1)).color(theme_colors.text_muted));
+                            ui.label(RichText::new(server).monospace());
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.add_enabled(server_count > 1, egui::Button::new(RichText::new("🗑").color(theme_colors.error)))
+                                    .on_disabled_hover_text("At least one server is needed")
+                                    .clicked()
+                                {
+                                    to_remove = Some(index);
+                                }
+                                if ui.add_enabled(index + 1 < server_count, egui::Button::new("⬇")).clicked() {
+                                    move_up = Some(index + 1);
+                                }
+                                if ui.add_enabled(index > 0, egui::Button::new("⬆")).on_hover_text("Try this server earlier").clicked() {
+                                    move_up = Some(index);
+                                }
+                                if self.server_check.as_ref().is_some_and(|(checking, _)| checking == server) {
+                                    ui.spinner();
+                                } else if ui.add_enabled(self.server_check.is_none(), egui::Button::new("🔌"))
+                                    .on_hover_text("Check that the server is reachable")
+                                    .clicked()
+                                {
+                                    to_check = Some(server.clone());
+                                }
+                            });
+                        });
+                    }
+                    if let Some(index) = move_up {
+                        self.blossom_settings.servers.swap(index - 1, index);
+                    }
+                    if let Some(index) = to_remove {
+                        self.blossom_settings.servers.remove(index);
+                    }
+
+                    ui.horizontal(|ui| {
+                        let presets: Vec<String> = self.blossom_settings.presets()
+                            .into_iter()
+                            .filter(|preset| !self.blossom_settings.servers.contains(preset))
+                            .collect();
+                        egui::ComboBox::from_id_source("blossom_presets")
+                            .selected_text("Add preset")
+                            .show_ui(ui, |ui| {
+                                for preset in presets {
+                                    if ui.selectable_label(false, &preset).clicked() {
+                                        self.blossom_settings.servers.push(preset);
+                                    }
+                                }
+                            });
+
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut self.new_server)
+                                .hint_text("https://blossom.example.com")
+                                .desired_width(200.0),
+                        );
+                        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        if ui.button("➕ Add").clicked() || submitted {
+                            add_clicked = true;
+                        }
+                    });
+
+                    if let Some(error) = &self.error_message {
+                        ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                    }
+                    if let Some(success) = &self.success_message {
+                        ui.colored_label(theme_colors.success, format!("✅ {}", success));
+                    }
+
+                    ui.add_space(10.0);
+                    ui.checkbox(&mut self.blossom_settings.mirror, "Mirror uploads to the other servers");
+                    ui.label(RichText::new("Uploaded images are also copied to the servers after the one that accepted them (BUD-04). One signed authorization is shared by all of them.").small().color(theme_colors.text_muted));
+
+                    ui.add_space(10.0);
+                    let processing = &mut self.blossom_settings.image_processing;
+                    ui.checkbox(&mut processing.enabled, "Downscale large images before upload");
+                    ui.add_enabled_ui(processing.enabled, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Longest side:");
+                            ui.add(egui::DragValue::new(&mut processing.max_dimension).range(256..=8192).suffix(" px"));
+                            ui.label("JPEG quality:");
+                            ui.add(egui::Slider::new(&mut processing.jpeg_quality, 40..=100));
+                        });
+                    });
+                    ui.label(RichText::new("Only images larger than this are re-encoded. SVGs and GIFs are uploaded as they are.").small().color(theme_colors.text_muted));
+
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        let syncing = self.server_list_fetch.is_some() || self.server_list_publish.is_some();
+                        ui.add_enabled_ui(!syncing, |ui| {
+                            if ui.button("⬆ Publish server list").on_hover_text("Publish these servers as your BUD-03 server list (kind 10063) so other clients and devices can find them").clicked() {
+                                publish_clicked = true;
+                            }
+                            if ui.button("⬇ Load from Nostr").on_hover_text("Replace these servers with the server list published from any of your devices").clicked() {
+                                fetch_clicked = true;
+                            }
+                        });
+                        if syncing {
+                            ui.spinner();
+                        }
+                    });
+
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if ui.button(RichText::new("💾 Save").color(theme_colors.success)).clicked() {
+                            self.blossom_settings.set_servers(self.blossom_settings.servers.clone());
+                            if let Err(e) = storage.save_blossom_settings(&self.blossom_settings) {
+                                tracing::error!("Failed to save Blossom settings: {}", e);
+                                self.error_message = Some("Failed to save Blossom settings".to_string());
+                            } else {
+                                result = Some(self.blossom_settings.clone());
+                                should_close = true;
+                            }
+                        }
+
+                        if ui.button(RichText::new("❌ Cancel").color(theme_colors.error)).clicked() {
+                            should_close = true;
+                        }
+                    });
+
+                    ui.add_space(10.0);
+                    ui.label("Default server: https://blossom.band");
+                    ui.label("You can use any Blossom-compatible server for image hosting.");
+                    ui.label(RichText::new("Presets can be replaced with your own list via \"preset_servers\" in blossom_settings.json.").small().color(theme_colors.text_muted));
+                });
+            });
+
+        self.open = window_open && !should_close;
+
+        if add_clicked {
+            self.add_server();
+        }
+
+        if let Some(server) = to_check {
+            self.start_server_check(server, services.runtime, services.activity);
+        }
+
+        if fetch_clicked {
+            self.start_server_list_fetch(&services);
+        }
+
+        if publish_clicked {
+            self.start_server_list_publish(&services);
+        }
+
+        result
+    }
+}
@@ -0,0 +1,207 @@
+use crate::activity::ActivityTracker;
+use crate::blossom_client::{format_size, BlobDescriptor, BlossomClient};
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, Window};
+use std::sync::mpsc;
+
+/// Lists the blobs the user uploaded to their Blossom servers and deletes them
+#[derive(Default)]
+pub struct BlossomUploadsDialog {
+    open: bool,
+    /// Hex public key the blobs were uploaded with
+    public_key: String,
+    blobs: Vec<BlobDescriptor>,
+    error_message: Option<String>,
+    success_message: Option<String>,
+    loading: Option<mpsc::Receiver<Result<Vec<BlobDescriptor>, String>>>,
+    /// Blob waiting for the user to confirm its deletion
+    confirm_delete: Option<BlobDescriptor>,
+    /// Hash of the blob being deleted and the servers it was removed from
+    deleting: Option<(String, mpsc::Receiver<Result<Vec<String>, String>>)>,
+}
+
+impl BlossomUploadsDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Open the dialog and fetch the blob list straight away
+    pub fn open(&mut self, public_key: String, blossom_client: &BlossomClient, runtime: &tokio::runtime::Runtime, activity: &ActivityTracker) {
+        self.open = true;
+        self.public_key = public_key;
+        self.confirm_delete = None;
+        self.refresh(blossom_client, runtime, activity);
+    }
+
+    fn refresh(&mut self, blossom_client: &BlossomClient, runtime: &tokio::runtime::Runtime, activity: &ActivityTracker) {
+        if self.loading.is_some() {
+            return;
+        }
+        self.error_message = None;
+
+        let (sender, receiver) = mpsc::channel();
+        let client = blossom_client.clone();
+        let public_key = self.public_key.clone();
+        let activity_guard = activity.begin("Listing Blossom uploads");
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = client.list_blobs(&public_key).await.map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        self.loading = Some(receiver);
+    }
+
+    fn start_delete(&mut self, blob: BlobDescriptor, blossom_client: &BlossomClient, runtime: &tokio::runtime::Runtime, activity: &ActivityTracker) {
+        let (sender, receiver) = mpsc::channel();
+        let client = blossom_client.clone();
+        let sha256 = blob.sha256.clone();
+        let activity_guard = activity.begin_critical("Deleting Blossom upload");
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = client.delete_blob(&sha256).await.map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        self.deleting = Some((blob.sha256, receiver));
+        self.error_message = None;
+        self.success_message = None;
+    }
+
+    fn poll(&mut self, ctx: &Context) {
+        if let Some(receiver) = &self.loading {
+            match receiver.try_recv() {
+                Ok(Ok(blobs)) => {
+                    self.loading = None;
+                    self.blobs = blobs;
+                }
+                Ok(Err(e)) => {
+                    self.loading = None;
+                    self.error_message = Some(e);
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.loading = None;
+                }
+            }
+        }
+
+        if let Some((sha256, receiver)) = &self.deleting {
+            match receiver.try_recv() {
+                Ok(Ok(servers)) => {
+                    let sha256 = sha256.clone();
+                    self.blobs.retain(|blob| blob.sha256 != sha256);
+                    self.success_message = Some(format!("Deleted from {}", servers.join(", ")));
+                    self.deleting = None;
+                }
+                Ok(Err(e)) => {
+                    self.error_message = Some(e);
+                    self.deleting = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.deleting = None;
+                }
+            }
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        blossom_client: &BlossomClient,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+        theme_colors: &ThemeColors,
+    ) {
+        self.poll(ctx);
+
+        if !self.open {
+            return;
+        }
+
+        let mut window_open = self.open;
+        let mut refresh = false;
+        let mut to_delete = None;
+
+        Window::new("🗂 Manage Uploads")
+            .open(&mut window_open)
+            .collapsible(false)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{} blob(s) on your Blossom servers", self.blobs.len())).color(theme_colors.text));
+                    if self.loading.is_some() {
+                        ui.spinner();
+                    } else if ui.button("🔄 Refresh").clicked() {
+                        refresh = true;
+                    }
+                });
+
+                if let Some(error) = &self.error_message {
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                }
+                if let Some(success) = &self.success_message {
+                    ui.colored_label(theme_colors.success, format!("✅ {}", success));
+                }
+                ui.add_space(6.0);
+
+                egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    for blob in &self.blobs {
+                        ui.horizontal(|ui| {
+                            ui.hyperlink_to(RichText::new(&blob.url).small(), &blob.url);
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                let deleting = self.deleting.as_ref().is_some_and(|(sha256, _)| *sha256 == blob.sha256);
+                                if deleting {
+                                    ui.spinner();
+                                } else if ui
+                                    .add_enabled(self.deleting.is_none(), egui::Button::new(RichText::new("🗑").color(theme_colors.error)))
+                                    .on_hover_text("Delete this blob from your servers")
+                                    .clicked()
+                                {
+                                    self.confirm_delete = Some(blob.clone());
+                                }
+                                ui.label(RichText::new(format_size(blob.size)).small().color(theme_colors.text_muted));
+                                if !blob.content_type.is_empty() {
+                                    ui.label(RichText::new(&blob.content_type).small().color(theme_colors.text_muted));
+                                }
+                            });
+                        });
+                    }
+                });
+
+                if let Some(blob) = self.confirm_delete.clone() {
+                    ui.separator();
+                    ui.colored_label(
+                        theme_colors.warning,
+                        "⚠ Posts that still link to this image will show it as missing. Delete it anyway?",
+                    );
+                    ui.label(RichText::new(&blob.url).small().monospace());
+                    ui.horizontal(|ui| {
+                        if ui.button(RichText::new("🗑 Delete").color(theme_colors.error)).clicked() {
+                            to_delete = Some(blob);
+                            self.confirm_delete = None;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.confirm_delete = None;
+                        }
+                    });
+                }
+            });
+
+        if refresh {
+            self.refresh(blossom_client, runtime, activity);
+        }
+        if let Some(blob) = to_delete {
+            self.start_delete(blob, blossom_client, runtime, activity);
+        }
+
+        self.open = window_open;
+    }
+}
@@ -0,0 +1,82 @@
+use crate::theme::ThemeColors;
+use egui::{RichText, Ui};
+
+/// A piece of Markdown syntax shown in the cheat sheet
+pub struct CheatSheetEntry {
+    pub name: &'static str,
+    /// Inserted at the cursor when the entry is clicked
+    pub snippet: &'static str,
+    /// Block elements go on their own lines, separated by blank lines
+    pub block: bool,
+}
+
+const fn inline(name: &'static str, snippet: &'static str) -> CheatSheetEntry {
+    CheatSheetEntry { name, snippet, block: false }
+}
+
+const fn block(name: &'static str, snippet: &'static str) -> CheatSheetEntry {
+    CheatSheetEntry { name, snippet, block: true }
+}
+
+pub const CHEAT_SHEET: &[(&str, &[CheatSheetEntry])] = &[
+    (
+        "Headings",
+        &[
+            block("Heading", "## Heading"),
+            block("Subheading", "### Subheading"),
+        ],
+    ),
+    (
+        "Text",
+        &[
+            inline("Bold", "**bold**"),
+            inline("Italic", "*italic*"),
+            inline("Strikethrough", "~~struck~~"),
+            inline("Inline code", "`code`"),
+        ],
+    ),
+    (
+        "Links & images",
+        &[
+            inline("Link", "[link text](https://example.com)"),
+            inline("Image", "![description](https://example.com/image.png)"),
+            inline("Nostr mention", "nostr:npub1..."),
+        ],
+    ),
+    (
+        "Blocks",
+        &[
+            block("Bullet list", "- First item\n- Second item"),
+            block("Numbered list", "1. First step\n2. Second step"),
+            block("Quote", "> Quoted text"),
+            block("Code block", "```\ncode\n```"),
+            block("Table", "| Column | Column |\n| ------ | ------ |\n| Cell   | Cell   |"),
+            block("Horizontal rule", "---"),
+        ],
+    ),
+];
+
+/// List common Markdown syntax. Returns the entry the user clicked.
+pub fn show_cheat_sheet(ui: &mut Ui, theme_colors: &ThemeColors) -> Option<&'static CheatSheetEntry> {
+    let mut clicked = None;
+
+    ui.label(RichText::new("Markdown").strong().color(theme_colors.primary));
+    ui.label(RichText::new("Click an example to insert it at the cursor.").small().color(theme_colors.text_muted));
+
+    egui::ScrollArea::vertical().id_source("cheat_sheet").show(ui, |ui| {
+        for (section, entries) in CHEAT_SHEET {
+            ui.add_space(6.0);
+            ui.label(RichText::new(*section).strong().color(theme_colors.text_secondary));
+            for entry in entries.iter() {
+                let response = ui
+                    .add(egui::Button::new(RichText::new(entry.snippet).monospace().small()).wrap())
+                    .on_hover_text(entry.name);
+                if response.clicked() {
+                    clicked = Some(entry);
+                }
+            }
+        }
+    });
+
+    clicked
+}
@@ -0,0 +1,224 @@
+use crate::search::fuzzy_score;
+use crate::theme::ThemeColors;
+use egui::{Align2, Context, Key, Modifiers, RichText, TextEdit, Window};
+
+/// An action that can be run from the command palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteCommand {
+    NewPost,
+    SavePost,
+    PublishPost,
+    TogglePreview,
+    DuplicatePost,
+    DeletePost,
+    ExportPost,
+    ExportPostHtml,
+    ExportSite,
+    ImportPost,
+    OpenPostsFolder,
+    SearchPosts,
+    Trash,
+    Appearance,
+    Preferences,
+    Credentials,
+    RelaySettings,
+    BlossomSettings,
+    ReconnectRelays,
+    LookUpEvent,
+    RecoverPost,
+    WritingStats,
+    KeyboardShortcuts,
+}
+
+impl PaletteCommand {
+    pub const ALL: [PaletteCommand; 23] = [
+        PaletteCommand::NewPost,
+        PaletteCommand::SavePost,
+        PaletteCommand::PublishPost,
+        PaletteCommand::TogglePreview,
+        PaletteCommand::DuplicatePost,
+        PaletteCommand::DeletePost,
+        PaletteCommand::ExportPost,
+        PaletteCommand::ExportPostHtml,
+        PaletteCommand::ExportSite,
+        PaletteCommand::ImportPost,
+        PaletteCommand::OpenPostsFolder,
+        PaletteCommand::SearchPosts,
+        PaletteCommand::Trash,
+        PaletteCommand::Appearance,
+        PaletteCommand::Preferences,
+        PaletteCommand::Credentials,
+        PaletteCommand::RelaySettings,
+        PaletteCommand::BlossomSettings,
+        PaletteCommand::ReconnectRelays,
+        PaletteCommand::LookUpEvent,
+        PaletteCommand::RecoverPost,
+        PaletteCommand::WritingStats,
+        PaletteCommand::KeyboardShortcuts,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaletteCommand::NewPost => "➕ New Post",
+            PaletteCommand::SavePost => "💾 Save Post",
+            PaletteCommand::PublishPost => "🚀 Publish Post",
+            PaletteCommand::TogglePreview => "👁 Toggle Preview",
+            PaletteCommand::DuplicatePost => "📑 Duplicate Post",
+            PaletteCommand::DeletePost => "🗑️ Delete Post",
+            PaletteCommand::ExportPost => "📤 Export Post",
+            PaletteCommand::ExportPostHtml => "🌐 Export Post as HTML",
+            PaletteCommand::ExportSite => "🌍 Export Static Site",
+            PaletteCommand::ImportPost => "📥 Import Post",
+            PaletteCommand::OpenPostsFolder => "📁 Open Posts Folder",
+            PaletteCommand::SearchPosts => "🔎 Search All Posts",
+            PaletteCommand::Trash => "🗑 Trash",
+            PaletteCommand::Appearance => "🎨 Theme and Appearance",
+            PaletteCommand::Preferences => "🛠 Preferences",
+            PaletteCommand::Credentials => "🔑 Nostr Credentials",
+            PaletteCommand::RelaySettings => "📡 Relay Settings",
+            PaletteCommand::BlossomSettings => "🌸 Blossom Settings",
+            PaletteCommand::ReconnectRelays => "🔄 Reconnect Relays",
+            PaletteCommand::LookUpEvent => "🔎 Look Up Event",
+            PaletteCommand::RecoverPost => "🛟 Recover Published Post",
+            PaletteCommand::WritingStats => "📊 Writing Stats",
+            PaletteCommand::KeyboardShortcuts => "⌨ Keyboard Shortcuts",
+        }
+    }
+
+    /// Whether the command acts on the post open in the editor
+    pub fn needs_post(&self) -> bool {
+        matches!(
+            self,
+            PaletteCommand::SavePost
+                | PaletteCommand::PublishPost
+                | PaletteCommand::TogglePreview
+                | PaletteCommand::DuplicatePost
+                | PaletteCommand::DeletePost
+                | PaletteCommand::ExportPost
+                | PaletteCommand::ExportPostHtml
+        )
+    }
+}
+
+/// Commands matching `query`, best match first
+fn matching_commands(query: &str, has_editable_post: bool) -> Vec<PaletteCommand> {
+    let mut scored: Vec<(u32, PaletteCommand)> = PaletteCommand::ALL
+        .into_iter()
+        .filter(|command| has_editable_post || !command.needs_post())
+        .filter_map(|command| fuzzy_score(query, command.label()).map(|score| (score, command)))
+        .collect();
+    // Stable, so equally good matches keep their listed order
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, command)| command).collect()
+}
+
+/// Fuzzy-filtered list of every action, opened with Ctrl+Shift+P
+#[derive(Default)]
+pub struct CommandPalette {
+    open: bool,
+    query: String,
+    /// Index into the filtered commands
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns the command the user picked, after which the palette closes
+    pub fn show(&mut self, ctx: &Context, has_editable_post: bool, theme_colors: &ThemeColors) -> Option<PaletteCommand> {
+        if !self.open {
+            return None;
+        }
+
+        // Take the navigation keys before the query field can use them
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+        if escape {
+            self.open = false;
+            return None;
+        }
+
+        let mut commands = matching_commands(&self.query, has_editable_post);
+        if down && !commands.is_empty() {
+            self.selected = (self.selected + 1) % commands.len();
+        }
+        if up && !commands.is_empty() {
+            self.selected = (self.selected + commands.len() - 1) % commands.len();
+        }
+
+        let mut chosen = None;
+
+        Window::new("Command Palette")
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_TOP, [0.0, 60.0])
+            .fixed_size([420.0, 0.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.query)
+                        .hint_text("Type a command…")
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.selected = 0;
+                    commands = matching_commands(&self.query, has_editable_post);
+                }
+                ui.add_space(4.0);
+
+                if commands.is_empty() {
+                    ui.label(RichText::new("No matching commands").color(theme_colors.text_muted));
+                }
+
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    // Full-width rows with the labels on the left
+                    ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
+                        for (index, command) in commands.iter().enumerate() {
+                            let selected = index == self.selected;
+                            let row = ui.selectable_label(selected, RichText::new(command.label()).color(theme_colors.text));
+                            if selected && (up || down) {
+                                row.scroll_to_me(None);
+                            }
+                            if row.clicked() {
+                                chosen = Some(*command);
+                            }
+                        }
+                    });
+                });
+
+                ui.add_space(4.0);
+                ui.label(
+                    RichText::new("↑↓ to choose · Enter to run · Esc to close")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+            });
+
+        if enter {
+            chosen = commands.get(self.selected).copied();
+        }
+        if chosen.is_some() {
+            self.open = false;
+        }
+        chosen
+    }
+}
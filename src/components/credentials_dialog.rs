@@ -1,33 +1,119 @@
-use crate::nostr_client::NostrClient;
+use crate::activity::ActivityTracker;
+use crate::credentials_file::CredentialsLocked;
+use crate::nostr_client::{NostrClient, ProfileNotFound, PublishReport};
 use crate::post::NostrCredentials;
-use crate::storage::Storage;
+use crate::relay_settings::RelaySettings;
+use crate::storage::{Storage, DEFAULT_ACCOUNT};
 use crate::theme::CatppuccinMocha;
 use egui::{RichText, Window};
+use std::sync::mpsc;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// How far checking the NIP-05 identifier against the public key has got
+enum Nip05State {
+    Checking(mpsc::Receiver<Result<bool, String>>),
+    Verified,
+    /// The identifier resolves to a different public key
+    Mismatch,
+    Failed(String),
+}
+
+/// A check of one NIP-05 identifier against one public key
+struct Nip05Check {
+    nip05: String,
+    public_key: String,
+    state: Nip05State,
+}
+
+/// A profile field that can be filled from the relays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProfileField {
+    DisplayName,
+    About,
+    Picture,
+    Nip05,
+}
+
+impl ProfileField {
+    fn label(&self) -> &'static str {
+        match self {
+            ProfileField::DisplayName => "display name",
+            ProfileField::About => "about",
+            ProfileField::Picture => "picture",
+            ProfileField::Nip05 => "NIP-05",
+        }
+    }
+}
+
+/// Profile fields as published on the relays
+type FetchedProfile = Vec<(ProfileField, Option<String>)>;
+
 pub struct CredentialsDialog {
     open: bool,
+    /// Label the credentials are saved under
+    account: String,
+    /// Accounts with saved credentials, offered to load
+    saved_accounts: Vec<String>,
+    /// Sign with a NIP-46 remote signer instead of a local private key
+    use_remote_signer: bool,
+    bunker_uri: String,
+    /// Remote signer being reached: the account label and the connected credentials
+    connecting: Option<(String, mpsc::Receiver<Result<NostrCredentials, String>>)>,
     private_key: String,
+    /// Passphrase for the encrypted credentials file
+    passphrase: String,
+    /// The saved credentials are encrypted and waiting for the passphrase
+    locked: bool,
     display_name: String,
     about: String,
     picture: String,
     nip05: String,
+    nip05_check: Option<Nip05Check>,
+    /// Profile being fetched from the relays; `None` inside when there's none
+    fetching_profile: Option<mpsc::Receiver<Result<Option<FetchedProfile>, String>>>,
+    /// Fetched values that would replace what's in the form, waiting for
+    /// the user to confirm
+    profile_conflicts: Vec<(ProfileField, String)>,
+    /// Profile (kind 0) event being sent to the relays; the error is `None`
+    /// when the relays have no profile to update
+    publishing_profile: Option<mpsc::Receiver<Result<PublishReport, Option<String>>>>,
+    /// No profile was found to update; waiting for the user to publish the
+    /// form as a new profile
+    confirm_new_profile: bool,
+    /// Id of the last profile event published from the dialog
+    published_profile_id: Option<String>,
     error_message: Option<String>,
     success_message: Option<String>,
+    /// Public key of the saved identity awaiting delete confirmation
+    confirm_delete: Option<String>,
 }
 
 impl Default for CredentialsDialog {
     fn default() -> Self {
         Self {
             open: false,
+            account: DEFAULT_ACCOUNT.to_string(),
+            saved_accounts: Vec::new(),
+            use_remote_signer: false,
+            bunker_uri: String::new(),
+            connecting: None,
             private_key: String::new(),
+            passphrase: String::new(),
+            locked: false,
             display_name: String::new(),
             about: String::new(),
             picture: String::new(),
             nip05: String::new(),
+            nip05_check: None,
+            fetching_profile: None,
+            profile_conflicts: Vec::new(),
+            publishing_profile: None,
+            confirm_new_profile: false,
+            published_profile_id: None,
             error_message: None,
             success_message: None,
+            confirm_delete: None,
         }
     }
 }
@@ -47,21 +133,25 @@ impl CredentialsDialog {
         self.open = true;
         self.error_message = None;
         self.success_message = None;
+        self.account = storage.active_account();
+        self.saved_accounts = storage.list_accounts();
+        self.passphrase.clear();
+        self.locked = false;
         
         // Automatically load existing credentials if available
-        match storage.load_credentials() {
+        match storage.load_credentials(&self.account) {
             Ok(Some(credentials)) => {
-                self.private_key = credentials.private_key.clone();
-                self.display_name = credentials.display_name.unwrap_or_default();
-                self.about = credentials.about.unwrap_or_default();
-                self.picture = credentials.picture.unwrap_or_default();
-                self.nip05 = credentials.nip05.unwrap_or_default();
+                self.fill_fields(credentials);
                 self.success_message = Some("Existing credentials loaded".to_string());
             }
             Ok(None) => {
                 // No existing credentials, start fresh
                 self.clear_fields();
             }
+            Err(e) if e.is::<CredentialsLocked>() => {
+                self.clear_fields();
+                self.locked = true;
+            }
             Err(e) => {
                 self.error_message = Some(format!("Failed to load credentials: {}", e));
                 self.clear_fields();
@@ -69,7 +159,76 @@ impl CredentialsDialog {
         }
     }
 
+    fn fill_fields(&mut self, credentials: NostrCredentials) {
+        self.profile_conflicts.clear();
+        self.use_remote_signer = credentials.bunker_uri.is_some();
+        self.bunker_uri = credentials.bunker_uri.unwrap_or_default();
+        self.private_key = credentials.private_key;
+        self.display_name = credentials.display_name.unwrap_or_default();
+        self.about = credentials.about.unwrap_or_default();
+        self.picture = credentials.picture.unwrap_or_default();
+        self.nip05 = credentials.nip05.unwrap_or_default();
+    }
+
+    /// Load the saved credentials of `account` into the form
+    fn load_account(&mut self, storage: &Storage, account: &str) {
+        self.error_message = None;
+        self.success_message = None;
+        self.locked = false;
+
+        match storage.load_credentials(account) {
+            Ok(Some(credentials)) => {
+                self.fill_fields(credentials);
+                self.success_message = Some(format!("Credentials for \"{}\" loaded", account));
+            }
+            Ok(None) => {
+                self.error_message = Some("No saved credentials found".to_string());
+            }
+            Err(e) if e.is::<CredentialsLocked>() => {
+                self.locked = true;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load credentials: {}", e));
+            }
+        }
+    }
+
+    /// Fill the key field from a QR code in an image the user picks
+    fn import_from_qr(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Images", &["png", "jpg", "jpeg", "gif", "webp", "bmp"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        self.error_message = None;
+        self.success_message = None;
+
+        match crate::qr::decode_qr_image(&path) {
+            Ok(contents) => match contents.iter().find_map(|c| crate::qr::extract_nsec(c)) {
+                Some(nsec) if NostrClient::validate_private_key(&nsec) => {
+                    self.private_key = nsec;
+                    self.success_message = Some("Private key imported from QR code".to_string());
+                }
+                Some(_) => {
+                    self.error_message = Some("The QR code contains an invalid nsec".to_string());
+                }
+                None => {
+                    self.error_message = Some("The QR code doesn't contain an nsec private key".to_string());
+                }
+            },
+            Err(e) => {
+                tracing::warn!("QR import failed: {}", e);
+                self.error_message = Some(format!("Couldn't read a QR code: {}", e));
+            }
+        }
+    }
+
     fn clear_fields(&mut self) {
+        self.profile_conflicts.clear();
+        self.use_remote_signer = false;
+        self.bunker_uri.clear();
         self.private_key.clear();
         self.display_name.clear();
         self.about.clear();
@@ -77,18 +236,415 @@ impl CredentialsDialog {
         self.nip05.clear();
     }
 
+    /// Decrypt the saved credentials with the entered passphrase and sign
+    /// in with them if they belong to the active account
+    fn unlock(
+        &mut self,
+        storage: &mut Storage,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        runtime: &tokio::runtime::Runtime,
+    ) {
+        let account = self.account.trim().to_string();
+        storage.set_credentials_passphrase(Some(std::mem::take(&mut self.passphrase)));
+
+        match storage.load_credentials(&account) {
+            Ok(Some(credentials)) => {
+                self.locked = false;
+                self.error_message = None;
+                if account == storage.active_account() {
+                    Self::sign_in(credentials.clone(), nostr_client, runtime);
+                }
+                self.fill_fields(credentials);
+                self.success_message = Some("Credentials unlocked".to_string());
+            }
+            Ok(None) => {
+                self.locked = false;
+                self.error_message = Some("No saved credentials found".to_string());
+            }
+            Err(e) => {
+                storage.set_credentials_passphrase(None);
+                self.error_message = Some(format!("{:#}", e));
+            }
+        }
+    }
+
+    /// Switch the running client to `credentials` in the background
+    fn sign_in(credentials: NostrCredentials, nostr_client: &Arc<Mutex<NostrClient>>, runtime: &tokio::runtime::Runtime) {
+        let client = nostr_client.clone();
+        runtime.spawn(async move {
+            let signer = match NostrClient::connect_signer(credentials).await {
+                Ok(signer) => signer,
+                Err(e) => {
+                    tracing::error!("Failed to sign in: {:#}", e);
+                    return;
+                }
+            };
+            if let Ok(mut client) = client.try_lock() {
+                // Drop connections made with the previous identity
+                if let Err(e) = client.reset_connections().await {
+                    tracing::warn!("Failed to reset relay connections: {}", e);
+                }
+                client.use_signer(signer);
+            }
+        });
+    }
+
+    /// Add the optional profile fields to `credentials`
+    fn with_profile(&self, mut credentials: NostrCredentials) -> NostrCredentials {
+        if !self.display_name.is_empty() {
+            credentials.display_name = Some(self.display_name.clone());
+        }
+        if !self.about.is_empty() {
+            credentials.about = Some(self.about.clone());
+        }
+        if !self.picture.is_empty() {
+            credentials.picture = Some(self.picture.clone());
+        }
+        if !self.nip05.is_empty() {
+            credentials.nip05 = Some(self.nip05.clone());
+        }
+        credentials
+    }
+
+    /// Sign in through the remote signer in the background. The account is
+    /// saved once the signer has answered with its public key.
+    fn start_remote_signer(
+        &mut self,
+        account: String,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        runtime: &tokio::runtime::Runtime,
+    ) {
+        let bunker_uri = self.bunker_uri.trim().to_string();
+        if !bunker_uri.starts_with("bunker://") {
+            self.error_message = Some("Remote signer URIs start with bunker://".to_string());
+            return;
+        }
+
+        self.error_message = None;
+        self.success_message = None;
+
+        let credentials = self.with_profile(NostrCredentials::remote(bunker_uri));
+        let (sender, receiver) = mpsc::channel();
+        self.connecting = Some((account, receiver));
+
+        let client = nostr_client.clone();
+        runtime.spawn(async move {
+            // Wait for the signer before taking the client away from everything else
+            let result = match NostrClient::connect_signer(credentials).await {
+                Ok(signer) => {
+                    let credentials = signer.credentials().clone();
+                    let mut client = client.lock().await;
+                    // Drop connections made with the previous identity
+                    if let Err(e) = client.reset_connections().await {
+                        tracing::warn!("Failed to reset relay connections: {}", e);
+                    }
+                    client.use_signer(signer);
+                    Ok(credentials)
+                }
+                Err(e) => Err(format!("{:#}", e)),
+            };
+            let _ = sender.send(result);
+        });
+    }
+
+    /// Save the account once its remote signer has connected
+    fn poll_remote_signer(&mut self, ctx: &egui::Context, storage: &mut Storage) -> bool {
+        let Some((account, receiver)) = &self.connecting else {
+            return false;
+        };
+
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                return false;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => Err("Connecting stopped unexpectedly".to_string()),
+        };
+        let account = account.clone();
+        self.connecting = None;
+
+        let credentials = match result {
+            Ok(credentials) => credentials,
+            Err(e) => {
+                self.error_message = Some(e);
+                return false;
+            }
+        };
+
+        match storage.save_credentials(&account, &credentials) {
+            Ok(()) => {
+                if let Err(e) = storage.set_active_account(&account) {
+                    tracing::warn!("Failed to remember active account: {:#}", e);
+                }
+                self.saved_accounts = storage.list_accounts();
+                self.success_message = Some("Remote signer connected and saved".to_string());
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to save: {}", e));
+                false
+            }
+        }
+    }
+
+    fn profile_field_mut(&mut self, field: ProfileField) -> &mut String {
+        match field {
+            ProfileField::DisplayName => &mut self.display_name,
+            ProfileField::About => &mut self.about,
+            ProfileField::Picture => &mut self.picture,
+            ProfileField::Nip05 => &mut self.nip05,
+        }
+    }
+
+    /// Fetch the signed-in identity's published profile in the background
+    fn fetch_profile(
+        &mut self,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
+        runtime: &tokio::runtime::Runtime,
+    ) {
+        self.error_message = None;
+        self.success_message = None;
+        self.profile_conflicts.clear();
+
+        let (sender, receiver) = mpsc::channel();
+        self.fetching_profile = Some(receiver);
+
+        let client = nostr_client.clone();
+        let relay_settings = relay_settings.clone();
+        runtime.spawn(async move {
+            let result = client
+                .lock()
+                .await
+                .fetch_profile(&relay_settings)
+                .await
+                .map(|metadata| {
+                    metadata.map(|metadata| {
+                        vec![
+                            (ProfileField::DisplayName, metadata.display_name.or(metadata.name)),
+                            (ProfileField::About, metadata.about),
+                            (ProfileField::Picture, metadata.picture),
+                            (ProfileField::Nip05, metadata.nip05),
+                        ]
+                    })
+                })
+                .map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+    }
+
+    /// Fill the empty fields from the fetched profile. Fields that were
+    /// typed in are only replaced once the user confirms.
+    fn poll_profile_fetch(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.fetching_profile else {
+            return;
+        };
+
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => Err("Fetching stopped unexpectedly".to_string()),
+        };
+        self.fetching_profile = None;
+
+        let profile = match result {
+            Ok(Some(profile)) => profile,
+            Ok(None) => {
+                self.success_message = Some("No profile found on your relays".to_string());
+                return;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to fetch profile: {}", e));
+                return;
+            }
+        };
+
+        let mut filled = 0;
+        for (field, value) in profile {
+            let Some(value) = value.filter(|v| !v.trim().is_empty()) else {
+                continue;
+            };
+            let current = self.profile_field_mut(field);
+            if current.trim().is_empty() {
+                *current = value;
+                filled += 1;
+            } else if *current != value {
+                self.profile_conflicts.push((field, value));
+            }
+        }
+        self.success_message = Some(format!("Profile fetched, {} empty field(s) filled", filled));
+    }
+
+    /// Sign the profile in the form and send it to the active relays in the
+    /// background. Only the signed-in identity can sign it. Without
+    /// `create_if_missing`, nothing is published if the relays have no
+    /// profile to update.
+    fn publish_profile(
+        &mut self,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+        create_if_missing: bool,
+    ) {
+        self.error_message = None;
+        self.success_message = None;
+        self.published_profile_id = None;
+        self.confirm_new_profile = false;
+
+        let Some(credentials) = nostr_client.try_lock().ok().and_then(|client| client.get_credentials().cloned()) else {
+            self.error_message = Some("Sign in with these credentials first".to_string());
+            return;
+        };
+        // Fields left empty in the form keep their value on the relays
+        let credentials = self.with_profile(NostrCredentials {
+            display_name: None,
+            about: None,
+            picture: None,
+            nip05: None,
+            ..credentials
+        });
+
+        let (sender, receiver) = mpsc::channel();
+        self.publishing_profile = Some(receiver);
+
+        let client = nostr_client.clone();
+        let relay_settings = relay_settings.clone();
+        let activity_guard = activity.begin_critical("Publishing profile");
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = client
+                .lock()
+                .await
+                .update_profile(&credentials, &relay_settings, create_if_missing)
+                .await
+                .map_err(|e| (!e.is::<ProfileNotFound>()).then(|| format!("{:#}", e)));
+            let _ = sender.send(result);
+        });
+    }
+
+    fn poll_profile_publish(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.publishing_profile else {
+            return;
+        };
+
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => Err(Some("Publishing stopped unexpectedly".to_string())),
+        };
+        self.publishing_profile = None;
+
+        match result {
+            Ok(report) => {
+                self.success_message = Some(format!(
+                    "Profile published to {} of {} relay(s)",
+                    report.successful_relays.len(),
+                    report.successful_relays.len() + report.failed_relays.len() + report.timed_out_relays.len(),
+                ));
+                self.published_profile_id = Some(report.event_id.to_hex());
+            }
+            Err(Some(e)) => self.error_message = Some(format!("Failed to publish profile: {}", e)),
+            Err(None) => self.confirm_new_profile = true,
+        }
+    }
+
+    /// Public key of the identity being edited, if it's known yet
+    fn identity_public_key(&self, nostr_client: &Arc<Mutex<NostrClient>>) -> Option<String> {
+        if self.use_remote_signer {
+            // The signer's key is only known once it has connected
+            let bunker_uri = self.bunker_uri.trim();
+            nostr_client.try_lock().ok().and_then(|client| {
+                client
+                    .get_credentials()
+                    .filter(|c| c.bunker_uri.as_deref() == Some(bunker_uri))
+                    .map(|c| c.public_key.clone())
+            })
+        } else {
+            NostrClient::get_public_key_from_private(self.private_key.trim()).ok()
+        }
+    }
+
+    /// Check the NIP-05 identifier in the background unless this identifier
+    /// and key were already checked
+    fn check_nip05(&mut self, public_key: Option<String>, runtime: &tokio::runtime::Runtime) {
+        let nip05 = self.nip05.trim().to_string();
+        let Some(public_key) = public_key.filter(|_| !nip05.is_empty()) else {
+            self.nip05_check = None;
+            return;
+        };
+        if self.nip05_check.as_ref().is_some_and(|c| c.nip05 == nip05 && c.public_key == public_key) {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let (lookup, key) = (nip05.clone(), public_key.clone());
+        runtime.spawn(async move {
+            let result = NostrClient::verify_nip05(&lookup, &key).await.map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+        self.nip05_check = Some(Nip05Check { nip05, public_key, state: Nip05State::Checking(receiver) });
+    }
+
+    fn poll_nip05_check(&mut self, ctx: &egui::Context) {
+        let Some(check) = &mut self.nip05_check else {
+            return;
+        };
+        let Nip05State::Checking(receiver) = &check.state else {
+            return;
+        };
+
+        check.state = match receiver.try_recv() {
+            Ok(Ok(true)) => Nip05State::Verified,
+            Ok(Ok(false)) => Nip05State::Mismatch,
+            Ok(Err(e)) => {
+                tracing::warn!("NIP-05 check of {} failed: {}", check.nip05, e);
+                Nip05State::Failed(e)
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => Nip05State::Failed("Checking stopped unexpectedly".to_string()),
+        };
+    }
+
+    /// Returns true when an account was saved or deleted, so the app can
+    /// refresh its account list
     pub fn show(
         &mut self,
         ctx: &egui::Context,
         storage: &mut Storage,
         nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
         runtime: &tokio::runtime::Runtime,
-    ) {
+        activity: &ActivityTracker,
+    ) -> bool {
         if !self.open {
-            return;
+            return false;
         }
 
         let mut close_dialog = false;
+        let mut accounts_changed = self.poll_remote_signer(ctx, storage);
+        let is_connecting = self.connecting.is_some();
+        self.poll_nip05_check(ctx);
+        self.poll_profile_fetch(ctx);
+        self.poll_profile_publish(ctx);
+        let public_key = self.identity_public_key(nostr_client);
+        // Profiles are fetched for the identity the client is signed in with
+        let signed_in = public_key.is_some()
+            && nostr_client
+                .try_lock()
+                .ok()
+                .and_then(|client| client.get_credentials().map(|c| c.public_key.clone()))
+                == public_key;
 
         Window::new("🔑 Nostr Credentials")
             .collapsible(false)
@@ -98,57 +654,122 @@ impl CredentialsDialog {
                 ui.vertical(|ui| {
                     ui.spacing_mut().item_spacing.y = 10.0;
 
+                    // Account the credentials belong to
+                    ui.horizontal(|ui| {
+                        ui.label("Account:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.account)
+                                .hint_text("e.g. personal, work")
+                                .desired_width(160.0)
+                        );
+
+                        let mut load_account = None;
+                        egui::ComboBox::from_id_source("credentials_saved_accounts")
+                            .selected_text("Saved accounts")
+                            .show_ui(ui, |ui| {
+                                for account in &self.saved_accounts {
+                                    if ui.selectable_label(&self.account == account, account).clicked() {
+                                        load_account = Some(account.clone());
+                                    }
+                                }
+                            });
+                        if let Some(account) = load_account {
+                            self.account = account.clone();
+                            self.load_account(storage, &account);
+                        }
+                    });
+                    ui.small("Save separate keys under different names to switch between them from the top bar");
+
                     // Load existing credentials button
                     if ui.button("📂 Load Existing Credentials").clicked() {
-                        match storage.load_credentials() {
-                            Ok(Some(credentials)) => {
-                                self.private_key = credentials.private_key.clone();
-                                self.display_name = credentials.display_name.unwrap_or_default();
-                                self.about = credentials.about.unwrap_or_default();
-                                self.picture = credentials.picture.unwrap_or_default();
-                                self.nip05 = credentials.nip05.unwrap_or_default();
-                                self.success_message = Some("Credentials loaded".to_string());
-                            }
-                            Ok(None) => {
-                                self.error_message = Some("No saved credentials found".to_string());
-                            }
-                            Err(e) => {
-                                self.error_message = Some(format!("Failed to load credentials: {}", e));
-                            }
-                        }
+                        let account = self.account.trim().to_string();
+                        self.load_account(storage, &account);
+                    }
+
+                    if self.locked {
+                        ui.group(|ui| {
+                            ui.label(RichText::new("🔒 These credentials are encrypted. Enter your passphrase to unlock them.").color(CatppuccinMocha::YELLOW));
+                            ui.horizontal(|ui| {
+                                let response = ui.add(
+                                    egui::TextEdit::singleline(&mut self.passphrase)
+                                        .password(true)
+                                        .desired_width(200.0)
+                                );
+                                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                if (ui.button("🔓 Unlock").clicked() || submitted) && !self.passphrase.is_empty() {
+                                    self.unlock(storage, nostr_client, runtime);
+                                }
+                            });
+                        });
                     }
 
                     ui.separator();
 
-                    // Private key input
-                    ui.label("Private Key:");
-                    ui.small("Supports both nsec (bech32) and hex formats");
-                    let _private_key_response = ui.add(
-                        egui::TextEdit::singleline(&mut self.private_key)
-                            .password(true)
-                            .hint_text("Enter your nsec1... or hex private key...")
-                    );
-
-                    // Validate private key format
-                    if !self.private_key.is_empty() && !NostrClient::validate_private_key(&self.private_key) {
-                        if self.private_key.starts_with("nsec") {
-                            ui.label(RichText::new("⚠️ Invalid nsec format").color(CatppuccinMocha::YELLOW));
-                        } else {
-                            ui.label(RichText::new("⚠️ Invalid private key format").color(CatppuccinMocha::YELLOW));
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.use_remote_signer, false, "🔑 Private key");
+                        ui.selectable_value(&mut self.use_remote_signer, true, "🛡 Remote signer (NIP-46)");
+                    });
+
+                    if self.use_remote_signer {
+                        ui.label("Bunker URI:");
+                        ui.small("Your signer app keeps the private key and approves each signature. The key is never stored on this device.");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.bunker_uri)
+                                .hint_text("bunker://...")
+                                .desired_width(f32::INFINITY)
+                        );
+                    } else {
+                        // Private key input
+                        ui.label("Private Key:");
+                        ui.small("Supports both nsec (bech32) and hex formats");
+                        let _private_key_response = ui.add(
+                            egui::TextEdit::singleline(&mut self.private_key)
+                                .password(true)
+                                .hint_text("Enter your nsec1... or hex private key...")
+                        );
+
+                        // Validate private key format
+                        if !self.private_key.is_empty() && !NostrClient::validate_private_key(&self.private_key) {
+                            if self.private_key.starts_with("nsec") {
+                                ui.label(RichText::new("⚠️ Invalid nsec format").color(CatppuccinMocha::YELLOW));
+                            } else {
+                                ui.label(RichText::new("⚠️ Invalid private key format").color(CatppuccinMocha::YELLOW));
+                            }
                         }
-                    }
 
-                    // Generate new key button
-                    if ui.button("🎲 Generate New Keys (nsec format)").clicked() {
-                        let credentials = NostrClient::generate_credentials();
-                        self.private_key = credentials.private_key;
-                        self.success_message = Some("New nsec key generated".to_string());
+                        ui.horizontal(|ui| {
+                            // Generate new key button
+                            if ui.button("🎲 Generate New Keys (nsec format)").clicked() {
+                                let credentials = NostrClient::generate_credentials();
+                                self.private_key = credentials.private_key;
+                                self.success_message = Some("New nsec key generated".to_string());
+                            }
+
+                            if ui.button("📷 Import from QR")
+                                .on_hover_text("Read an nsec from a QR code image or screenshot")
+                                .clicked()
+                            {
+                                self.import_from_qr();
+                            }
+                        });
                     }
 
                     ui.separator();
 
                     // Profile information
-                    ui.label("Profile Information (Optional):");
+                    ui.horizontal(|ui| {
+                        ui.label("Profile Information (Optional):");
+                        if self.fetching_profile.is_some() {
+                            ui.spinner();
+                        } else if ui
+                            .add_enabled(signed_in, egui::Button::new("📥 Fetch from relays"))
+                            .on_hover_text("Fill in the profile you last published")
+                            .on_disabled_hover_text("Save and sign in with these credentials first")
+                            .clicked()
+                        {
+                            self.fetch_profile(nostr_client, relay_settings, runtime);
+                        }
+                    });
 
                     ui.horizontal(|ui| {
                         ui.label("Display Name:");
@@ -167,11 +788,111 @@ impl CredentialsDialog {
 
                     ui.horizontal(|ui| {
                         ui.label("NIP-05:");
-                        ui.text_edit_singleline(&mut self.nip05);
+                        let response = ui.text_edit_singleline(&mut self.nip05);
+                        // Check the identifier once it has been entered
+                        if !response.has_focus() {
+                            self.check_nip05(public_key.clone(), runtime);
+                        }
+                        match self.nip05_check.as_ref().map(|c| &c.state) {
+                            Some(Nip05State::Checking(_)) => {
+                                ui.spinner();
+                            }
+                            Some(Nip05State::Verified) => {
+                                ui.label(RichText::new("✅").color(CatppuccinMocha::GREEN))
+                                    .on_hover_text("Resolves to this identity's public key");
+                            }
+                            Some(Nip05State::Mismatch) => {
+                                ui.label(RichText::new("⚠️").color(CatppuccinMocha::YELLOW))
+                                    .on_hover_text("Resolves to a different public key");
+                            }
+                            Some(Nip05State::Failed(e)) => {
+                                ui.label(RichText::new("⚠️").color(CatppuccinMocha::YELLOW))
+                                    .on_hover_text(format!("Couldn't verify: {}", e));
+                            }
+                            None => {}
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if self.publishing_profile.is_some() {
+                            ui.spinner();
+                            ui.label("Publishing your profile...");
+                        } else if ui
+                            .add_enabled(signed_in, egui::Button::new("📣 Publish profile"))
+                            .on_hover_text("Sign these profile fields and send them to your active relays")
+                            .on_disabled_hover_text("Save and sign in with these credentials first")
+                            .clicked()
+                        {
+                            self.publish_profile(nostr_client, relay_settings, runtime, activity, false);
+                        }
                     });
+                    if self.confirm_new_profile {
+                        ui.group(|ui| {
+                            ui.label(
+                                RichText::new("No profile was found on your relays. Publishing creates a new one with only these fields; if another client set more and the relays just didn't answer, those would be replaced.")
+                                    .color(CatppuccinMocha::YELLOW),
+                            );
+                            ui.horizontal(|ui| {
+                                if ui.button("📣 Publish as a new profile").clicked() {
+                                    self.publish_profile(nostr_client, relay_settings, runtime, activity, true);
+                                }
+                                if ui.button("Cancel").clicked() {
+                                    self.confirm_new_profile = false;
+                                }
+                            });
+                        });
+                    }
+                    if let Some(event_id) = &self.published_profile_id {
+                        ui.label(
+                            RichText::new(format!("Profile event {}", event_id))
+                                .small()
+                                .monospace()
+                                .color(CatppuccinMocha::OVERLAY1),
+                        );
+                    }
+
+                    if !self.profile_conflicts.is_empty() {
+                        ui.group(|ui| {
+                            let fields: Vec<&str> = self.profile_conflicts.iter().map(|(field, _)| field.label()).collect();
+                            ui.label(
+                                RichText::new(format!("Your relays have a different {}.", fields.join(", ")))
+                                    .color(CatppuccinMocha::YELLOW),
+                            );
+                            ui.horizontal(|ui| {
+                                if ui.button("Use relay profile").clicked() {
+                                    for (field, value) in std::mem::take(&mut self.profile_conflicts) {
+                                        *self.profile_field_mut(field) = value;
+                                    }
+                                }
+                                if ui.button("Keep mine").clicked() {
+                                    self.profile_conflicts.clear();
+                                }
+                            });
+                        });
+                    }
+
+                    if matches!(self.nip05_check.as_ref().map(|c| &c.state), Some(Nip05State::Mismatch)) {
+                        ui.label(
+                            RichText::new("⚠️ This NIP-05 identifier belongs to a different public key. Clients will show your posts as unverified.")
+                                .color(CatppuccinMocha::YELLOW),
+                        );
+                    }
 
                     ui.separator();
 
+                    // Passphrase for the file kept in case the system keyring is unavailable
+                    if !self.locked && !storage.has_credentials_passphrase() {
+                        ui.horizontal(|ui| {
+                            ui.label("File passphrase:");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.passphrase)
+                                    .password(true)
+                                    .hint_text("Optional")
+                            );
+                        });
+                        ui.small("Encrypts a backup copy of your credentials on disk. Without one, they're only kept in the system keyring.");
+                        ui.separator();
+                    }
+
                     // Show messages
                     if let Some(error) = &self.error_message {
                         ui.label(RichText::new(format!("❌ {}", error)).color(CatppuccinMocha::RED));
@@ -185,47 +906,44 @@ impl CredentialsDialog {
 
                     // Buttons
                     ui.horizontal(|ui| {
+                        if is_connecting {
+                            ui.spinner();
+                            ui.label("Waiting for the remote signer...");
+                            return;
+                        }
+
                         let save_clicked = ui.button("💾 Save").clicked();
-                        eprintln!("DEBUG: Button check - clicked: {}, private_key empty: {}", 
-                                save_clicked, self.private_key.is_empty());
-                        if save_clicked && !self.private_key.is_empty() {
-                            eprintln!("DEBUG: Save button clicked with private key: {}", if self.private_key.is_empty() { "EMPTY" } else { "NOT_EMPTY" });
-                            if NostrClient::validate_private_key(&self.private_key) {
-                                eprintln!("DEBUG: Private key validation passed");
+                        let account = self.account.trim().to_string();
+                        if save_clicked && !self.passphrase.is_empty() {
+                            storage.set_credentials_passphrase(Some(std::mem::take(&mut self.passphrase)));
+                        }
+                        if save_clicked && self.use_remote_signer {
+                            match Storage::validate_account_label(&account) {
+                                Ok(()) => self.start_remote_signer(account.clone(), nostr_client, runtime),
+                                Err(e) => self.error_message = Some(e),
+                            }
+                        } else if save_clicked && !self.private_key.is_empty() {
+                            if let Err(e) = Storage::validate_account_label(&account) {
+                                self.error_message = Some(e);
+                            } else if NostrClient::validate_private_key(&self.private_key) {
                                 match NostrClient::get_public_key_from_private(&self.private_key) {
                                     Ok(public_key) => {
-                                        eprintln!("DEBUG: Public key generated successfully");
-                                        let mut credentials = NostrCredentials::new(
+                                        let credentials = self.with_profile(NostrCredentials::new(
                                             self.private_key.clone(),
                                             public_key
-                                        );
-                                        
-                                        if !self.display_name.is_empty() {
-                                            credentials.display_name = Some(self.display_name.clone());
-                                        }
-                                        if !self.about.is_empty() {
-                                            credentials.about = Some(self.about.clone());
-                                        }
-                                        if !self.picture.is_empty() {
-                                            credentials.picture = Some(self.picture.clone());
-                                        }
-                                        if !self.nip05.is_empty() {
-                                            credentials.nip05 = Some(self.nip05.clone());
-                                        }
-
-                                        eprintln!("DEBUG: About to save credentials...");
+                                        ));
 
                                         // Save credentials
-                                        match storage.save_credentials(&credentials) {
+                                        match storage.save_credentials(&account, &credentials) {
                                             Ok(()) => {
+                                                // Sign in with the account that was just saved
+                                                if let Err(e) = storage.set_active_account(&account) {
+                                                    tracing::warn!("Failed to remember active account: {:#}", e);
+                                                }
+                                                accounts_changed = true;
+
                                                 // Set credentials in client
-                                                let client = nostr_client.clone();
-                                                let creds = credentials.clone();
-                                                runtime.spawn(async move {
-                                                    if let Ok(mut client) = client.try_lock() {
-                                                        let _ = client.set_credentials(creds);
-                                                    }
-                                                });
+                                                Self::sign_in(credentials, nostr_client, runtime);
                                                 
                                                 self.success_message = Some("Credentials saved successfully".to_string());
                                                 close_dialog = true;
@@ -240,23 +958,20 @@ impl CredentialsDialog {
                                     }
                                 }
                             } else {
-                                eprintln!("DEBUG: Private key validation failed");
                                 self.error_message = Some("Invalid private key format".to_string());
                             }
                         }
 
                         if ui.button("🗑️ Delete").clicked() {
-                            match storage.delete_credentials() {
-                                Ok(()) => {
-                                    self.private_key.clear();
-                                    self.display_name.clear();
-                                    self.about.clear();
-                                    self.picture.clear();
-                                    self.nip05.clear();
-                                    self.success_message = Some("Credentials deleted".to_string());
+                            match storage.load_credentials(&account) {
+                                Ok(Some(credentials)) => {
+                                    self.confirm_delete = Some(credentials.public_key);
+                                }
+                                Ok(None) => {
+                                    self.error_message = Some("No saved credentials to delete".to_string());
                                 }
                                 Err(e) => {
-                                    self.error_message = Some(format!("Failed to delete: {}", e));
+                                    self.error_message = Some(format!("Failed to load credentials: {}", e));
                                 }
                             }
                         }
@@ -268,10 +983,101 @@ impl CredentialsDialog {
                 });
             });
 
+        accounts_changed |= self.show_delete_confirmation(ctx, storage, nostr_client, runtime);
+
         if close_dialog {
             self.open = false;
+            self.confirm_delete = None;
             self.error_message = None;
             self.success_message = None;
         }
+
+        accounts_changed
+    }
+
+    /// Ask before removing the identity; without a backup of the nsec it
+    /// cannot be recovered. Returns true once the account is deleted.
+    fn show_delete_confirmation(
+        &mut self,
+        ctx: &egui::Context,
+        storage: &mut Storage,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        runtime: &tokio::runtime::Runtime,
+    ) -> bool {
+        let Some(public_key) = self.confirm_delete.clone() else {
+            return false;
+        };
+
+        let mut confirmed = false;
+        let mut cancelled = false;
+
+        Window::new("⚠️ Delete Credentials?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("This removes your Nostr identity from this device:");
+                ui.label(RichText::new(&public_key).monospace().small());
+                ui.add_space(6.0);
+                ui.label(
+                    RichText::new("Make sure your nsec is backed up first. Without it you can never post as this identity again.")
+                        .color(CatppuccinMocha::YELLOW),
+                );
+                ui.label("Your posts on relays are not affected.");
+                ui.add_space(6.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(RichText::new("🗑️ Delete from this device").color(CatppuccinMocha::RED)).clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Keep credentials").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if cancelled {
+            self.confirm_delete = None;
+        }
+
+        if !confirmed {
+            return false;
+        }
+
+        self.confirm_delete = None;
+        self.error_message = None;
+        self.success_message = None;
+
+        let account = self.account.trim().to_string();
+        match storage.delete_credentials(&account) {
+            Ok(()) => {
+                self.clear_fields();
+                self.saved_accounts = storage.list_accounts();
+
+                // Sign the running client out too if this identity is in use,
+                // so the app reflects the deletion
+                let client = nostr_client.clone();
+                let deleted_key = public_key.clone();
+                runtime.spawn(async move {
+                    let mut client = client.lock().await;
+                    if client.get_credentials().is_some_and(|c| c.public_key == deleted_key) {
+                        if let Err(e) = client.reset_connections().await {
+                            tracing::warn!("Failed to reset relay connections: {}", e);
+                        }
+                        client.clear_credentials();
+                    }
+                });
+
+                self.success_message = Some(format!(
+                    "Credentials deleted. Identity {} was removed from this device and signed out if it was in use.",
+                    public_key
+                ));
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to delete: {}", e));
+                false
+            }
+        }
     }
 }
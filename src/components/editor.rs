@@ -1,19 +1,252 @@
+use crate::app_settings::{format_date, DEFAULT_DATE_FORMAT};
+use crate::components::cheat_sheet::show_cheat_sheet;
+use crate::components::find_bar::{highlighted_layout_job, FindBar};
+use crate::components::markdown_viewer::{with_max_width, MarkdownViewer};
+use crate::components::TableDialog;
+use crate::html_export::PAGE_BREAK_MARKER;
+use crate::lint::{find_long_lines, wrap_line};
+use crate::markdown_format::{apply_format, expand_placeholder_at, MarkdownFormat};
 use crate::post::BlogPost;
-use crate::theme::ThemeColors;
+use crate::preview::{strip_markdown, PreviewWidth};
+use crate::theme::{tag_color, ThemeColors};
+use egui::text::{CCursor, CCursorRange};
 use egui::{RichText, Ui};
+use std::collections::{HashMap, HashSet};
+
+/// Width prose is re-wrapped to when fixing an overly long line
+const WRAP_WIDTH: usize = 80;
+
+/// What the content area shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ViewMode {
+    #[default]
+    Edit,
+    Preview,
+    /// The text with all Markdown syntax removed
+    Plaintext,
+}
 
 pub struct MarkdownEditor {
     current_post: Option<BlogPost>,
-    preview_mode: bool,
+    view_mode: ViewMode,
+    show_cheat_sheet: bool,
+    /// Showing fetched/published content that isn't a local draft
+    read_only: bool,
     new_tag: String,
+    new_gallery_image: String,
+    scroll_sync: ScrollSync,
+    table_dialog: TableDialog,
+    max_line_length: usize,
+    /// Text of long lines the user chose to keep as they are
+    dismissed_long_lines: HashSet<String>,
+    tag_colors: HashMap<String, [u8; 3]>,
+    markdown_viewer: MarkdownViewer,
+    preview_width: PreviewWidth,
+    /// Drop cached previews and images on the next frame
+    clear_preview_cache: bool,
+    /// strftime pattern for inserted dates
+    date_format: String,
+    /// Characters selected in the content editor when it last had focus
+    content_selection: Option<std::ops::Range<usize>>,
+    find_bar: FindBar,
+}
+
+/// Id of the content `TextEdit`, used to read and move its cursor
+fn content_editor_id() -> egui::Id {
+    egui::Id::new("markdown_content_editor")
+}
+
+/// Character and byte index of the content editor's cursor, or the end of
+/// the content if it has never had focus
+fn cursor_index(state: Option<&egui::text_edit::TextEditState>, content: &str) -> (usize, usize) {
+    let char_index = state
+        .and_then(|s| s.cursor.char_range())
+        .map(|range| range.primary.index)
+        .unwrap_or_else(|| content.chars().count());
+    let byte_index = content
+        .char_indices()
+        .nth(char_index)
+        .map(|(i, _)| i)
+        .unwrap_or(content.len());
+    (char_index, byte_index)
+}
+
+/// Insert text at the content editor's cursor, or at the end if it has never had focus
+fn insert_at_cursor(ctx: &egui::Context, content: &mut String, text: &str) {
+    let id = content_editor_id();
+    let state = egui::TextEdit::load_state(ctx, id);
+    let (char_index, byte_index) = cursor_index(state.as_ref(), content);
+
+    content.insert_str(byte_index, text);
+
+    // Move the cursor to the end of the inserted text
+    if let Some(mut state) = state {
+        let new_index = char_index + text.chars().count();
+        state.cursor.set_char_range(Some(CCursorRange::one(CCursor::new(new_index))));
+        state.store(ctx, id);
+    }
+}
+
+/// Insert a block element (like a horizontal rule) at the cursor on its own
+/// lines, adding only the blank lines needed to separate it from its neighbours
+fn insert_block_at_cursor(ctx: &egui::Context, content: &mut String, block: &str) {
+    let state = egui::TextEdit::load_state(ctx, content_editor_id());
+    let (_, byte_index) = cursor_index(state.as_ref(), content);
+    let (before, after) = content.split_at(byte_index);
+
+    let leading = if before.is_empty() || before.ends_with("\n\n") {
+        ""
+    } else if before.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    let trailing = if after.starts_with("\n\n") {
+        ""
+    } else if after.is_empty() || after.starts_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+
+    insert_at_cursor(ctx, content, &format!("{}{}{}", leading, block, trailing));
+}
+
+/// Select a range of characters in the content editor, shown once it has focus
+fn set_editor_selection(ctx: &egui::Context, range: std::ops::Range<usize>) {
+    let id = content_editor_id();
+    let mut state = egui::TextEdit::load_state(ctx, id).unwrap_or_default();
+    state.cursor.set_char_range(Some(CCursorRange::two(CCursor::new(range.start), CCursor::new(range.end))));
+    state.store(ctx, id);
+}
+
+/// Select a range of characters in the content editor and focus it
+fn select_in_editor(ctx: &egui::Context, range: std::ops::Range<usize>) {
+    set_editor_selection(ctx, range);
+    ctx.memory_mut(|mem| mem.request_focus(content_editor_id()));
+}
+
+/// How far down the content a character offset is, by line
+fn line_ratio(content: &str, char_offset: usize) -> f32 {
+    let total_lines = content.lines().count().max(1);
+    let line = content.chars().take(char_offset).filter(|c| *c == '\n').count();
+    line as f32 / total_lines as f32
+}
+
+/// Keyboard shortcut for a toolbar action, used with the command key
+fn format_shortcut(format: MarkdownFormat) -> Option<egui::Key> {
+    match format {
+        MarkdownFormat::Bold => Some(egui::Key::B),
+        MarkdownFormat::Italic => Some(egui::Key::I),
+        MarkdownFormat::Link => Some(egui::Key::K),
+        _ => None,
+    }
+}
+
+/// Replace a `{{date}}` that was just typed at the cursor with `date`,
+/// unless it's inside code. Returns true if it was replaced.
+fn expand_date_placeholder(ctx: &egui::Context, content: &mut String, date: &str) -> bool {
+    const PLACEHOLDER: &str = "{{date}}";
+
+    let id = content_editor_id();
+    let Some(mut state) = egui::TextEdit::load_state(ctx, id) else {
+        return false;
+    };
+    let Some(cursor) = state.cursor.char_range().map(|range| range.primary.index) else {
+        return false;
+    };
+    let Some(cursor) = expand_placeholder_at(content, cursor, PLACEHOLDER, date) else {
+        return false;
+    };
+
+    state.cursor.set_char_range(Some(CCursorRange::one(CCursor::new(cursor))));
+    state.store(ctx, id);
+    true
+}
+
+/// Keeps the editor and preview at roughly the same place in a long post.
+///
+/// The sync is approximate: we remember how far down the document the last
+/// visible view was (as a fraction of its scrollable height) and apply the same
+/// fraction to the other view when switching.
+#[derive(Default)]
+struct ScrollSync {
+    ratio: f32,
+    pending: bool,
+    editor_max: Option<f32>,
+    preview_max: Option<f32>,
+}
+
+impl ScrollSync {
+    fn request(&mut self) {
+        self.pending = true;
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn scroll_area(&mut self, preview: bool) -> egui::ScrollArea {
+        let id = if preview { "preview_scroll" } else { "editor_scroll" };
+        let mut area = egui::ScrollArea::vertical().id_source(id);
+
+        let max = if preview { self.preview_max } else { self.editor_max };
+        if self.pending {
+            // Until the view has been laid out once we don't know its height,
+            // so the sync is applied on the following frame
+            if let Some(max) = max {
+                area = area.vertical_scroll_offset(self.ratio * max);
+                self.pending = false;
+            }
+        }
+
+        area
+    }
+
+    /// Scroll to a fraction of the way down on the next frame
+    fn scroll_to(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self.pending = true;
+    }
+
+    fn record<R>(&mut self, preview: bool, output: &egui::scroll_area::ScrollAreaOutput<R>) {
+        let max = (output.content_size.y - output.inner_rect.height()).max(0.0);
+        if preview {
+            self.preview_max = Some(max);
+        } else {
+            self.editor_max = Some(max);
+        }
+
+        if !self.pending {
+            self.ratio = if max > 0.0 {
+                (output.state.offset.y / max).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+        }
+    }
 }
 
 impl Default for MarkdownEditor {
     fn default() -> Self {
         Self {
             current_post: None,
-            preview_mode: false,
+            view_mode: ViewMode::Edit,
+            show_cheat_sheet: false,
+            read_only: false,
             new_tag: String::new(),
+            new_gallery_image: String::new(),
+            scroll_sync: ScrollSync::default(),
+            table_dialog: TableDialog::new(),
+            max_line_length: 1000,
+            dismissed_long_lines: HashSet::new(),
+            tag_colors: HashMap::new(),
+            markdown_viewer: MarkdownViewer::new(),
+            preview_width: PreviewWidth::Full,
+            clear_preview_cache: false,
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            content_selection: None,
+            find_bar: FindBar::new(),
         }
     }
 }
@@ -24,8 +257,73 @@ impl MarkdownEditor {
     }
 
     pub fn set_post(&mut self, post: BlogPost) {
+        // Cached images and rendered Markdown belong to the previous post
+        if self.current_post.as_ref().map(|p| p.id) != Some(post.id) {
+            self.clear_preview_cache = true;
+        }
         self.current_post = Some(post);
-        self.preview_mode = false;
+        self.view_mode = ViewMode::Edit;
+        self.read_only = false;
+        self.scroll_sync.reset();
+        self.dismissed_long_lines.clear();
+        self.content_selection = None;
+    }
+
+    /// Show a post fetched from relays without letting it be edited
+    pub fn set_post_read_only(&mut self, post: BlogPost) {
+        self.set_post(post);
+        self.read_only = true;
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_max_line_length(&mut self, max_line_length: usize) {
+        self.max_line_length = max_line_length;
+    }
+
+    pub fn set_date_format(&mut self, date_format: String) {
+        self.date_format = date_format;
+    }
+
+    pub fn set_tag_colors(&mut self, tag_colors: HashMap<String, [u8; 3]>) {
+        self.tag_colors = tag_colors;
+    }
+
+    /// Put the content cursor at a character offset and scroll it into view
+    pub fn jump_to(&mut self, ctx: &egui::Context, char_offset: usize) {
+        let Some(post) = &self.current_post else {
+            return;
+        };
+
+        self.view_mode = ViewMode::Edit;
+
+        let id = content_editor_id();
+        let mut state = egui::TextEdit::load_state(ctx, id).unwrap_or_default();
+        state.cursor.set_char_range(Some(CCursorRange::one(CCursor::new(char_offset))));
+        state.store(ctx, id);
+        ctx.memory_mut(|mem| mem.request_focus(id));
+
+        self.scroll_sync.scroll_to(line_ratio(&post.content, char_offset));
+    }
+
+    /// Switch the open post between editing and the rendered preview
+    pub fn toggle_preview(&mut self) {
+        if self.current_post.is_none() || self.read_only {
+            return;
+        }
+        self.view_mode = if self.view_mode == ViewMode::Preview { ViewMode::Edit } else { ViewMode::Preview };
+        self.scroll_sync.request();
+    }
+
+    pub fn set_preview_width(&mut self, preview_width: PreviewWidth) {
+        self.preview_width = preview_width;
+    }
+
+    /// Free memory held by the preview (rendered Markdown and loaded images)
+    pub fn clear_preview_cache(&mut self) {
+        self.clear_preview_cache = true;
     }
 
     pub fn get_post(&self) -> Option<&BlogPost> {
@@ -43,6 +341,27 @@ impl MarkdownEditor {
     pub fn show(&mut self, ui: &mut Ui, theme_colors: &ThemeColors) -> EditorAction {
         let mut action = EditorAction::None;
 
+        if self.clear_preview_cache {
+            self.markdown_viewer.clear(ui.ctx());
+            self.clear_preview_cache = false;
+        }
+
+        if self.read_only {
+            if let Some(post) = &self.current_post {
+                return Self::show_read_only(&mut self.markdown_viewer, &mut self.preview_width, &self.tag_colors, ui, post, theme_colors);
+            }
+        }
+
+        // Ctrl+F toggles find and replace
+        if self.current_post.is_some() && ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F)) {
+            if self.find_bar.is_open() {
+                self.find_bar.close();
+            } else {
+                self.find_bar.open();
+                self.view_mode = ViewMode::Edit;
+            }
+        }
+
         if let Some(post) = &mut self.current_post {
             ui.vertical(|ui| {
                 // Header with controls
@@ -55,9 +374,22 @@ impl MarkdownEditor {
                             action = EditorAction::Save;
                         }
 
+                        // One-off copies outside the library
+                        ui.menu_button("📄 Save a Copy", |ui| {
+                            if ui.button("With frontmatter…").on_hover_text("Title, tags and other metadata followed by the content").clicked() {
+                                action = EditorAction::SaveCopy { include_frontmatter: true };
+                                ui.close_menu();
+                            }
+                            if ui.button("Body only…").on_hover_text("Just the Markdown content").clicked() {
+                                action = EditorAction::SaveCopy { include_frontmatter: false };
+                                ui.close_menu();
+                            }
+                        });
+
                         // Publish button
                         if post.is_ready_to_publish() {
-                            if ui.button(RichText::new("🚀 Publish").color(theme_colors.primary)).clicked() {
+                            let publish_label = if post.has_been_published() { "🔄 Update" } else { "🚀 Publish" };
+                            if ui.button(RichText::new(publish_label).color(theme_colors.primary)).clicked() {
                                 action = EditorAction::Publish;
                             }
                         }
@@ -67,10 +399,46 @@ impl MarkdownEditor {
                             action = EditorAction::InsertImage;
                         }
 
-                        // Preview toggle
-                        let preview_text = if self.preview_mode { "📝 Edit" } else { "👁 Preview" };
-                        if ui.button(preview_text).clicked() {
-                            self.preview_mode = !self.preview_mode;
+                        // Table button
+                        if ui.button("📊 Table").on_hover_text("Paste a table from CSV/TSV").clicked() {
+                            self.table_dialog.open();
+                        }
+
+                        // Horizontal rule button
+                        if ui.button("➖ Rule").on_hover_text("Insert a horizontal rule (---) at the cursor").clicked() {
+                            insert_block_at_cursor(ui.ctx(), &mut post.content, "---");
+                            post.updated_at = chrono::Utc::now();
+                            action = EditorAction::Changed;
+                        }
+
+                        // Page break button
+                        if ui
+                            .button("📄 Page break")
+                            .on_hover_text("Start a new page here in HTML exports and when printing them")
+                            .clicked()
+                        {
+                            insert_block_at_cursor(ui.ctx(), &mut post.content, PAGE_BREAK_MARKER);
+                            post.updated_at = chrono::Utc::now();
+                            action = EditorAction::Changed;
+                        }
+
+                        // Markdown cheat sheet toggle
+                        if ui
+                            .selectable_label(self.show_cheat_sheet, "❓ Markdown")
+                            .on_hover_text("Show common Markdown syntax")
+                            .clicked()
+                        {
+                            self.show_cheat_sheet = !self.show_cheat_sheet;
+                        }
+
+                        // View mode
+                        let previous_mode = self.view_mode;
+                        ui.selectable_value(&mut self.view_mode, ViewMode::Plaintext, "🗒 Plaintext")
+                            .on_hover_text("Show the text without Markdown syntax");
+                        ui.selectable_value(&mut self.view_mode, ViewMode::Preview, "👁 Preview");
+                        ui.selectable_value(&mut self.view_mode, ViewMode::Edit, "📝 Edit");
+                        if self.view_mode != previous_mode {
+                            self.scroll_sync.request();
                         }
                     });
                 });
@@ -87,6 +455,25 @@ impl MarkdownEditor {
                     }
                 });
 
+                // Summary, shown as the article excerpt by Nostr clients
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Summary:").color(theme_colors.text));
+                    let mut summary = post.summary.clone().unwrap_or_default();
+                    let summary_response = ui.add(
+                        egui::TextEdit::multiline(&mut summary)
+                            .desired_rows(2)
+                            .desired_width(f32::INFINITY)
+                            .hint_text("A sentence or two shown as the excerpt in Nostr clients"),
+                    );
+                    if summary_response.changed() {
+                        // The summary is a single frontmatter line and tag value
+                        let summary = summary.replace(['\r', '\n'], " ");
+                        post.summary = if summary.trim().is_empty() { None } else { Some(summary) };
+                        post.updated_at = chrono::Utc::now();
+                        action = EditorAction::Changed;
+                    }
+                });
+
                 // Tags section
                 ui.horizontal(|ui| {
                     ui.label(RichText::new("Tags:").color(theme_colors.text));
@@ -95,7 +482,7 @@ impl MarkdownEditor {
                     let tags_to_remove: Vec<String> = post.tags.iter().cloned().collect();
                     for tag in &tags_to_remove {
                         ui.horizontal(|ui| {
-                            ui.label(RichText::new(&format!("#{}", tag)).color(theme_colors.primary));
+                            ui.label(RichText::new(&format!("#{}", tag)).color(tag_color(tag, &self.tag_colors)));
                             if ui.small_button("❌").clicked() {
                                 post.remove_tag(tag);
                                 action = EditorAction::Changed;
@@ -136,51 +523,139 @@ impl MarkdownEditor {
                     }
                 });
 
+                // Gallery images after the featured one
+                let gallery_title = match post.gallery_images.len() {
+                    0 => "Gallery".to_string(),
+                    n => format!("Gallery ({})", n),
+                };
+                egui::CollapsingHeader::new(RichText::new(gallery_title).color(theme_colors.text))
+                    .id_source("gallery_images")
+                    .show(ui, |ui| {
+                        let images = post.images();
+                        let mut image_action = None;
+                        for (index, url) in images.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                let label = if index == 0 { RichText::new("★").color(theme_colors.warning) } else { RichText::new(format!("{}.", index)) };
+                                ui.label(label);
+                                ui.label(RichText::new(url).small().monospace());
+                                if index > 0 && ui.small_button("⬆").on_hover_text(if index == 1 { "Make this the featured image" } else { "Move up" }).clicked() {
+                                    image_action = Some((index, Some(index - 1)));
+                                }
+                                if index + 1 < images.len() && ui.small_button("⬇").on_hover_text("Move down").clicked() {
+                                    image_action = Some((index, Some(index + 1)));
+                                }
+                                if ui.small_button("❌").clicked() {
+                                    image_action = Some((index, None));
+                                }
+                            });
+                        }
+                        match image_action {
+                            Some((from, Some(to))) => {
+                                post.move_image(from, to);
+                                action = EditorAction::Changed;
+                            }
+                            Some((index, None)) => {
+                                post.remove_image(index);
+                                action = EditorAction::Changed;
+                            }
+                            None => {}
+                        }
+
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut self.new_gallery_image).hint_text("https://..."));
+                            if ui.button("➕ Add image").clicked() && !self.new_gallery_image.trim().is_empty() {
+                                let mut images = post.images();
+                                images.push(self.new_gallery_image.trim().to_string());
+                                post.set_images(images);
+                                self.new_gallery_image.clear();
+                                action = EditorAction::Changed;
+                            }
+                        });
+                        ui.label(
+                            RichText::new("★ marks the featured image. The rest are published as extra image tags.")
+                                .small()
+                                .color(theme_colors.text_muted),
+                        );
+                    });
+
+                // Visibility
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut post.unlisted, "🔗 Unlisted")
+                        .on_hover_text("Publish without hashtags, summary or image so the article is only reachable by its link. Relays and clients may still index it.")
+                        .changed()
+                    {
+                        post.updated_at = chrono::Utc::now();
+                        action = EditorAction::Changed;
+                    }
+                    if ui.checkbox(&mut post.private, "🔒 Private")
+                        .on_hover_text("Encrypt this post's content on disk with your private posts passphrase. The title and tags stay readable.")
+                        .changed()
+                    {
+                        post.updated_at = chrono::Utc::now();
+                        action = EditorAction::Changed;
+                    }
+                });
+
                 ui.separator();
 
                 // Content area
-                if self.preview_mode {
+                if self.view_mode == ViewMode::Plaintext {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Plaintext").strong().color(theme_colors.success));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.label(RichText::new(format!("{} words", post.word_count())).small().color(theme_colors.text_secondary));
+                            if preview_width_menu(ui, &mut self.preview_width) {
+                                action = EditorAction::PreviewWidthChanged(self.preview_width);
+                            }
+                        });
+                    });
+
+                    let max_width = self.preview_width.max_width();
+                    let scroll_output = self.scroll_sync.scroll_area(true).show(ui, |ui| {
+                        with_max_width(ui, max_width, |ui| {
+                            ui.add(egui::Label::new(RichText::new(strip_markdown(&post.content)).color(theme_colors.text)).wrap());
+                        });
+                    });
+                    self.scroll_sync.record(true, &scroll_output);
+                } else if self.view_mode == ViewMode::Preview {
                     // Preview mode
                     ui.horizontal(|ui| {
                         ui.label(RichText::new("Preview").strong().color(theme_colors.success));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             ui.label(RichText::new(format!("{} words", post.word_count())).small().color(theme_colors.text_secondary));
+                            if preview_width_menu(ui, &mut self.preview_width) {
+                                action = EditorAction::PreviewWidthChanged(self.preview_width);
+                            }
                         });
                     });
 
-                    egui::ScrollArea::vertical().show(ui, |ui| {
-                        // Custom markdown preview with colored headers
-                        let lines: Vec<&str> = post.content.lines().collect();
-                        for line in lines {
-                            if line.starts_with("# ") {
-                                ui.heading(RichText::new(&line[2..]).color(theme_colors.primary));
-                            } else if line.starts_with("## ") {
-                                ui.add(egui::Label::new(RichText::new(&line[3..]).heading().color(theme_colors.secondary)));
-                            } else if line.starts_with("### ") {
-                                ui.add(egui::Label::new(RichText::new(&line[4..]).strong().color(theme_colors.info)));
-                            } else if line.starts_with("#### ") {
-                                ui.add(egui::Label::new(RichText::new(&line[5..]).strong().color(theme_colors.success)));
-                            } else if line.starts_with("##### ") {
-                                ui.add(egui::Label::new(RichText::new(&line[6..]).strong().color(theme_colors.warning)));
-                            } else if line.starts_with("###### ") {
-                                ui.add(egui::Label::new(RichText::new(&line[7..]).strong().color(theme_colors.error)));
-                            } else if line.starts_with("**") && line.ends_with("**") && line.len() > 4 {
-                                ui.add(egui::Label::new(RichText::new(&line[2..line.len()-2]).strong().color(theme_colors.text)));
-                            } else if line.starts_with("*") && line.ends_with("*") && line.len() > 2 && !line.starts_with("**") {
-                                ui.add(egui::Label::new(RichText::new(&line[1..line.len()-1]).italics().color(theme_colors.secondary)));
-                            } else if line.starts_with("> ") {
-                                ui.add(egui::Label::new(RichText::new(&line[2..]).color(theme_colors.text_secondary)));
-                            } else if line.starts_with("```") {
-                                ui.add(egui::Label::new(RichText::new(line).monospace().color(theme_colors.text_muted)));
-                            } else if line.trim().is_empty() {
-                                ui.add_space(5.0);
-                            } else {
-                                ui.add(egui::Label::new(RichText::new(line).color(theme_colors.text)));
-                            }
-                        }
+                    let max_width = self.preview_width.max_width();
+                    let scroll_output = self.scroll_sync.scroll_area(true).show(ui, |ui| {
+                        with_max_width(ui, max_width, |ui| {
+                            self.markdown_viewer.render(ui, &post.content);
+                        });
                     });
+                    self.scroll_sync.record(true, &scroll_output);
                 } else {
                     // Edit mode
+                    if self.show_cheat_sheet {
+                        egui::SidePanel::right("markdown_cheat_sheet")
+                            .resizable(true)
+                            .default_width(220.0)
+                            .show_inside(ui, |ui| {
+                                if let Some(entry) = show_cheat_sheet(ui, theme_colors) {
+                                    if entry.block {
+                                        insert_block_at_cursor(ui.ctx(), &mut post.content, entry.snippet);
+                                    } else {
+                                        insert_at_cursor(ui.ctx(), &mut post.content, entry.snippet);
+                                    }
+                                    ui.ctx().memory_mut(|mem| mem.request_focus(content_editor_id()));
+                                    post.updated_at = chrono::Utc::now();
+                                    action = EditorAction::Changed;
+                                }
+                            });
+                    }
+
                     ui.horizontal(|ui| {
                         ui.label(RichText::new("Content (Markdown)").strong().color(theme_colors.primary));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -188,19 +663,151 @@ impl MarkdownEditor {
                         });
                     });
 
-                    egui::ScrollArea::vertical().show(ui, |ui| {
-                        let content_response = ui.add_sized(
-                            [ui.available_width(), ui.available_height() - 50.0],
-                            egui::TextEdit::multiline(&mut post.content)
-                                .font(egui::TextStyle::Monospace)
-                                .hint_text("Write your blog post in Markdown...")
-                        );
+                    // Formatting toolbar, applied to the selection
+                    let mut format = None;
+                    ui.horizontal(|ui| {
+                        for markdown_format in MarkdownFormat::ALL {
+                            let label = RichText::new(markdown_format.label());
+                            let label = match markdown_format {
+                                MarkdownFormat::Bold => label.strong(),
+                                MarkdownFormat::Italic => label.italics(),
+                                MarkdownFormat::Code => label.monospace(),
+                                _ => label,
+                            };
+                            let hint = match format_shortcut(markdown_format) {
+                                Some(key) => format!(
+                                    "{} ({})",
+                                    markdown_format.name(),
+                                    ui.ctx().format_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, key))
+                                ),
+                                None => markdown_format.name().to_string(),
+                            };
+                            if ui.small_button(label).on_hover_text(hint).clicked() {
+                                format = Some(markdown_format);
+                            }
+                        }
+                    });
+
+                    let find_output = self.find_bar.show(ui, &mut post.content, theme_colors);
+                    if let Some(selected) = find_output.selected {
+                        self.scroll_sync.scroll_to(line_ratio(&post.content, selected.start));
+                        set_editor_selection(ui.ctx(), selected.clone());
+                        self.content_selection = Some(selected);
+                    }
+                    if find_output.changed {
+                        post.updated_at = chrono::Utc::now();
+                        action = EditorAction::Changed;
+                    }
+                    if find_output.closed {
+                        ui.ctx().memory_mut(|mem| mem.request_focus(content_editor_id()));
+                    }
+
+                    // Long line warnings
+                    let lines: Vec<&str> = post.content.lines().collect();
+                    let long_lines: Vec<_> = find_long_lines(&post.content, self.max_line_length)
+                        .into_iter()
+                        .filter(|l| !self.dismissed_long_lines.contains(lines[l.line_number - 1]))
+                        .collect();
+                    let mut to_wrap = None;
+                    let mut to_dismiss = None;
+                    for long_line in long_lines.iter().take(3) {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(format!(
+                                "⚠ Line {} is {} characters long and may render poorly in some clients",
+                                long_line.line_number, long_line.length
+                            )).small().color(theme_colors.warning));
+                            if long_line.can_wrap && ui.small_button("↩ Wrap").on_hover_text("Split this paragraph across shorter lines").clicked() {
+                                to_wrap = Some(long_line.line_number);
+                            }
+                            if ui.small_button("Dismiss").clicked() {
+                                to_dismiss = Some(lines[long_line.line_number - 1].to_string());
+                            }
+                        });
+                    }
+                    if long_lines.len() > 3 {
+                        ui.label(RichText::new(format!("...and {} more long lines", long_lines.len() - 3)).small().color(theme_colors.text_muted));
+                    }
+                    if let Some(line) = to_dismiss {
+                        self.dismissed_long_lines.insert(line);
+                    }
+                    if let Some(line_number) = to_wrap {
+                        wrap_line(&mut post.content, line_number, WRAP_WIDTH);
+                        post.updated_at = chrono::Utc::now();
+                        action = EditorAction::Changed;
+                    }
+
+                    // Ctrl+Shift+D inserts today's date at the cursor
+                    let editor_focused = ui.memory(|m| m.has_focus(content_editor_id()));
+                    if editor_focused
+                        && ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::D))
+                    {
+                        let date = format_date(&self.date_format, chrono::Local::now().date_naive());
+                        insert_at_cursor(ui.ctx(), &mut post.content, &date);
+                        post.updated_at = chrono::Utc::now();
+                        action = EditorAction::Changed;
+                    }
+
+                    if editor_focused {
+                        for markdown_format in MarkdownFormat::ALL {
+                            if let Some(key) = format_shortcut(markdown_format) {
+                                if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, key)) {
+                                    format = Some(markdown_format);
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(markdown_format) = format {
+                        // Without a selection, format at the end of the post
+                        let selection = self.content_selection.clone().unwrap_or_else(|| {
+                            let end = post.content.chars().count();
+                            end..end
+                        });
+                        let selected = apply_format(&mut post.content, selection, markdown_format);
+                        select_in_editor(ui.ctx(), selected.clone());
+                        self.content_selection = Some(selected);
+                        post.updated_at = chrono::Utc::now();
+                        action = EditorAction::Changed;
+                    }
+
+                    let scroll_output = self.scroll_sync.scroll_area(false).show(ui, |ui| {
+                        let find_bar = &self.find_bar;
+                        let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+                            let (matches, current) = find_bar.highlights(text);
+                            let job = highlighted_layout_job(ui, text, &matches, current, wrap_width, theme_colors);
+                            ui.fonts(|fonts| fonts.layout_job(job))
+                        };
+                        let mut text_edit = egui::TextEdit::multiline(&mut post.content)
+                            .id(content_editor_id())
+                            .font(egui::TextStyle::Monospace)
+                            .hint_text("Write your blog post in Markdown...")
+                            .desired_width(f32::INFINITY)
+                            .min_size(egui::vec2(0.0, ui.available_height() - 50.0));
+                        if find_bar.is_searching() {
+                            text_edit = text_edit.layouter(&mut layouter);
+                        }
+                        let output = text_edit.show(ui);
+                        
+                        // Only reported while focused; keep the last selection for the toolbar
+                        if let Some(cursor_range) = output.cursor_range {
+                            self.content_selection = Some(cursor_range.as_sorted_char_range());
+                        }
                         
-                        if content_response.changed() {
+                        if output.response.changed() {
+                            let date = format_date(&self.date_format, chrono::Local::now().date_naive());
+                            expand_date_placeholder(ui.ctx(), &mut post.content, &date);
                             post.updated_at = chrono::Utc::now();
                             action = EditorAction::Changed;
                         }
                     });
+                    self.scroll_sync.record(false, &scroll_output);
+                }
+
+                // Table paste helper
+                if let Some(table) = self.table_dialog.show(ui.ctx(), theme_colors) {
+                    insert_at_cursor(ui.ctx(), &mut post.content, &format!("\n\n{}\n\n", table));
+                    post.updated_at = chrono::Utc::now();
+                    action = EditorAction::Changed;
                 }
 
                 // Status bar
@@ -219,6 +826,7 @@ impl MarkdownEditor {
                             "Updated: {}",
                             post.updated_at.format("%Y-%m-%d %H:%M")
                         )).small().color(theme_colors.text_secondary));
+                        ui.label(RichText::new(format!("~{} min read", post.reading_time())).small().color(theme_colors.text_secondary));
                     });
                 });
             });
@@ -236,12 +844,94 @@ impl MarkdownEditor {
     }
 }
 
+impl MarkdownEditor {
+    /// Rendered article with its metadata and no edit controls
+    fn show_read_only(
+        markdown_viewer: &mut MarkdownViewer,
+        preview_width: &mut PreviewWidth,
+        tag_colors: &HashMap<String, [u8; 3]>,
+        ui: &mut Ui,
+        post: &BlogPost,
+        theme_colors: &ThemeColors,
+    ) -> EditorAction {
+        let mut action = EditorAction::None;
+
+        ui.horizontal(|ui| {
+            ui.heading("📖 Published Article");
+            ui.label(RichText::new("read-only").small().color(theme_colors.text_muted));
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .button(RichText::new("✏️ Edit a local copy").color(theme_colors.success))
+                    .on_hover_text("Save this article as a local post you can edit and republish")
+                    .clicked()
+                {
+                    action = EditorAction::EditLocalCopy;
+                }
+                if preview_width_menu(ui, preview_width) {
+                    action = EditorAction::PreviewWidthChanged(*preview_width);
+                }
+            });
+        });
+
+        ui.separator();
+
+        ui.label(RichText::new(&post.title).size(22.0).strong().color(theme_colors.primary));
+        if let Some(summary) = &post.summary {
+            ui.label(RichText::new(summary).italics().color(theme_colors.text_secondary));
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new(post.created_at.format("%Y-%m-%d").to_string()).small().color(theme_colors.text_muted));
+            ui.label(RichText::new(format!("{} words", post.word_count())).small().color(theme_colors.text_muted));
+            for tag in &post.tags {
+                ui.label(RichText::new(format!("#{}", tag)).small().color(tag_color(tag, tag_colors)));
+            }
+        });
+
+        if let Some(event_id) = &post.nostr_event_id {
+            ui.label(RichText::new(format!("Event: {}", event_id)).small().monospace().color(theme_colors.text_muted));
+        }
+        if !post.published_relays.is_empty() {
+            ui.label(
+                RichText::new(format!("Seen on: {}", post.published_relays.join(", ")))
+                    .small()
+                    .color(theme_colors.text_muted),
+            );
+        }
+
+        ui.separator();
+        markdown_viewer.show(ui, &post.content, preview_width.max_width());
+
+        action
+    }
+}
+
+/// Dropdown of preview widths; returns true when the choice changed
+fn preview_width_menu(ui: &mut Ui, preview_width: &mut PreviewWidth) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_source("preview_width")
+        .selected_text(preview_width.label())
+        .show_ui(ui, |ui| {
+            for width in PreviewWidth::ALL {
+                changed |= ui.selectable_value(preview_width, width, width.label()).changed();
+            }
+        });
+    changed
+}
+
 #[derive(Debug, Clone)]
 pub enum EditorAction {
     None,
     Changed,
     Save,
+    /// Write the current post to a file of the user's choosing
+    SaveCopy { include_frontmatter: bool },
     Publish,
     InsertImage,
     UploadFeaturedImage,
+    /// Turn the read-only article into an editable local post
+    EditLocalCopy,
+    /// The user picked a different preview width, which should be remembered
+    PreviewWidthChanged(PreviewWidth),
 }
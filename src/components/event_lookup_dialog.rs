@@ -0,0 +1,231 @@
+use crate::activity::ActivityTracker;
+use crate::components::markdown_viewer::MarkdownViewer;
+use crate::nostr_client::{NostrClient, ResolvedEvent};
+use crate::post::BlogPost;
+use crate::relay_settings::RelaySettings;
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, ScrollArea, TextEdit, Window};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Quick read-only lookup of any event by naddr, nevent, note or hex id
+#[derive(Default)]
+pub struct EventLookupDialog {
+    open: bool,
+    reference: String,
+    error_message: Option<String>,
+    resolved: Option<ResolvedEvent>,
+    markdown_viewer: MarkdownViewer,
+    pending: Option<mpsc::Receiver<Result<ResolvedEvent, String>>>,
+}
+
+impl EventLookupDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.error_message = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns an article the user chose to open in the reader
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+        theme_colors: &ThemeColors,
+    ) -> Option<BlogPost> {
+        let mut result = None;
+
+        // Poll an in-flight lookup
+        if let Some(receiver) = &self.pending {
+            match receiver.try_recv() {
+                Ok(Ok(resolved)) => {
+                    self.pending = None;
+                    self.resolved = Some(resolved);
+                }
+                Ok(Err(e)) => {
+                    self.pending = None;
+                    self.error_message = Some(e);
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.pending = None;
+                    self.error_message = Some("Lookup task stopped unexpectedly".to_string());
+                }
+            }
+        }
+
+        if !self.open {
+            return result;
+        }
+
+        let mut window_open = self.open;
+        let mut start_lookup = false;
+        let is_fetching = self.pending.is_some();
+
+        Window::new("🔎 Look Up Event")
+            .open(&mut window_open)
+            .collapsible(false)
+            .default_width(560.0)
+            .default_height(480.0)
+            .show(ctx, |ui| {
+                ui.label(RichText::new("Paste an naddr, nevent, note or hex event id to fetch it from your active relays.").color(theme_colors.text));
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    let response = ui.add_enabled(
+                        !is_fetching,
+                        TextEdit::singleline(&mut self.reference)
+                            .hint_text("naddr1... / nevent1... / note1...")
+                            .desired_width(400.0),
+                    );
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        start_lookup = true;
+                    }
+
+                    if is_fetching {
+                        ui.spinner();
+                    } else if ui.button("🔍 Look Up").clicked() {
+                        start_lookup = true;
+                    }
+                });
+
+                if let Some(error) = &self.error_message {
+                    ui.add_space(8.0);
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                }
+
+                let Some(resolved) = &self.resolved else {
+                    return;
+                };
+
+                ui.separator();
+                egui::Grid::new("event_lookup_details").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                    ui.label(RichText::new("Kind").strong());
+                    ui.label(kind_label(resolved.kind));
+                    ui.end_row();
+
+                    ui.label(RichText::new("Author").strong());
+                    ui.add(egui::Label::new(RichText::new(&resolved.author).monospace().small()).wrap());
+                    ui.end_row();
+
+                    ui.label(RichText::new("Created").strong());
+                    ui.label(
+                        resolved
+                            .created_at
+                            .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_else(|| "Unknown".to_string()),
+                    );
+                    ui.end_row();
+
+                    ui.label(RichText::new("Event id").strong());
+                    ui.add(egui::Label::new(RichText::new(&resolved.id).monospace().small()).wrap());
+                    ui.end_row();
+                });
+
+                if !resolved.signature_valid {
+                    ui.colored_label(theme_colors.warning, "⚠ The event's signature is invalid; its content may have been tampered with.");
+                }
+
+                ui.separator();
+                if let Some(article) = &resolved.article {
+                    ui.horizontal(|ui| {
+                        ui.heading(RichText::new(&article.title).color(theme_colors.primary));
+                        if ui
+                            .button("📖 Open in Reader")
+                            .on_hover_text("Show the article in the main view, where you can save a local copy")
+                            .clicked()
+                        {
+                            result = Some(article.clone());
+                        }
+                    });
+                    if let Some(summary) = &article.summary {
+                        ui.label(RichText::new(summary).italics().color(theme_colors.text_secondary));
+                    }
+                    ui.add_space(4.0);
+                    self.markdown_viewer.show(ui, &article.content, None);
+                } else {
+                    ScrollArea::vertical().show(ui, |ui| {
+                        ui.add(egui::Label::new(RichText::new(&resolved.content).color(theme_colors.text)).wrap());
+                    });
+                }
+            });
+
+        self.open = window_open;
+
+        if result.is_some() {
+            self.open = false;
+        }
+
+        if start_lookup && !is_fetching {
+            self.start_lookup(ctx, nostr_client, relay_settings, runtime, activity);
+        }
+
+        result
+    }
+
+    fn start_lookup(
+        &mut self,
+        ctx: &Context,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+    ) {
+        let reference = self.reference.trim().to_string();
+        if reference.is_empty() {
+            self.error_message = Some("Please enter an naddr, nevent, note or event id".to_string());
+            return;
+        }
+
+        self.error_message = None;
+        self.resolved = None;
+        self.markdown_viewer.clear(ctx);
+
+        let (sender, receiver) = mpsc::channel();
+        self.pending = Some(receiver);
+
+        let client = nostr_client.clone();
+        let relay_settings = relay_settings.clone();
+        let activity_guard = activity.begin("Looking up event on relays");
+
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = {
+                let client_guard = client.lock().await;
+                client_guard
+                    .fetch_event(&reference, &relay_settings)
+                    .await
+                    .map(|event| ResolvedEvent::from_event(&event, relay_settings.get_active_relays()))
+                    .map_err(|e| format!("{:#}", e))
+            };
+
+            let _ = sender.send(result);
+        });
+    }
+}
+
+fn kind_label(kind: u16) -> String {
+    let name = match kind {
+        0 => "profile metadata",
+        1 => "short text note",
+        6 => "repost",
+        7 => "reaction",
+        30023 => "long-form article",
+        30024 => "long-form draft",
+        _ => return kind.to_string(),
+    };
+    format!("{} ({})", kind, name)
+}
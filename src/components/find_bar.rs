@@ -0,0 +1,212 @@
+use crate::find_replace::{char_range, find_matches, replace_all, FindOptions};
+use crate::theme::ThemeColors;
+use egui::text::{LayoutJob, TextFormat};
+use egui::{RichText, TextEdit, Ui};
+use std::ops::Range;
+
+/// What the find bar did this frame
+#[derive(Debug, Default)]
+pub struct FindBarOutput {
+    /// Character range of the match that became current
+    pub selected: Option<Range<usize>>,
+    /// The content was changed by a replace
+    pub changed: bool,
+    /// The bar was closed and the editor should get focus back
+    pub closed: bool,
+}
+
+/// Find and replace within the post being edited
+#[derive(Default)]
+pub struct FindBar {
+    open: bool,
+    query: String,
+    replacement: String,
+    options: FindOptions,
+    /// Index of the current match
+    current: usize,
+    focus_query: bool,
+    message: Option<String>,
+}
+
+impl FindBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the bar, keeping the last search
+    pub fn open(&mut self) {
+        self.open = true;
+        self.focus_query = true;
+        self.message = None;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Whether matches should be highlighted in the editor
+    pub fn is_searching(&self) -> bool {
+        self.open && !self.query.is_empty()
+    }
+
+    /// Byte ranges of the matches in `text` and the index of the current one
+    pub fn highlights(&self, text: &str) -> (Vec<Range<usize>>, usize) {
+        (find_matches(text, &self.query, self.options), self.current)
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, content: &mut String, theme_colors: &ThemeColors) -> FindBarOutput {
+        let mut output = FindBarOutput::default();
+        if !self.open {
+            return output;
+        }
+
+        let match_count = find_matches(content, &self.query, self.options).len();
+        if self.current >= match_count {
+            self.current = 0;
+        }
+
+        // Some(true) moves to the next match, Some(false) to the previous one
+        let mut step = None;
+        let mut search_changed = false;
+        let mut replace_current = false;
+        let mut replace_every = false;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Find:").color(theme_colors.text));
+            let response = ui.add(TextEdit::singleline(&mut self.query).hint_text("Search this post").desired_width(200.0));
+            if self.focus_query {
+                response.request_focus();
+                self.focus_query = false;
+            }
+            search_changed |= response.changed();
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                step = Some(!ui.input(|i| i.modifiers.shift));
+                // Keep the focus so Enter can be pressed again
+                self.focus_query = true;
+            }
+
+            if !self.query.is_empty() {
+                if match_count == 0 {
+                    ui.label(RichText::new("No matches").small().color(theme_colors.warning));
+                } else {
+                    ui.label(
+                        RichText::new(format!("{} of {}", self.current + 1, match_count))
+                            .small()
+                            .color(theme_colors.text_secondary),
+                    );
+                }
+            }
+
+            if ui.small_button("⬆").on_hover_text("Previous match (Shift+Enter)").clicked() {
+                step = Some(false);
+            }
+            if ui.small_button("⬇").on_hover_text("Next match (Enter)").clicked() {
+                step = Some(true);
+            }
+            search_changed |= ui.checkbox(&mut self.options.case_sensitive, "Match case").changed();
+            search_changed |= ui.checkbox(&mut self.options.whole_word, "Whole word").changed();
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("❌").on_hover_text("Close (Esc)").clicked() {
+                    output.closed = true;
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Replace:").color(theme_colors.text));
+            ui.add(TextEdit::singleline(&mut self.replacement).hint_text("Replace with").desired_width(200.0));
+            if ui.add_enabled(match_count > 0, egui::Button::new("Replace").small()).clicked() {
+                replace_current = true;
+            }
+            if ui.add_enabled(match_count > 0, egui::Button::new("Replace All").small()).clicked() {
+                replace_every = true;
+            }
+            if let Some(message) = &self.message {
+                ui.label(RichText::new(message).small().color(theme_colors.success));
+            }
+        });
+
+        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+            output.closed = true;
+        }
+
+        if search_changed {
+            self.current = 0;
+            self.message = None;
+        }
+
+        let matches = find_matches(content, &self.query, self.options);
+        if replace_current {
+            if let Some(range) = matches.get(self.current) {
+                content.replace_range(range.clone(), &self.replacement);
+                output.changed = true;
+                self.message = None;
+            }
+        } else if replace_every {
+            let count = replace_all(content, &self.query, &self.replacement, self.options);
+            output.changed = count > 0;
+            self.current = 0;
+            self.message = Some(format!("Replaced {} {}", count, if count == 1 { "match" } else { "matches" }));
+        } else if let (Some(forward), false) = (step, matches.is_empty()) {
+            self.current = if forward {
+                (self.current + 1) % matches.len()
+            } else {
+                (self.current + matches.len() - 1) % matches.len()
+            };
+        }
+
+        // Move to the current match after searching, stepping or replacing one
+        if search_changed || step.is_some() || replace_current {
+            let matches = if output.changed { find_matches(content, &self.query, self.options) } else { matches };
+            if let Some(range) = matches.get(self.current) {
+                output.selected = Some(char_range(content, range.clone()));
+            }
+        }
+
+        if output.closed {
+            self.open = false;
+        }
+        output
+    }
+}
+
+/// Lay out editor text in the monospace font with find matches highlighted
+pub fn highlighted_layout_job(
+    ui: &Ui,
+    text: &str,
+    matches: &[Range<usize>],
+    current: usize,
+    wrap_width: f32,
+    theme_colors: &ThemeColors,
+) -> LayoutJob {
+    let color = ui
+        .visuals()
+        .override_text_color
+        .unwrap_or_else(|| ui.visuals().widgets.inactive.text_color());
+    let plain = TextFormat {
+        font_id: egui::TextStyle::Monospace.resolve(ui.style()),
+        color,
+        ..Default::default()
+    };
+
+    let mut job = LayoutJob::default();
+    job.wrap.max_width = wrap_width;
+    let mut last = 0;
+    for (index, range) in matches.iter().enumerate() {
+        job.append(&text[last..range.start], 0.0, plain.clone());
+        let highlight = if index == current {
+            TextFormat { background: theme_colors.warning, color: theme_colors.background, ..plain.clone() }
+        } else {
+            TextFormat { background: theme_colors.warning.gamma_multiply(0.35), ..plain.clone() }
+        };
+        job.append(&text[range.clone()], 0.0, highlight);
+        last = range.end;
+    }
+    job.append(&text[last..], 0.0, plain);
+    job
+}
@@ -0,0 +1,67 @@
+use crate::post::ImportReport;
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, Window};
+
+/// Explains how an imported file was interpreted. Purely informational; the
+/// post has already been imported when this is shown.
+#[derive(Default)]
+pub struct ImportReportDialog {
+    open: bool,
+    title: String,
+    report: ImportReport,
+}
+
+impl ImportReportDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, title: String, report: ImportReport) {
+        self.open = true;
+        self.title = title;
+        self.report = report;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn show(&mut self, ctx: &Context, theme_colors: &ThemeColors) {
+        if !self.open {
+            return;
+        }
+
+        let mut window_open = self.open;
+        let mut close = false;
+
+        Window::new("📥 Import Report")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let title = if self.title.is_empty() { "Untitled" } else { &self.title };
+                ui.label(RichText::new(format!("✅ Imported \"{}\"", title)).color(theme_colors.success));
+                ui.add_space(6.0);
+
+                if self.report.notes.is_empty() {
+                    ui.label(RichText::new("All frontmatter fields were read as expected.").color(theme_colors.text_secondary));
+                } else {
+                    for note in &self.report.notes {
+                        ui.label(RichText::new(format!("• {}", note)).color(theme_colors.text_secondary));
+                    }
+                }
+
+                ui.add_space(6.0);
+                ui.label(
+                    RichText::new("Fix these in the source file and import it again, or edit the post directly.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+                if ui.button("OK").clicked() {
+                    close = true;
+                }
+            });
+
+        self.open = window_open && !close;
+    }
+}
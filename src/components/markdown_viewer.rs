@@ -1,13 +1,57 @@
 use egui::Ui;
 
-pub struct MarkdownViewer;
+/// Renders Markdown with CommonMark. The cache keeps parsed state between
+/// frames; it and any images loaded for the article are dropped with `clear`.
+#[derive(Default)]
+pub struct MarkdownViewer {
+    cache: egui_commonmark::CommonMarkCache,
+}
 
 impl MarkdownViewer {
-    pub fn show(ui: &mut Ui, content: &str) {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render `content` in its own scroll area, optionally limited to
+    /// `max_width` and centered
+    pub fn show(&mut self, ui: &mut Ui, content: &str, max_width: Option<f32>) {
         egui::ScrollArea::vertical().show(ui, |ui| {
-            let mut commonmark_cache = egui_commonmark::CommonMarkCache::default();
-            egui_commonmark::CommonMarkViewer::new("markdown_viewer")
-                .show(ui, &mut commonmark_cache, content);
+            with_max_width(ui, max_width, |ui| self.render(ui, content));
         });
     }
+
+    /// Render `content` into `ui` without scrolling, for callers that
+    /// manage the scroll area themselves
+    pub fn render(&mut self, ui: &mut Ui, content: &str) {
+        egui_commonmark::CommonMarkViewer::new("markdown_viewer")
+            .show(ui, &mut self.cache, content);
+    }
+
+    /// Free the cached render state and all images held by egui's image loaders
+    pub fn clear(&mut self, ctx: &egui::Context) {
+        self.cache = egui_commonmark::CommonMarkCache::default();
+        ctx.forget_all_images();
+        tracing::debug!("Cleared preview cache");
+    }
+}
+
+/// Lay out `add_contents` in a centered column no wider than `max_width`,
+/// outlined so the edge of the simulated screen is visible
+pub fn with_max_width(ui: &mut Ui, max_width: Option<f32>, add_contents: impl FnOnce(&mut Ui)) {
+    let Some(max_width) = max_width.filter(|w| *w < ui.available_width()) else {
+        add_contents(ui);
+        return;
+    };
+
+    let margin = (ui.available_width() - max_width) / 2.0;
+    ui.horizontal_top(|ui| {
+        ui.add_space(margin);
+        egui::Frame::none()
+            .stroke(ui.visuals().widgets.noninteractive.bg_stroke)
+            .inner_margin(egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.set_width(max_width - 16.0);
+                ui.vertical(add_contents);
+            });
+    });
 }
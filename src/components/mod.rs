@@ -1,14 +1,52 @@
+pub mod blossom_dialog;
+pub mod blossom_uploads_dialog;
+pub mod cheat_sheet;
+pub mod command_palette;
 pub mod credentials_dialog;
 pub mod editor;
+pub mod event_lookup_dialog;
+pub mod find_bar;
+pub mod import_report_dialog;
 pub mod markdown_viewer;
+pub mod naddr_dialog;
+pub mod onboarding_dialog;
+pub mod passphrase_dialog;
+pub mod preferences_dialog;
+pub mod profile_broadcast_dialog;
 pub mod publish_dialog;
+pub mod qr_dialog;
+pub mod recover_dialog;
 pub mod relay_dialog;
+pub mod relay_test_dialog;
+pub mod search_dialog;
 pub mod settings_dialog;
+pub mod shortcuts_dialog;
 pub mod sidebar;
+pub mod table_dialog;
+pub mod trash_dialog;
+pub mod writing_stats_dialog;
 
+pub use blossom_dialog::{BlossomDialog, BlossomDialogServices};
+pub use blossom_uploads_dialog::BlossomUploadsDialog;
+pub use command_palette::{CommandPalette, PaletteCommand};
 pub use credentials_dialog::CredentialsDialog;
 pub use editor::{MarkdownEditor, EditorAction};
+pub use event_lookup_dialog::EventLookupDialog;
+pub use import_report_dialog::ImportReportDialog;
+pub use naddr_dialog::NaddrDialog;
+pub use onboarding_dialog::{OnboardingAction, OnboardingDialog};
+pub use passphrase_dialog::{PassphraseDialog, PassphrasePurpose};
+pub use preferences_dialog::PreferencesDialog;
+pub use profile_broadcast_dialog::ProfileBroadcastDialog;
 pub use publish_dialog::PublishDialog;
+pub use qr_dialog::QrDialog;
+pub use recover_dialog::RecoverDialog;
 pub use relay_dialog::RelayDialog;
+pub use relay_test_dialog::RelayTestDialog;
+pub use search_dialog::SearchDialog;
 pub use settings_dialog::SettingsDialog;
+pub use shortcuts_dialog::ShortcutsDialog;
 pub use sidebar::{Sidebar, SidebarAction};
+pub use table_dialog::TableDialog;
+pub use trash_dialog::TrashDialog;
+pub use writing_stats_dialog::WritingStatsDialog;
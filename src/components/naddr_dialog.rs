@@ -0,0 +1,78 @@
+use crate::nostr_client::ArticleAddress;
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, Window};
+
+/// Shows a published article's naddr so it can be opened and edited in other
+/// NIP-23 clients
+#[derive(Default)]
+pub struct NaddrDialog {
+    open: bool,
+    title: String,
+    address: Option<ArticleAddress>,
+}
+
+impl NaddrDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, title: String, address: ArticleAddress) {
+        self.open = true;
+        self.title = title;
+        self.address = Some(address);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn show(&mut self, ctx: &Context, theme_colors: &ThemeColors) {
+        if !self.open {
+            return;
+        }
+
+        let Some(address) = &self.address else {
+            self.open = false;
+            return;
+        };
+
+        let mut window_open = self.open;
+
+        Window::new("🔗 Article Address")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.label(RichText::new(&self.title).strong().color(theme_colors.primary));
+                ui.label(
+                    RichText::new("Paste the naddr into another long-form Nostr client to open and edit this same article. Publishing from there replaces the version published here.")
+                        .small()
+                        .color(theme_colors.text_secondary),
+                );
+                ui.add_space(8.0);
+
+                ui.label(RichText::new("naddr").strong());
+                ui.horizontal(|ui| {
+                    ui.add(egui::Label::new(RichText::new(&address.naddr).monospace().small()).wrap());
+                });
+                if ui.button("📋 Copy naddr").clicked() {
+                    ui.output_mut(|o| o.copied_text = address.naddr.clone());
+                }
+
+                ui.add_space(8.0);
+                ui.label(RichText::new("Coordinate (kind:pubkey:d)").strong());
+                ui.add(egui::Label::new(RichText::new(&address.coordinate).monospace().small()).wrap());
+                if ui.button("📋 Copy coordinate").clicked() {
+                    ui.output_mut(|o| o.copied_text = address.coordinate.clone());
+                }
+                ui.label(
+                    RichText::new("30023 is the long-form article kind, followed by the author's hex public key and the article's stable \"d\" identifier.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+            });
+
+        self.open = window_open;
+    }
+}
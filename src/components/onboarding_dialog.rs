@@ -0,0 +1,190 @@
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, Window};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OnboardingStep {
+    Welcome,
+    Identity,
+    Relays,
+    Blossom,
+    FirstPost,
+}
+
+impl OnboardingStep {
+    fn all() -> [OnboardingStep; 5] {
+        [
+            OnboardingStep::Welcome,
+            OnboardingStep::Identity,
+            OnboardingStep::Relays,
+            OnboardingStep::Blossom,
+            OnboardingStep::FirstPost,
+        ]
+    }
+
+    fn index(&self) -> usize {
+        Self::all().iter().position(|s| s == self).unwrap_or(0)
+    }
+
+    fn next(&self) -> Option<OnboardingStep> {
+        Self::all().get(self.index() + 1).copied()
+    }
+
+    fn previous(&self) -> Option<OnboardingStep> {
+        self.index().checked_sub(1).map(|i| Self::all()[i])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnboardingAction {
+    None,
+    OpenCredentials,
+    OpenRelaySettings,
+    OpenBlossomSettings,
+    CreateFirstPost,
+    /// The user finished or skipped onboarding; don't show it again
+    Finished,
+}
+
+/// One-time walkthrough shown to new users on first launch
+pub struct OnboardingDialog {
+    open: bool,
+    step: OnboardingStep,
+}
+
+impl Default for OnboardingDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            step: OnboardingStep::Welcome,
+        }
+    }
+}
+
+impl OnboardingDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.step = OnboardingStep::Welcome;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn show(&mut self, ctx: &Context, has_credentials: bool, theme_colors: &ThemeColors) -> OnboardingAction {
+        let mut action = OnboardingAction::None;
+
+        if !self.open {
+            return action;
+        }
+
+        let mut window_open = self.open;
+
+        Window::new("👋 Welcome to Blogster")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(480.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!("Step {} of {}", self.step.index() + 1, OnboardingStep::all().len()))
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+                ui.add_space(8.0);
+
+                match self.step {
+                    OnboardingStep::Welcome => {
+                        ui.heading(RichText::new("Write long-form posts for Nostr").color(theme_colors.primary));
+                        ui.add_space(8.0);
+                        ui.label("Blogster publishes Markdown articles to Nostr relays and uploads images to Blossom servers.");
+                        ui.label("This short setup covers your key, your relays and an image server. You can change everything later from the ⚙️ Settings menu.");
+                    }
+                    OnboardingStep::Identity => {
+                        ui.heading(RichText::new("🔑 Your Nostr identity").color(theme_colors.primary));
+                        ui.add_space(8.0);
+                        ui.label("Posts are signed with your Nostr private key (nsec). Generate a new key, or import the one you already use in other Nostr apps.");
+                        ui.add_space(8.0);
+                        if has_credentials {
+                            ui.colored_label(theme_colors.success, "✅ A key is already set up");
+                        }
+                        if ui.button("🔑 Generate or import a key").clicked() {
+                            action = OnboardingAction::OpenCredentials;
+                        }
+                    }
+                    OnboardingStep::Relays => {
+                        ui.heading(RichText::new("🌐 Relays").color(theme_colors.primary));
+                        ui.add_space(8.0);
+                        ui.label("Relays are the servers your posts are published to. Blogster comes with a set of popular defaults; add the relays your readers use.");
+                        ui.add_space(8.0);
+                        if ui.button("🌐 Choose relays").clicked() {
+                            action = OnboardingAction::OpenRelaySettings;
+                        }
+                    }
+                    OnboardingStep::Blossom => {
+                        ui.heading(RichText::new("🌸 Image hosting").color(theme_colors.primary));
+                        ui.add_space(8.0);
+                        ui.label("Images are uploaded to a Blossom server and linked from your post. Pick a server you trust, or keep the default.");
+                        ui.add_space(8.0);
+                        if ui.button("🌸 Set Blossom server").clicked() {
+                            action = OnboardingAction::OpenBlossomSettings;
+                        }
+                    }
+                    OnboardingStep::FirstPost => {
+                        ui.heading(RichText::new("✏️ Your first post").color(theme_colors.primary));
+                        ui.add_space(8.0);
+                        ui.label("You're ready. Create a draft, write in Markdown, then use 🚀 Publish when it's done.");
+                        ui.add_space(8.0);
+                        if ui.button(RichText::new("✏️ Create my first post").color(theme_colors.success)).clicked() {
+                            action = OnboardingAction::CreateFirstPost;
+                        }
+                    }
+                }
+
+                ui.add_space(16.0);
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    if ui.button("Skip setup").clicked() {
+                        action = OnboardingAction::Finished;
+                    }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        match self.step.next() {
+                            Some(next) => {
+                                if ui.button("Next ➡").clicked() {
+                                    self.step = next;
+                                }
+                            }
+                            None => {
+                                if ui.button("✅ Finish").clicked() {
+                                    action = OnboardingAction::Finished;
+                                }
+                            }
+                        }
+
+                        if let Some(previous) = self.step.previous() {
+                            if ui.button("⬅ Back").clicked() {
+                                self.step = previous;
+                            }
+                        }
+                    });
+                });
+            });
+
+        // Closing the window counts as skipping
+        if !window_open {
+            action = OnboardingAction::Finished;
+        }
+
+        if matches!(action, OnboardingAction::Finished | OnboardingAction::CreateFirstPost) {
+            self.open = false;
+        }
+
+        action
+    }
+}
@@ -0,0 +1,113 @@
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, TextEdit, Window};
+
+/// Why the passphrase is being asked for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PassphrasePurpose {
+    /// Decrypt a private post so it can be opened
+    Unlock(uuid::Uuid),
+    /// Choose the passphrase the open private post is saved with
+    Encrypt,
+}
+
+/// Asks for the passphrase that private posts are encrypted with
+pub struct PassphraseDialog {
+    open: bool,
+    purpose: PassphrasePurpose,
+    passphrase: String,
+    confirmation: String,
+    error_message: Option<String>,
+}
+
+impl Default for PassphraseDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            purpose: PassphrasePurpose::Encrypt,
+            passphrase: String::new(),
+            confirmation: String::new(),
+            error_message: None,
+        }
+    }
+}
+
+impl PassphraseDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, purpose: PassphrasePurpose) {
+        self.open = true;
+        self.purpose = purpose;
+        self.passphrase.clear();
+        self.confirmation.clear();
+        self.error_message = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns the passphrase once the user confirms it
+    pub fn show(&mut self, ctx: &Context, theme_colors: &ThemeColors) -> Option<(PassphrasePurpose, String)> {
+        if !self.open {
+            return None;
+        }
+
+        let mut result = None;
+        let mut window_open = self.open;
+        let choosing = self.purpose == PassphrasePurpose::Encrypt;
+        let title = if choosing { "🔒 Set Private Posts Passphrase" } else { "🔒 Unlock Private Post" };
+
+        Window::new(title)
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let explanation = if choosing {
+                    "Private posts are encrypted on disk with this passphrase. Titles and tags stay visible in the sidebar. There is no way to recover the content if you forget it."
+                } else {
+                    "This post is encrypted. Enter your private posts passphrase to open it. It is remembered until Blogster closes."
+                };
+                ui.label(RichText::new(explanation).color(theme_colors.text));
+                ui.add_space(8.0);
+
+                let mut submit = false;
+                ui.horizontal(|ui| {
+                    ui.label("Passphrase:");
+                    let response = ui.add(TextEdit::singleline(&mut self.passphrase).password(true).desired_width(240.0));
+                    submit |= !choosing && response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                });
+                if choosing {
+                    ui.horizontal(|ui| {
+                        ui.label("Repeat:");
+                        let response = ui.add(TextEdit::singleline(&mut self.confirmation).password(true).desired_width(240.0));
+                        submit |= response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    });
+                }
+
+                if let Some(error) = &self.error_message {
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                }
+
+                ui.add_space(8.0);
+                let button = if choosing { "🔒 Encrypt" } else { "🔓 Unlock" };
+                if ui.button(button).clicked() || submit {
+                    if self.passphrase.is_empty() {
+                        self.error_message = Some("Please enter a passphrase".to_string());
+                    } else if choosing && self.passphrase != self.confirmation {
+                        self.error_message = Some("The passphrases don't match".to_string());
+                    } else {
+                        result = Some((self.purpose, std::mem::take(&mut self.passphrase)));
+                    }
+                }
+            });
+
+        self.open = window_open && result.is_none();
+        if result.is_some() {
+            self.confirmation.clear();
+        }
+        result
+    }
+}
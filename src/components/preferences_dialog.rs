@@ -0,0 +1,500 @@
+use crate::app_settings::{format_date, is_valid_date_format, AppSettings, DEFAULT_DATE_FORMAT, MIN_WINDOW_OPACITY};
+use crate::fonts::{load_font_file, FontChoice, DEFAULT_FONT_SIZE, MAX_FONT_SIZE, MIN_FONT_SIZE};
+use crate::storage::Storage;
+use crate::theme::{tag_color, ThemeColors};
+use egui::{Context, RichText, Window};
+
+/// A bundled font list plus a button to pick a font file; returns whether
+/// the choice changed
+fn font_picker(ui: &mut egui::Ui, id: &str, choice: &mut FontChoice, error_message: &mut Option<String>) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source(id)
+            .selected_text(choice.label())
+            .show_ui(ui, |ui| {
+                for bundled in FontChoice::BUNDLED {
+                    let label = bundled.label();
+                    changed |= ui.selectable_value(choice, bundled, label).changed();
+                }
+            });
+        if ui.button("📁 Font file…").on_hover_text("Use a TTF or OTF font installed on this computer").clicked() {
+            if let Some(path) = rfd::FileDialog::new().add_filter("Fonts", &["ttf", "otf"]).pick_file() {
+                match load_font_file(&path) {
+                    Ok(_) => {
+                        *choice = FontChoice::File(path);
+                        *error_message = None;
+                        changed = true;
+                    }
+                    Err(e) => *error_message = Some(format!("{:#}", e)),
+                }
+            }
+        }
+    });
+    changed
+}
+
+pub struct PreferencesDialog {
+    open: bool,
+    settings: AppSettings,
+    error_message: Option<String>,
+    success_message: Option<String>,
+    settings_changed: bool,
+    known_tags: Vec<String>,
+}
+
+impl Default for PreferencesDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            settings: AppSettings::default(),
+            error_message: None,
+            success_message: None,
+            settings_changed: false,
+            known_tags: Vec::new(),
+        }
+    }
+}
+
+impl PreferencesDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `known_tags` are the tags used across all posts, offered for coloring
+    pub fn open(&mut self, current_settings: &AppSettings, known_tags: Vec<String>) {
+        self.open = true;
+        self.settings = current_settings.clone();
+        self.known_tags = known_tags;
+        self.error_message = None;
+        self.success_message = None;
+        self.settings_changed = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns the new settings if they were saved
+    pub fn show(&mut self, ctx: &Context, storage: &Storage, theme_colors: &ThemeColors) -> Option<AppSettings> {
+        let mut result = None;
+        let mut should_close = false;
+
+        if !self.open {
+            return result;
+        }
+
+        let mut window_open = self.open;
+
+        Window::new("🛠 Preferences")
+            .open(&mut window_open)
+            .resizable(true)
+            .default_width(450.0)
+            .show(ctx, |ui| {
+                ui.heading(RichText::new("Publishing").strong().color(theme_colors.primary));
+                ui.separator();
+
+                if ui
+                    .checkbox(&mut self.settings.include_client_tag, "Identify Blogster as the publishing client")
+                    .changed()
+                {
+                    self.settings_changed = true;
+                }
+                ui.label(
+                    RichText::new("Adds a \"client\" tag with the app name and version to published articles.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(8.0);
+                if ui
+                    .checkbox(&mut self.settings.confirm_before_publish, "Confirm before publishing")
+                    .changed()
+                {
+                    self.settings_changed = true;
+                }
+                ui.label(
+                    RichText::new("Shows the relays and identity a post will be published to before broadcasting.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label("Publish timeout:");
+                    if ui
+                        .add(egui::DragValue::new(&mut self.settings.publish_timeout_secs).range(5..=300).suffix(" s"))
+                        .changed()
+                    {
+                        self.settings_changed = true;
+                    }
+                });
+                ui.label(
+                    RichText::new("Relays that haven't answered by then are reported as timed out.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(8.0);
+                if ui
+                    .checkbox(&mut self.settings.strip_image_metadata, "Remove metadata from uploaded images")
+                    .changed()
+                {
+                    self.settings_changed = true;
+                }
+                ui.label(
+                    RichText::new("Strips EXIF data such as GPS location and camera model from photos before they are uploaded.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("Editor").strong().color(theme_colors.primary));
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Warn about lines longer than:");
+                    if ui
+                        .add(egui::DragValue::new(&mut self.settings.max_line_length).range(80..=20000).suffix(" chars"))
+                        .changed()
+                    {
+                        self.settings_changed = true;
+                    }
+                });
+                ui.label(
+                    RichText::new("Some clients render very long unbroken lines poorly. Code blocks are never flagged.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut self.settings.autosave, "Autosave after").changed() {
+                        self.settings_changed = true;
+                    }
+                    if ui
+                        .add_enabled(
+                            self.settings.autosave,
+                            egui::DragValue::new(&mut self.settings.autosave_interval_secs).range(5..=600).suffix(" s"),
+                        )
+                        .changed()
+                    {
+                        self.settings_changed = true;
+                    }
+                    ui.label("without edits");
+                });
+                ui.label(
+                    RichText::new("Saves the open post quietly. Private posts are only autosaved once the passphrase has been entered.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("New Posts").strong().color(theme_colors.primary));
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Default title:");
+                    if ui.text_edit_singleline(&mut self.settings.new_post_title).changed() {
+                        self.settings_changed = true;
+                    }
+                });
+                ui.label("Default content:");
+                if ui
+                    .add(
+                        egui::TextEdit::multiline(&mut self.settings.new_post_content)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_rows(4)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .changed()
+                {
+                    self.settings_changed = true;
+                }
+                ui.label(
+                    RichText::new("Use {{date}} to insert today's date.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label("Date format:");
+                    if ui
+                        .add(egui::TextEdit::singleline(&mut self.settings.date_format).desired_width(120.0))
+                        .on_hover_text("strftime pattern, e.g. %Y-%m-%d or %B %-d, %Y")
+                        .changed()
+                    {
+                        self.settings_changed = true;
+                    }
+                    if is_valid_date_format(&self.settings.date_format) {
+                        let example = format_date(&self.settings.date_format, chrono::Local::now().date_naive());
+                        ui.label(RichText::new(example).color(theme_colors.text_secondary));
+                    } else {
+                        ui.label(
+                            RichText::new(format!("Invalid format, {} will be used", DEFAULT_DATE_FORMAT))
+                                .color(theme_colors.warning),
+                        );
+                    }
+                });
+                ui.label(
+                    RichText::new("Press Ctrl+Shift+D in the editor, or type {{date}}, to insert today's date.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("Crash Recovery").strong().color(theme_colors.primary));
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Save unsaved edits every:");
+                    if ui
+                        .add(egui::DragValue::new(&mut self.settings.recovery_interval_secs).range(2..=600).suffix(" s"))
+                        .changed()
+                    {
+                        self.settings_changed = true;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Recovery folder:");
+                    let folder = self
+                        .settings
+                        .recovery_dir
+                        .clone()
+                        .unwrap_or_else(|| storage.default_recovery_dir());
+                    ui.label(RichText::new(folder.display().to_string()).monospace().small());
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("📁 Choose…").clicked() {
+                        if let Some(dir) = rfd::FileDialog::new().set_title("Choose recovery folder").pick_folder() {
+                            self.settings.recovery_dir = Some(dir);
+                            self.settings_changed = true;
+                        }
+                    }
+                    if self.settings.recovery_dir.is_some() && ui.button("↺ Use default").clicked() {
+                        self.settings.recovery_dir = None;
+                        self.settings_changed = true;
+                    }
+                });
+                ui.label(
+                    RichText::new("Unsaved edits are copied here so they survive a crash, e.g. a RAM disk or a synced folder. Snapshots already in the old folder are not moved.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("Notifications").strong().color(theme_colors.primary));
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Hide success messages after:");
+                    if ui
+                        .add(egui::DragValue::new(&mut self.settings.success_dismiss_secs).range(1..=120).suffix(" s"))
+                        .changed()
+                    {
+                        self.settings_changed = true;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    let mut auto_dismiss_errors = self.settings.error_dismiss_secs.is_some();
+                    if ui.checkbox(&mut auto_dismiss_errors, "Hide error messages after:").changed() {
+                        self.settings.error_dismiss_secs = auto_dismiss_errors.then_some(10);
+                        self.settings_changed = true;
+                    }
+                    if let Some(secs) = &mut self.settings.error_dismiss_secs {
+                        if ui.add(egui::DragValue::new(secs).range(1..=600).suffix(" s")).changed() {
+                            self.settings_changed = true;
+                        }
+                    }
+                });
+                ui.label(
+                    RichText::new("When unticked, errors stay visible until you dismiss them.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("Tag Colors").strong().color(theme_colors.primary));
+                ui.separator();
+
+                if self.known_tags.is_empty() {
+                    ui.label(RichText::new("Tags you add to posts will show up here.").color(theme_colors.text_muted));
+                }
+
+                egui::ScrollArea::vertical().id_source("tag_colors").max_height(160.0).show(ui, |ui| {
+                    for tag in &self.known_tags {
+                        let key = tag.to_lowercase();
+                        ui.horizontal(|ui| {
+                            let current = tag_color(tag, &self.settings.tag_colors);
+                            let mut rgb = [current.r(), current.g(), current.b()];
+                            if ui.color_edit_button_srgb(&mut rgb).changed() {
+                                self.settings.tag_colors.insert(key.clone(), rgb);
+                                self.settings_changed = true;
+                            }
+                            ui.label(RichText::new(format!("#{}", tag)).color(current));
+
+                            if self.settings.tag_colors.contains_key(&key)
+                                && ui.small_button("↺").on_hover_text("Use the automatic color").clicked()
+                            {
+                                self.settings.tag_colors.remove(&key);
+                                self.settings_changed = true;
+                            }
+                        });
+                    }
+                });
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("Fonts").strong().color(theme_colors.primary));
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Interface font:");
+                    if font_picker(ui, "ui_font", &mut self.settings.ui_font, &mut self.error_message) {
+                        self.settings_changed = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Editor font:");
+                    if font_picker(ui, "editor_font", &mut self.settings.editor_font, &mut self.error_message) {
+                        self.settings_changed = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Text size:");
+                    if ui
+                        .add(egui::Slider::new(&mut self.settings.font_size, MIN_FONT_SIZE..=MAX_FONT_SIZE).step_by(0.5).suffix(" pt"))
+                        .changed()
+                    {
+                        self.settings_changed = true;
+                    }
+                    if self.settings.font_size != DEFAULT_FONT_SIZE && ui.small_button("↺").on_hover_text("Use the default size").clicked() {
+                        self.settings.font_size = DEFAULT_FONT_SIZE;
+                        self.settings_changed = true;
+                    }
+                });
+                ui.label(
+                    RichText::new("The editor font is used for post content and code. The built-in fonts stay as a fallback for characters and emoji a font doesn't have.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("Window").strong().color(theme_colors.primary));
+                ui.separator();
+
+                if ui
+                    .checkbox(&mut self.settings.always_on_top, "Keep window on top of other windows")
+                    .changed()
+                {
+                    self.settings_changed = true;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Window opacity:");
+                    if ui
+                        .add(egui::Slider::new(&mut self.settings.window_opacity, MIN_WINDOW_OPACITY..=1.0).show_value(false))
+                        .changed()
+                    {
+                        self.settings_changed = true;
+                    }
+                    ui.label(format!("{:.0}%", self.settings.window_opacity * 100.0));
+                });
+                ui.label(
+                    RichText::new("Handy for writing over reference material. Opacity needs a desktop that supports transparent windows; elsewhere the window stays opaque.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("Export").strong().color(theme_colors.primary));
+                ui.separator();
+
+                if ui
+                    .checkbox(&mut self.settings.html_export_stats, "Include post details in HTML exports")
+                    .changed()
+                {
+                    self.settings_changed = true;
+                }
+                ui.label(
+                    RichText::new("Adds the date, word count, reading time and tags under the title.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+                ui.heading(RichText::new("Advanced").strong().color(theme_colors.primary));
+                ui.separator();
+
+                if ui
+                    .checkbox(&mut self.settings.developer_tools, "Show developer tools")
+                    .changed()
+                {
+                    self.settings_changed = true;
+                }
+                ui.label(
+                    RichText::new("Adds relay troubleshooting actions to the Tools menu.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.add_space(16.0);
+
+                // Show error/success messages
+                if let Some(error) = &self.error_message {
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                    ui.add_space(8.0);
+                }
+
+                if let Some(success) = &self.success_message {
+                    ui.colored_label(theme_colors.success, format!("✅ {}", success));
+                    ui.add_space(8.0);
+                }
+
+                ui.separator();
+
+                // Action buttons
+                ui.horizontal(|ui| {
+                    if ui.button(RichText::new("💾 Save").color(theme_colors.success)).clicked() && self.settings_changed {
+                        let recovery_check = self
+                            .settings
+                            .recovery_dir
+                            .as_deref()
+                            .map_or(Ok(()), |dir| storage.check_recovery_dir(dir));
+
+                        if let Err(e) = recovery_check {
+                            tracing::warn!("Recovery folder rejected: {:#}", e);
+                            self.error_message = Some(format!("Recovery folder can't be used: {:#}", e));
+                        } else if let Err(e) = storage.save_app_settings(&self.settings) {
+                            tracing::error!("Failed to save app settings: {}", e);
+                            self.error_message = Some("Failed to save preferences".to_string());
+                        } else {
+                            result = Some(self.settings.clone());
+                            self.settings_changed = false;
+                            self.success_message = Some("Preferences saved!".to_string());
+                        }
+                    }
+
+                    if ui.button(RichText::new("🔄 Reset").color(theme_colors.warning)).clicked() {
+                        self.settings = AppSettings::default();
+                        self.settings_changed = true;
+                        self.success_message = Some("Preferences reset to defaults".to_string());
+                    }
+
+                    if ui.button(RichText::new("❌ Cancel").color(theme_colors.error)).clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        // Update the open state from the window
+        self.open = window_open;
+
+        if should_close {
+            self.open = false;
+        }
+
+        result
+    }
+}
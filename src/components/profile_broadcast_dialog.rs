@@ -0,0 +1,133 @@
+use crate::activity::ActivityTracker;
+use crate::nostr_client::{NostrClient, PublishReport};
+use crate::relay_settings::RelaySettings;
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, Window};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Rebroadcasts the profile (kind 0) to all active relays and shows how each relay responded
+#[derive(Default)]
+pub struct ProfileBroadcastDialog {
+    open: bool,
+    report: Option<PublishReport>,
+    error_message: Option<String>,
+    pending: Option<mpsc::Receiver<Result<PublishReport, String>>>,
+}
+
+impl ProfileBroadcastDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Open the dialog and start broadcasting straight away
+    pub fn start(
+        &mut self,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+    ) {
+        if self.pending.is_some() {
+            self.open = true;
+            return;
+        }
+
+        self.open = true;
+        self.report = None;
+        self.error_message = None;
+
+        let (sender, receiver) = mpsc::channel();
+        self.pending = Some(receiver);
+
+        let client = nostr_client.clone();
+        let relay_settings = relay_settings.clone();
+        let activity_guard = activity.begin_critical("Republishing profile");
+
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = {
+                let client_guard = client.lock().await;
+                client_guard
+                    .republish_profile(&relay_settings)
+                    .await
+                    .map_err(|e| e.to_string())
+            };
+
+            let _ = sender.send(result);
+        });
+    }
+
+    pub fn show(&mut self, ctx: &Context, theme_colors: &ThemeColors) {
+        // Poll an in-flight broadcast
+        if let Some(receiver) = &self.pending {
+            match receiver.try_recv() {
+                Ok(Ok(report)) => {
+                    self.pending = None;
+                    self.report = Some(report);
+                }
+                Ok(Err(e)) => {
+                    self.pending = None;
+                    self.error_message = Some(e);
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.pending = None;
+                    self.error_message = Some("Profile broadcast stopped unexpectedly".to_string());
+                }
+            }
+        }
+
+        if !self.open {
+            return;
+        }
+
+        let mut window_open = self.open;
+
+        Window::new("📣 Republish Profile")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if self.pending.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Sending your profile to all active relays...");
+                    });
+                }
+
+                if let Some(error) = &self.error_message {
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                }
+
+                if let Some(report) = &self.report {
+                    ui.label(
+                        RichText::new(format!("Profile event {}", report.event_id.to_hex()))
+                            .small()
+                            .monospace()
+                            .color(theme_colors.text_muted),
+                    );
+                    ui.add_space(4.0);
+
+                    for relay in &report.successful_relays {
+                        ui.colored_label(theme_colors.success, format!("✅ {}", relay));
+                    }
+                    for (relay, reason) in &report.failed_relays {
+                        ui.colored_label(theme_colors.error, format!("❌ {}: {}", relay, reason));
+                    }
+                    for relay in &report.timed_out_relays {
+                        ui.colored_label(theme_colors.warning, format!("⏱ {}: timed out", relay));
+                    }
+                }
+            });
+
+        self.open = window_open;
+    }
+}
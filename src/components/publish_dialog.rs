@@ -1,17 +1,96 @@
-use crate::nostr_client::NostrClient;
+use crate::activity::ActivityTracker;
+use crate::nostr_client::{NostrClient, PublishReport};
 use crate::post::BlogPost;
 use crate::relay_settings::RelaySettings;
 use crate::theme::CatppuccinMocha;
+use chrono::{DateTime, Local, NaiveDate, Timelike, Utc};
 use egui::{RichText, Window};
+use std::sync::mpsc;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// Largest size the featured image thumbnail is drawn at
+const THUMBNAIL_SIZE: egui::Vec2 = egui::vec2(320.0, 180.0);
+
+/// Hour of the day offered for scheduled posts that don't have a time yet
+const DEFAULT_SCHEDULE_HOUR: u32 = 8;
+
+/// Add a one-off relay to the post, or tick it again if it is already a write relay
+fn add_extra_relay(post: &mut BlogPost, input: &str, write_relays: &[String]) -> Result<(), String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Please enter a relay URL".to_string());
+    }
+
+    let url = RelaySettings::normalize_relay_url(input);
+    RelaySettings::validate_relay_url(&url)?;
+    if write_relays.contains(&url) {
+        post.excluded_relays.retain(|relay| relay != &url);
+    } else if !post.extra_relays.contains(&url) {
+        post.extra_relays.push(url);
+    }
+    Ok(())
+}
+
+/// A date and local time as the UTC instant it stands for, if it exists
+fn local_schedule_time(date: NaiveDate, hour: u32, minute: u32) -> Option<DateTime<Utc>> {
+    let local = date.and_hms_opt(hour, minute, 0)?.and_local_timezone(Local).earliest()?;
+    Some(local.with_timezone(&Utc))
+}
+
+/// Thumbnail of the image that will be sent as the article's `image` tag
+fn show_featured_image(ui: &mut egui::Ui, image_url: Option<&str>) {
+    let url = image_url.map(str::trim).unwrap_or_default();
+
+    if url.starts_with("http://") || url.starts_with("https://") {
+        let image = egui::Image::from_uri(url)
+            .max_size(THUMBNAIL_SIZE)
+            .maintain_aspect_ratio(true)
+            .rounding(4.0);
+
+        // Pending loads show a spinner; only real failures get the placeholder
+        if image.load_for_size(ui.ctx(), THUMBNAIL_SIZE).is_err() {
+            ui.label(RichText::new("🖼 Featured image could not be loaded").color(CatppuccinMocha::RED))
+                .on_hover_text(url);
+        } else {
+            ui.add(image).on_hover_text(url);
+        }
+    } else if url.starts_with("file://") {
+        ui.label(RichText::new("⚠ The featured image is a local file:// URL that readers can't load. Upload it first.").color(CatppuccinMocha::YELLOW));
+    } else if url.is_empty() {
+        ui.label(RichText::new("🖼 No featured image").color(CatppuccinMocha::OVERLAY1));
+    } else {
+        ui.label(RichText::new(format!("🖼 No preview for image URL: {}", url)).color(CatppuccinMocha::OVERLAY1));
+    }
+}
+
 pub struct PublishDialog {
     open: bool,
     post: Option<BlogPost>,
     is_publishing: bool,
+    /// Outcome of the running publish task
+    /// Running publish: the report and the npub the post was signed with
+    publishing: Option<mpsc::Receiver<Result<(PublishReport, Option<String>), String>>>,
+    /// Relay counts shown once the post has been published
+    published_message: Option<String>,
+    /// How each relay answered the last publish
+    published_report: Option<PublishReport>,
     error_message: Option<String>,
     progress_message: Option<String>,
+    confirm_before_publish: bool,
+    awaiting_confirmation: bool,
+    relay_selection_changed: bool,
+    /// One-off relay URL typed in, not yet added to the post
+    new_relay: String,
+    /// Signed event JSON being prepared for the clipboard
+    signing: Option<mpsc::Receiver<Result<String, String>>>,
+    copied_message: Option<String>,
+    /// Local date and time picked for publishing later
+    schedule_date: NaiveDate,
+    schedule_hour: u32,
+    schedule_minute: u32,
+    /// A post that was scheduled or unscheduled and its new time, to be saved
+    schedule_change: Option<(uuid::Uuid, Option<DateTime<Utc>>)>,
 }
 
 impl Default for PublishDialog {
@@ -20,8 +99,21 @@ impl Default for PublishDialog {
             open: false,
             post: None,
             is_publishing: false,
+            publishing: None,
+            published_message: None,
+            published_report: None,
             error_message: None,
             progress_message: None,
+            confirm_before_publish: true,
+            awaiting_confirmation: false,
+            relay_selection_changed: false,
+            new_relay: String::new(),
+            signing: None,
+            copied_message: None,
+            schedule_date: Local::now().date_naive(),
+            schedule_hour: DEFAULT_SCHEDULE_HOUR,
+            schedule_minute: 0,
+            schedule_change: None,
         }
     }
 }
@@ -32,11 +124,53 @@ impl PublishDialog {
     }
 
     pub fn open(&mut self, post: BlogPost) {
+        // Offer the current schedule, or tomorrow morning
+        let scheduled = post.scheduled_at.map(|t| t.with_timezone(&Local).naive_local()).unwrap_or_else(|| {
+            let tomorrow = Local::now().date_naive().succ_opt().unwrap_or_else(|| Local::now().date_naive());
+            tomorrow.and_hms_opt(DEFAULT_SCHEDULE_HOUR, 0, 0).unwrap_or_default()
+        });
+        self.schedule_date = scheduled.date();
+        self.schedule_hour = scheduled.hour();
+        self.schedule_minute = scheduled.minute();
+        self.schedule_change = None;
+
         self.open = true;
         self.post = Some(post);
         self.is_publishing = false;
+        self.publishing = None;
+        self.published_message = None;
+        self.published_report = None;
         self.error_message = None;
         self.progress_message = None;
+        self.awaiting_confirmation = false;
+        self.relay_selection_changed = false;
+        self.new_relay.clear();
+        self.copied_message = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// The post's relay opt-outs and one-off relays if the user changed them,
+    /// so they can be saved
+    pub fn take_relay_selection(&mut self) -> Option<(uuid::Uuid, Vec<String>, Vec<String>)> {
+        if !self.relay_selection_changed {
+            return None;
+        }
+        self.relay_selection_changed = false;
+        self.post.as_ref().map(|post| (post.id, post.excluded_relays.clone(), post.extra_relays.clone()))
+    }
+
+    /// The post's new publishing time if the user scheduled or unscheduled
+    /// it, so it can be saved
+    pub fn take_schedule(&mut self) -> Option<(uuid::Uuid, Option<DateTime<Utc>>)> {
+        self.schedule_change.take()
+    }
+
+    /// Whether publishing requires an explicit confirmation step
+    pub fn set_confirm_before_publish(&mut self, enabled: bool) {
+        self.confirm_before_publish = enabled;
     }
 
     pub fn show(
@@ -45,21 +179,93 @@ impl PublishDialog {
         nostr_client: &Arc<Mutex<NostrClient>>,
         relay_settings: &RelaySettings,
         runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
     ) -> Option<BlogPost> {
         if !self.open {
             return None;
         }
 
+        // Copy a freshly signed event once it's ready
+        if let Some(receiver) = &self.signing {
+            match receiver.try_recv() {
+                Ok(Ok(json)) => {
+                    self.signing = None;
+                    ctx.output_mut(|o| o.copied_text = json);
+                    self.error_message = None;
+                    self.copied_message = Some("Signed event copied. Nothing was sent to any relay.".to_string());
+                }
+                Ok(Err(e)) => {
+                    self.signing = None;
+                    self.error_message = Some(e);
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.signing = None;
+                    self.error_message = Some("Signing stopped unexpectedly".to_string());
+                }
+            }
+        }
+
+        // Pick up the result of a running publish
+        let mut published_post = None;
+        if let Some(receiver) = &self.publishing {
+            match receiver.try_recv() {
+                Ok(Ok((report, npub))) => {
+                    self.publishing = None;
+                    self.is_publishing = false;
+                    self.progress_message = None;
+                    let updating = self.post.as_ref().is_some_and(|p| p.has_been_published());
+                    self.published_message = Some(published_summary(&report, updating));
+                    if let Some(post) = &mut self.post {
+                        // Only the relays that accepted the event count as published to
+                        post.set_published(report.event_id.to_hex(), report.successful_relays.clone(), report.published_at);
+                        post.published_by = npub;
+                        published_post = Some(post.clone());
+                    }
+                    self.published_report = Some(report);
+                }
+                Ok(Err(e)) => {
+                    self.publishing = None;
+                    self.is_publishing = false;
+                    self.progress_message = None;
+                    self.error_message = Some(e);
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.publishing = None;
+                    self.is_publishing = false;
+                    self.progress_message = None;
+                    self.error_message = Some("Publishing stopped unexpectedly".to_string());
+                }
+            }
+        }
+
         let mut close_dialog = false;
-        let published_post = None;
         let mut should_start_publishing = false;
+        let mut should_sign_copy = false;
+        let is_signing = self.signing.is_some();
+        let relays_locked = self.is_publishing || self.published_message.is_some();
+        let updating = self.post.as_ref().is_some_and(|p| p.has_been_published());
+        let title = if updating { "🔄 Update Published Article" } else { "🚀 Publish to Nostr" };
+        let current_npub = nostr_client.try_lock().ok().and_then(|client| client.npub());
+        // Published before under another account; publishing now would cross-post
+        let other_account = self
+            .post
+            .as_ref()
+            .and_then(|p| p.published_by.clone())
+            .filter(|published_by| current_npub.as_ref().is_some_and(|npub| npub != published_by));
 
-        Window::new("🚀 Publish to Nostr")
+        Window::new(title)
+            .id(egui::Id::new("publish_dialog"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
             .show(ctx, |ui| {
-                if let Some(post) = &self.post {
+                if let Some(post) = &mut self.post {
                     ui.vertical(|ui| {
                         ui.spacing_mut().item_spacing.y = 10.0;
 
@@ -77,34 +283,183 @@ impl PublishDialog {
                                         }
                                     });
                                 }
+
+                                show_featured_image(ui, post.image_url.as_deref());
+                                if !post.gallery_images.is_empty() {
+                                    ui.label(format!("🖼 {} more gallery image{}", post.gallery_images.len(), if post.gallery_images.len() == 1 { "" } else { "s" }));
+                                }
+
+                                if let Some(published_by) = &other_account {
+                                    ui.label(RichText::new("⚠️ This post was published by a different account. Publishing now creates a separate article under the account you are signed in with.").color(CatppuccinMocha::YELLOW));
+                                    ui.label(RichText::new(format!("Published by: {}", published_by)).small().monospace());
+                                } else if updating {
+                                    ui.label(RichText::new("🔄 This replaces the article you already published, at the same address. It keeps its original publication date.").color(CatppuccinMocha::BLUE));
+                                }
+
+                                if post.unlisted {
+                                    ui.label(RichText::new("🔗 Unlisted: hashtags, summary and image will not be published").color(CatppuccinMocha::YELLOW));
+                                    ui.label(RichText::new("The article stays reachable by its link; relays and clients still decide what is discoverable.").small());
+                                }
                             });
                         });
 
                         ui.separator();
 
-                        // Relay information
+                        // Relay selection for this post
                         ui.label(RichText::new("Publishing to relays:").strong());
-                        for relay in relay_settings.get_active_relays() {
-                            ui.label(format!("• {}", relay));
+                        ui.label(RichText::new("Untick a relay to skip it, or add one, for this post only.").small().color(CatppuccinMocha::OVERLAY1));
+                        let write_relays = relay_settings.get_write_relays();
+                        for relay in write_relays.iter().cloned() {
+                            let mut included = !post.excluded_relays.contains(&relay);
+                            if ui.add_enabled(!relays_locked, egui::Checkbox::new(&mut included, relay.as_str())).changed() {
+                                if included {
+                                    post.excluded_relays.retain(|r| r != &relay);
+                                } else {
+                                    post.excluded_relays.push(relay.clone());
+                                }
+                                self.relay_selection_changed = true;
+                            }
+                        }
+                        for relay in post.extra_relays.clone() {
+                            let mut included = true;
+                            if ui.add_enabled(!relays_locked, egui::Checkbox::new(&mut included, relay.as_str()))
+                                .on_hover_text("Added for this post only. Untick to remove it.")
+                                .changed()
+                            {
+                                post.extra_relays.retain(|r| r != &relay);
+                                self.relay_selection_changed = true;
+                            }
+                        }
+                        if !relays_locked {
+                            ui.horizontal(|ui| {
+                                let response = ui.add(
+                                    egui::TextEdit::singleline(&mut self.new_relay)
+                                        .hint_text("wss://relay.example.com")
+                                        .desired_width(220.0),
+                                );
+                                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                if ui.button("➕ Add relay").on_hover_text("Also publish this post to another relay").clicked() || submitted {
+                                    match add_extra_relay(post, &self.new_relay, &write_relays) {
+                                        Ok(()) => {
+                                            self.new_relay.clear();
+                                            self.error_message = None;
+                                            self.relay_selection_changed = true;
+                                        }
+                                        Err(e) => self.error_message = Some(e),
+                                    }
+                                }
+                            });
                         }
 
                         ui.separator();
 
+                        // Publish automatically at a later time
+                        if !relays_locked {
+                            ui.label(RichText::new("⏰ Publish later:").strong());
+                            if let Some(scheduled_at) = post.scheduled_at {
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "Scheduled for {}",
+                                            scheduled_at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                                        ))
+                                        .color(CatppuccinMocha::BLUE),
+                                    );
+                                    if ui.small_button("✖ Unschedule").clicked() {
+                                        post.scheduled_at = None;
+                                        self.schedule_change = Some((post.id, None));
+                                    }
+                                });
+                            }
+                            ui.horizontal(|ui| {
+                                ui.add(egui_extras::DatePickerButton::new(&mut self.schedule_date).id_source("publish_schedule_date"));
+                                ui.add(egui::DragValue::new(&mut self.schedule_hour).range(0..=23).custom_formatter(|n, _| format!("{:02}", n)));
+                                ui.label(":");
+                                ui.add(egui::DragValue::new(&mut self.schedule_minute).range(0..=59).custom_formatter(|n, _| format!("{:02}", n)));
+                                let label = if post.scheduled_at.is_some() { "Reschedule" } else { "⏰ Schedule" };
+                                if ui.button(label).on_hover_text("Publish automatically at this time while Blogster is running").clicked() {
+                                    match local_schedule_time(self.schedule_date, self.schedule_hour, self.schedule_minute) {
+                                        Some(time) if time > Utc::now() => {
+                                            post.scheduled_at = Some(time);
+                                            self.schedule_change = Some((post.id, Some(time)));
+                                            close_dialog = true;
+                                        }
+                                        Some(_) => self.error_message = Some("Pick a time in the future".to_string()),
+                                        None => self.error_message = Some("That time doesn't exist on this date".to_string()),
+                                    }
+                                }
+                            });
+                            ui.separator();
+                        }
+
                         // Show messages
                         if let Some(error) = &self.error_message {
                             ui.label(RichText::new(format!("❌ {}", error)).color(CatppuccinMocha::RED));
                         }
 
+                        if let Some(copied) = &self.copied_message {
+                            ui.label(RichText::new(format!("📋 {}", copied)).color(CatppuccinMocha::GREEN));
+                        }
+
                         if let Some(progress) = &self.progress_message {
                             ui.label(RichText::new(format!("⏳ {}", progress)).color(CatppuccinMocha::YELLOW));
                         }
 
                         ui.separator();
 
+                        if let Some(published) = &self.published_message {
+                            ui.label(RichText::new(format!("✅ {}", published)).color(CatppuccinMocha::GREEN));
+                            if let Some(report) = &self.published_report {
+                                for (relay, result) in report.relay_results() {
+                                    match result {
+                                        Ok(()) => ui.label(RichText::new(format!("✅ {}", relay)).small().color(CatppuccinMocha::GREEN)),
+                                        Err(reason) => ui.label(RichText::new(format!("❌ {}: {}", relay, reason)).small().color(CatppuccinMocha::RED)),
+                                    };
+                                }
+                            }
+                            if ui.button("Close").clicked() {
+                                close_dialog = true;
+                            }
+                            return;
+                        }
+
+                        // Final confirmation
+                        if self.awaiting_confirmation && !self.is_publishing {
+                            let relays = relay_settings.publish_relays(&post.excluded_relays, &post.extra_relays).unwrap_or_default();
+                            let relay_count = relays.len();
+                            let npub = current_npub.clone().unwrap_or_else(|| "unknown identity".to_string());
+
+                            ui.group(|ui| {
+                                ui.label(RichText::new("⚠️ Are you sure?").strong().color(CatppuccinMocha::YELLOW));
+                                ui.label(format!(
+                                    "This will {} publicly on {} relay{} and cannot be fully unpublished.",
+                                    if updating { "replace the published article" } else { "publish" },
+                                    relay_count,
+                                    if relay_count == 1 { "" } else { "s" }
+                                ));
+                                for relay in &relays {
+                                    ui.label(RichText::new(format!("• {}", relay)).small().monospace());
+                                }
+                                ui.label(RichText::new(format!("Identity: {}", npub)).small().monospace());
+                            });
+
+                            ui.horizontal(|ui| {
+                                if ui.button(RichText::new("✅ Yes, publish").color(CatppuccinMocha::GREEN)).clicked() {
+                                    self.awaiting_confirmation = false;
+                                    should_start_publishing = true;
+                                }
+                                if ui.button("↩ Back").clicked() {
+                                    self.awaiting_confirmation = false;
+                                }
+                            });
+                            return;
+                        }
+
                         // Buttons
                         ui.horizontal(|ui| {
                             if !self.is_publishing {
-                                if ui.button(RichText::new("🚀 Publish").color(CatppuccinMocha::GREEN)).clicked() {
+                                let publish_label = if updating { "🔄 Update" } else { "🚀 Publish" };
+                                if ui.button(RichText::new(publish_label).color(CatppuccinMocha::GREEN)).clicked() {
                                     // Check if credentials are available
                                     let has_credentials = {
                                         let client = nostr_client.clone();
@@ -116,19 +471,35 @@ impl PublishDialog {
                                         result
                                     };
                                     
-                                    if has_credentials {
+                                    // Never publish to an empty or broken relay set
+                                    let relay_check = relay_settings.publish_relays(&post.excluded_relays, &post.extra_relays);
+
+                                    if let Err(e) = relay_check {
+                                        self.error_message = Some(e);
+                                    } else if has_credentials && self.confirm_before_publish {
+                                        self.error_message = None;
+                                        self.awaiting_confirmation = true;
+                                    } else if has_credentials {
                                         should_start_publishing = true;
                                     } else {
                                         self.error_message = Some("No Nostr credentials configured. Please set up your credentials first.".to_string());
                                     }
                                 }
 
+                                if ui
+                                    .add_enabled(!is_signing, egui::Button::new("📋 Sign & Copy JSON"))
+                                    .on_hover_text("Sign the article event and copy it to broadcast with another tool. Nothing is sent to relays.")
+                                    .clicked()
+                                {
+                                    should_sign_copy = true;
+                                }
+
                                 if ui.button("❌ Cancel").clicked() {
                                     close_dialog = true;
                                 }
                             } else {
                                 ui.spinner();
-                                ui.label("Publishing...");
+                                ui.label(if updating { "Updating..." } else { "Publishing..." });
                             }
                         });
                     });
@@ -137,66 +508,121 @@ impl PublishDialog {
 
         // Handle publishing outside the UI closure
         if should_start_publishing {
-            self.start_publishing(nostr_client, runtime, relay_settings);
+            self.start_publishing(nostr_client, runtime, relay_settings, activity);
+        }
+
+        if should_sign_copy {
+            self.start_signing_copy(nostr_client, runtime, activity);
         }
 
         if close_dialog {
             self.open = false;
             self.post = None;
             self.is_publishing = false;
+            self.publishing = None;
+            self.published_message = None;
+            self.published_report = None;
             self.error_message = None;
             self.progress_message = None;
+            self.awaiting_confirmation = false;
         }
 
         published_post
     }
 
+    fn start_signing_copy(
+        &mut self,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+    ) {
+        let Some(post) = self.post.clone() else {
+            return;
+        };
+
+        self.copied_message = None;
+        let (sender, receiver) = mpsc::channel();
+        self.signing = Some(receiver);
+
+        let client = nostr_client.clone();
+        // A remote signer can take a while to answer
+        let activity_guard = activity.begin(format!("Signing \"{}\"", post.title));
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = {
+                let client_guard = client.lock().await;
+                client_guard.sign_article_json(&post).await.map_err(|e| e.to_string())
+            };
+            let _ = sender.send(result);
+        });
+    }
+
     fn start_publishing(
         &mut self,
         nostr_client: &Arc<Mutex<NostrClient>>,
         runtime: &tokio::runtime::Runtime,
         relay_settings: &RelaySettings,
+        activity: &ActivityTracker,
     ) {
-        if let Some(post) = self.post.take() {
-            self.is_publishing = true;
-            self.progress_message = Some("Connecting to relays...".to_string());
-
-            let client = nostr_client.clone();
-            
-            // Clone the post and relay settings for the async operation
-            let post_clone = post.clone();
-            let relay_settings_clone = relay_settings.clone();
-            
-            // Spawn the publishing task
-            runtime.spawn(async move {
-                let result = {
-                    let client_guard = client.lock().await;
-                    
-                    // Connect to relays first
-                    if let Err(e) = client_guard.connect_to_relays(&relay_settings_clone).await {
-                        Err(format!("Failed to connect to relays: {}", e))
-                    } else {
-                        // Publish the post
-                        client_guard.publish_long_form_post(&post_clone, &relay_settings_clone).await
-                            .map_err(|e| e.to_string())
-                    }
-                };
-                
-                match result {
-                    Ok((event_id, relays)) => {
-                        // Update post status
-                        let mut _updated_post = post_clone;
-                        _updated_post.set_published(event_id.to_hex(), relays);
-                        
-                        // In a real implementation, you'd need to communicate this back to the UI
-                        // This is a simplified version
-                        tracing::info!("Successfully published post: {}", _updated_post.title);
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to publish post: {}", e);
+        let Some(post) = self.post.clone() else {
+            return;
+        };
+
+        self.is_publishing = true;
+        self.error_message = None;
+        self.copied_message = None;
+        self.progress_message = Some("Connecting to relays...".to_string());
+
+        let (sender, receiver) = mpsc::channel();
+        self.publishing = Some(receiver);
+
+        let client = nostr_client.clone();
+        let relay_settings = relay_settings.clone();
+        let activity_guard = activity.begin_critical(format!("Publishing \"{}\"", post.title));
+
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = {
+                let client_guard = client.lock().await;
+                // Connects to the post's relays itself
+                client_guard
+                    .publish_long_form_post(&post, &relay_settings)
+                    .await
+                    .map(|report| (report, client_guard.npub()))
+                    .map_err(|e| format!("{:#}", e))
+            };
+
+            match &result {
+                Ok((report, _)) => {
+                    if !report.timed_out_relays.is_empty() {
+                        tracing::warn!("Relays timed out, retry later: {:?}", report.timed_out_relays);
                     }
+                    tracing::info!("Published \"{}\" to {} relays", post.title, report.successful_relays.len());
                 }
-            });
-        }
+                Err(e) => tracing::error!("Failed to publish post: {}", e),
+            }
+
+            let _ = sender.send(result);
+        });
+    }
+}
+
+/// One-line outcome of a publish, e.g. "Published to 3 of 4 relays"
+fn published_summary(report: &PublishReport, updated: bool) -> String {
+    let accepted = report.successful_relays.len();
+    let attempted = accepted + report.failed_relays.len() + report.timed_out_relays.len();
+    let mut summary = format!(
+        "{} {} of {} relay{}",
+        if updated { "Updated on" } else { "Published to" },
+        accepted,
+        attempted,
+        if attempted == 1 { "" } else { "s" }
+    );
+    if !report.failed_relays.is_empty() {
+        summary.push_str(&format!(", {} rejected it", report.failed_relays.len()));
+    }
+    if !report.timed_out_relays.is_empty() {
+        summary.push_str(&format!(", {} didn't answer in time", report.timed_out_relays.len()));
     }
+    summary
 }
@@ -0,0 +1,121 @@
+use crate::qr::encode_qr;
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, TextureHandle, TextureOptions, Window};
+
+/// Size the QR codes are drawn at
+const QR_DISPLAY_SIZE: f32 = 240.0;
+
+/// Shows the identity's npub (and, behind a warning, its nsec) as QR codes
+/// for scanning into a mobile client
+#[derive(Default)]
+pub struct QrDialog {
+    open: bool,
+    npub: String,
+    nsec: Option<String>,
+    npub_texture: Option<TextureHandle>,
+    nsec_texture: Option<TextureHandle>,
+    nsec_acknowledged: bool,
+    error_message: Option<String>,
+}
+
+impl QrDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, npub: String, nsec: Option<String>) {
+        *self = Self {
+            open: true,
+            npub,
+            nsec,
+            ..Self::default()
+        };
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn texture(ctx: &Context, name: &str, data: &str) -> Result<TextureHandle, String> {
+        encode_qr(data)
+            .map(|image| ctx.load_texture(name, image, TextureOptions::NEAREST))
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn show(&mut self, ctx: &Context, theme_colors: &ThemeColors) {
+        if !self.open {
+            return;
+        }
+
+        if self.npub_texture.is_none() && self.error_message.is_none() {
+            match Self::texture(ctx, "npub_qr", &self.npub) {
+                Ok(texture) => self.npub_texture = Some(texture),
+                Err(e) => self.error_message = Some(e),
+            }
+        }
+
+        let mut window_open = self.open;
+
+        Window::new("📱 Identity QR Codes")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                // Public key: safe to share
+                ui.heading(RichText::new("Public key (npub)").color(theme_colors.success));
+                ui.label(RichText::new("Safe to share. Scan it to follow this identity.").small().color(theme_colors.text_muted));
+                if let Some(texture) = &self.npub_texture {
+                    ui.add(egui::Image::new(texture).fit_to_exact_size(egui::vec2(QR_DISPLAY_SIZE, QR_DISPLAY_SIZE)));
+                }
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.npub).monospace().small());
+                    if ui.small_button("📋").on_hover_text("Copy npub").clicked() {
+                        ui.output_mut(|o| o.copied_text = self.npub.clone());
+                    }
+                });
+
+                if let Some(error) = &self.error_message {
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                }
+
+                let Some(nsec) = &self.nsec else {
+                    return;
+                };
+
+                ui.add_space(12.0);
+                ui.separator();
+
+                // Private key: kept hidden until the user explicitly asks
+                ui.heading(RichText::new("⚠ Private key (nsec)").color(theme_colors.error));
+                ui.label(
+                    RichText::new("Anyone who scans this code can post as you and read your encrypted messages. Never share or screenshot it.")
+                        .color(theme_colors.warning),
+                );
+                ui.checkbox(&mut self.nsec_acknowledged, "I understand and want to move my key to another device");
+
+                if self.nsec_acknowledged {
+                    if self.nsec_texture.is_none() {
+                        match Self::texture(ui.ctx(), "nsec_qr", nsec) {
+                            Ok(texture) => self.nsec_texture = Some(texture),
+                            Err(e) => {
+                                ui.colored_label(theme_colors.error, format!("❌ {}", e));
+                            }
+                        }
+                    }
+                    if let Some(texture) = &self.nsec_texture {
+                        ui.add(egui::Image::new(texture).fit_to_exact_size(egui::vec2(QR_DISPLAY_SIZE, QR_DISPLAY_SIZE)));
+                    }
+                } else {
+                    // Drop the secret's texture as soon as it is hidden again
+                    self.nsec_texture = None;
+                }
+            });
+
+        self.open = window_open;
+        if !self.open {
+            // Don't keep the secret around after the window closes
+            self.nsec = None;
+            self.nsec_texture = None;
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::activity::ActivityTracker;
+use crate::nostr_client::{post_from_article_event, NostrClient};
+use crate::post::BlogPost;
+use crate::relay_settings::RelaySettings;
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, TextEdit, Window};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Rebuilds a lost local post from an article that is still published on relays
+pub struct RecoverDialog {
+    open: bool,
+    reference: String,
+    error_message: Option<String>,
+    pending: Option<mpsc::Receiver<Result<BlogPost, String>>>,
+}
+
+impl Default for RecoverDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            reference: String::new(),
+            error_message: None,
+            pending: None,
+        }
+    }
+}
+
+impl RecoverDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.reference.clear();
+        self.error_message = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns the recovered post once the fetch has completed
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+        theme_colors: &ThemeColors,
+    ) -> Option<BlogPost> {
+        let mut result = None;
+
+        // Poll an in-flight fetch
+        if let Some(receiver) = &self.pending {
+            match receiver.try_recv() {
+                Ok(Ok(post)) => {
+                    self.pending = None;
+                    self.open = false;
+                    result = Some(post);
+                }
+                Ok(Err(e)) => {
+                    self.pending = None;
+                    self.error_message = Some(e);
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.pending = None;
+                    self.error_message = Some("Recovery task stopped unexpectedly".to_string());
+                }
+            }
+        }
+
+        if !self.open {
+            return result;
+        }
+
+        let mut window_open = self.open;
+        let mut start_recovery = false;
+        let is_fetching = self.pending.is_some();
+
+        Window::new("🛟 Recover Published Post")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(RichText::new("Fetch an article published on your relays to view it or save a local copy.").color(theme_colors.text));
+                ui.label(RichText::new("Paste an naddr, nevent, note or hex event id.").small().color(theme_colors.text_muted));
+                ui.add_space(8.0);
+
+                let response = ui.add_enabled(
+                    !is_fetching,
+                    TextEdit::singleline(&mut self.reference)
+                        .hint_text("naddr1...")
+                        .desired_width(400.0),
+                );
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    start_recovery = true;
+                }
+
+                if let Some(error) = &self.error_message {
+                    ui.add_space(8.0);
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                }
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if is_fetching {
+                        ui.spinner();
+                        ui.label("Fetching from relays...");
+                    } else if ui.button(RichText::new("🔍 Recover").color(theme_colors.success)).clicked() {
+                        start_recovery = true;
+                    }
+                });
+            });
+
+        self.open = window_open;
+
+        if start_recovery && !is_fetching {
+            self.start_recovery(nostr_client, relay_settings, runtime, activity);
+        }
+
+        result
+    }
+
+    fn start_recovery(
+        &mut self,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+    ) {
+        let reference = self.reference.trim().to_string();
+        if reference.is_empty() {
+            self.error_message = Some("Please enter an naddr, nevent, note or event id".to_string());
+            return;
+        }
+
+        self.error_message = None;
+
+        let (sender, receiver) = mpsc::channel();
+        self.pending = Some(receiver);
+
+        let client = nostr_client.clone();
+        let relay_settings = relay_settings.clone();
+        let activity_guard = activity.begin("Recovering post from relays");
+
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = {
+                let client_guard = client.lock().await;
+                client_guard
+                    .fetch_event(&reference, &relay_settings)
+                    .await
+                    .and_then(|event| post_from_article_event(&event, relay_settings.get_active_relays()))
+                    .map_err(|e| e.to_string())
+            };
+
+            let _ = sender.send(result);
+        });
+    }
+}
@@ -1,7 +1,17 @@
+use crate::nostr_client::{relay_list_to_custom_relays, NostrClient, RelayConnection};
+use crate::relay_settings::CustomRelay;
 use crate::relay_settings::RelaySettings;
 use crate::storage::Storage;
 use crate::theme::ThemeColors;
 use egui::{Context, RichText, Window, ScrollArea, TextEdit};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How often relay connection states are refreshed while the dialog is open
+const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
 
 pub struct RelayDialog {
     open: bool,
@@ -10,6 +20,15 @@ pub struct RelayDialog {
     error_message: Option<String>,
     success_message: Option<String>,
     settings_changed: bool,
+    /// Last known connection state of each active relay
+    relay_status: HashMap<String, RelayConnection>,
+    status_check: Option<mpsc::Receiver<Vec<(String, RelayConnection)>>>,
+    last_status_check: Option<Instant>,
+    /// Handshake times (or errors) from "Test connection", per relay
+    connection_tests: HashMap<String, Result<Duration, String>>,
+    running_tests: Vec<(String, mpsc::Receiver<Result<Duration, String>>)>,
+    /// NIP-65 relay list being fetched
+    relay_list_import: Option<mpsc::Receiver<Result<Vec<CustomRelay>, String>>>,
 }
 
 impl Default for RelayDialog {
@@ -21,6 +40,12 @@ impl Default for RelayDialog {
             error_message: None,
             success_message: None,
             settings_changed: false,
+            relay_status: HashMap::new(),
+            status_check: None,
+            last_status_check: None,
+            connection_tests: HashMap::new(),
+            running_tests: Vec::new(),
+            relay_list_import: None,
         }
     }
 }
@@ -37,22 +62,195 @@ impl RelayDialog {
         self.error_message = None;
         self.success_message = None;
         self.settings_changed = false;
+        self.last_status_check = None;
+        self.connection_tests.clear();
     }
 
     pub fn is_open(&self) -> bool {
         self.open
     }
 
+    /// Ask the relay pool for fresh connection states every few seconds
+    fn refresh_status(&mut self, ctx: &Context, nostr_client: &Arc<Mutex<NostrClient>>, runtime: &tokio::runtime::Runtime) {
+        if let Some(receiver) = &self.status_check {
+            match receiver.try_recv() {
+                Ok(status) => {
+                    self.status_check = None;
+                    self.relay_status = status.into_iter().collect();
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.status_check = None,
+            }
+        }
+
+        let due = self.last_status_check.map_or(true, |checked| checked.elapsed() >= STATUS_REFRESH_INTERVAL);
+        if self.status_check.is_none() && due {
+            self.last_status_check = Some(Instant::now());
+            let (sender, receiver) = mpsc::channel();
+            self.status_check = Some(receiver);
+
+            let client = nostr_client.clone();
+            let relay_settings = self.relay_settings.clone();
+            runtime.spawn(async move {
+                let status = client.lock().await.get_relay_status(&relay_settings).await;
+                let _ = sender.send(status);
+            });
+        }
+
+        ctx.request_repaint_after(Duration::from_millis(500));
+    }
+
+    fn poll_connection_tests(&mut self) {
+        let mut finished = Vec::new();
+        for (index, (relay_url, receiver)) in self.running_tests.iter().enumerate() {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    self.connection_tests.insert(relay_url.clone(), result);
+                    finished.push(index);
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.connection_tests.insert(relay_url.clone(), Err("Test stopped unexpectedly".to_string()));
+                    finished.push(index);
+                }
+            }
+        }
+        for index in finished.into_iter().rev() {
+            self.running_tests.remove(index);
+        }
+    }
+
+    fn start_connection_test(&mut self, relay_url: String, runtime: &tokio::runtime::Runtime) {
+        if self.running_tests.iter().any(|(url, _)| url == &relay_url) {
+            return;
+        }
+        self.connection_tests.remove(&relay_url);
+
+        let (sender, receiver) = mpsc::channel();
+        self.running_tests.push((relay_url.clone(), receiver));
+
+        runtime.spawn(async move {
+            let result = NostrClient::test_relay(&relay_url)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+    }
+
+    /// Fetch the relay list published with the user's profile
+    fn start_relay_list_import(&mut self, nostr_client: &Arc<Mutex<NostrClient>>, runtime: &tokio::runtime::Runtime) {
+        self.error_message = None;
+        self.success_message = None;
+
+        let (sender, receiver) = mpsc::channel();
+        self.relay_list_import = Some(receiver);
+
+        let client = nostr_client.clone();
+        runtime.spawn(async move {
+            let result = client
+                .lock()
+                .await
+                .fetch_relay_list()
+                .await
+                .map(relay_list_to_custom_relays)
+                .map_err(|e| format!("{:#}", e));
+            let _ = sender.send(result);
+        });
+    }
+
+    fn poll_relay_list_import(&mut self, ctx: &Context) {
+        let Some(receiver) = &self.relay_list_import else {
+            return;
+        };
+
+        match receiver.try_recv() {
+            Ok(Ok(relays)) => {
+                self.relay_list_import = None;
+                let found = relays.len();
+                let read_only = relays.iter().filter(|relay| !relay.write).count();
+                let added = self.relay_settings.import_relays(relays);
+                if added > 0 {
+                    self.settings_changed = true;
+                }
+                let mut message = format!("Imported {} of {} relays from your profile", added, found);
+                if read_only > 0 {
+                    message.push_str(&format!("; {} are read-only and won't be published to", read_only));
+                }
+                self.success_message = Some(message);
+            }
+            Ok(Err(e)) => {
+                self.relay_list_import = None;
+                self.error_message = Some(e);
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.relay_list_import = None;
+                self.error_message = Some("Relay list import stopped unexpectedly".to_string());
+            }
+        }
+    }
+
+    /// Status icon and hover text for a relay in the list
+    fn status_indicator(&self, relay: &str) -> (&'static str, String) {
+        let (icon, state) = match self.relay_status.get(relay) {
+            Some(RelayConnection::Connected) => ("🟢", "Connected"),
+            Some(RelayConnection::Connecting) => ("🟡", "Connecting"),
+            Some(RelayConnection::Disconnected) => ("🔴", "Disconnected"),
+            Some(RelayConnection::NotAdded) => ("⚪", "Not connected yet; Blogster connects when it needs this relay"),
+            None => ("⚪", "Not in use"),
+        };
+        (icon, state.to_string())
+    }
+
+    /// "Test connection" button and its latest result
+    fn show_connection_test(&self, ui: &mut egui::Ui, relay: &str, theme_colors: &ThemeColors) -> bool {
+        let running = self.running_tests.iter().any(|(url, _)| url == relay);
+        let clicked = ui
+            .add_enabled(!running, egui::Button::new("🔌").small())
+            .on_hover_text("Test connection")
+            .clicked();
+        if running {
+            ui.spinner();
+        } else {
+            match self.connection_tests.get(relay) {
+                Some(Ok(latency)) => {
+                    ui.label(RichText::new(format!("{} ms", latency.as_millis())).small().color(theme_colors.success));
+                }
+                Some(Err(e)) => {
+                    ui.label(RichText::new(format!("❌ {}", e)).small().color(theme_colors.error));
+                }
+                None => {}
+            }
+        }
+        clicked
+    }
+
     /// Returns the new relay settings if they were changed
-    pub fn show(&mut self, ctx: &Context, storage: &Storage, theme_colors: &ThemeColors) -> Option<RelaySettings> {
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        storage: &Storage,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        runtime: &tokio::runtime::Runtime,
+        theme_colors: &ThemeColors,
+    ) -> Option<RelaySettings> {
         let mut result = None;
         let mut should_close = false;
         let mut add_relay_clicked = false;
+        let mut test_relay = None;
+        let mut import_clicked = false;
         
         if !self.open {
             return result;
         }
 
+        self.refresh_status(ctx, nostr_client, runtime);
+        self.poll_connection_tests();
+        self.poll_relay_list_import(ctx);
+        let importing = self.relay_list_import.is_some();
+
         let mut window_open = self.open;
         
         Window::new("🌐 Relay Settings")
@@ -97,9 +295,34 @@ impl RelayDialog {
                         ui.add_space(4.0);
                         
                         for relay in RelaySettings::get_default_relays() {
+                            let is_blocked = self.relay_settings.is_blocked(&relay);
                             ui.horizontal(|ui| {
-                                ui.label("🟢");
-                                ui.label(RichText::new(&relay).color(theme_colors.text_secondary));
+                                if is_blocked {
+                                    ui.label("⛔");
+                                    ui.label(RichText::new(&relay).strikethrough().color(theme_colors.text_muted));
+                                } else {
+                                    let (icon, state) = self.status_indicator(&relay);
+                                    ui.label(icon).on_hover_text(state);
+                                    ui.label(RichText::new(&relay).color(theme_colors.text_secondary));
+                                    if self.show_connection_test(ui, &relay, theme_colors) {
+                                        test_relay = Some(relay.clone());
+                                    }
+                                }
+                                
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if is_blocked {
+                                        if ui.small_button("Unblock").clicked() {
+                                            self.relay_settings.unblock_relay(&relay);
+                                            self.settings_changed = true;
+                                        }
+                                    } else if ui.small_button(RichText::new("🚫 Block").color(theme_colors.warning))
+                                        .on_hover_text("Never publish to this relay")
+                                        .clicked()
+                                    {
+                                        self.relay_settings.block_relay(&relay);
+                                        self.settings_changed = true;
+                                    }
+                                });
                             });
                         }
                         
@@ -107,7 +330,17 @@ impl RelayDialog {
                     }
                     
                     // Custom relays section
-                    ui.label(RichText::new("Custom Relays:").strong().color(theme_colors.text));
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Custom Relays:").strong().color(theme_colors.text));
+                        if importing {
+                            ui.spinner();
+                        } else if ui.small_button("📥 Import from my Nostr profile")
+                            .on_hover_text("Add the relays from your published NIP-65 relay list, with their read/write markers")
+                            .clicked()
+                        {
+                            import_clicked = true;
+                        }
+                    });
                     ui.add_space(4.0);
                     
                     // Add new relay
@@ -115,7 +348,7 @@ impl RelayDialog {
                         ui.label("Add relay:");
                         let response = ui.add(
                             TextEdit::singleline(&mut self.new_relay_url)
-                                .hint_text("wss://relay.example.com")
+                                .hint_text("relay.example.com")
                                 .desired_width(300.0)
                         );
                         
@@ -123,8 +356,28 @@ impl RelayDialog {
                            (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))) {
                             add_relay_clicked = true;
                         }
+
+                        // Try the relay before adding it
+                        let new_relay = RelaySettings::normalize_relay_url(self.new_relay_url.trim());
+                        if !self.new_relay_url.trim().is_empty() && self.show_connection_test(ui, &new_relay, theme_colors) {
+                            test_relay = Some(new_relay);
+                        }
                     });
                     
+                    // Suggestions from popular and previously used relays
+                    let suggestions = self.relay_settings.suggestions(&self.new_relay_url);
+                    if !suggestions.is_empty() {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(RichText::new("Suggestions:").small().color(theme_colors.text_muted));
+                            for suggestion in suggestions {
+                                if ui.small_button(&suggestion).clicked() {
+                                    self.new_relay_url = suggestion;
+                                    add_relay_clicked = true;
+                                }
+                            }
+                        });
+                    }
+                    
                     ui.add_space(8.0);
                     
                     // List existing custom relays
@@ -133,25 +386,59 @@ impl RelayDialog {
                             .max_height(200.0)
                             .show(ui, |ui| {
                                 let mut to_remove = None;
+                                let mut to_block = None;
+                                let mut new_flags = None;
                                 
                                 for (index, relay) in self.relay_settings.custom_relays.iter().enumerate() {
                                     ui.horizontal(|ui| {
-                                        let status_color = if self.relay_settings.use_custom_relays {
-                                            "🟢"
-                                        } else {
-                                            "🔴"
-                                        };
-                                        ui.label(status_color);
-                                        ui.label(RichText::new(relay).color(theme_colors.text_secondary));
+                                        let (icon, state) = self.status_indicator(&relay.url);
+                                        ui.label(icon).on_hover_text(state);
+                                        ui.label(RichText::new(&relay.url).color(theme_colors.text_secondary));
+                                        if self.show_connection_test(ui, &relay.url, theme_colors) {
+                                            test_relay = Some(relay.url.clone());
+                                        }
                                         
                                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                             if ui.button(RichText::new("🗑").color(theme_colors.error)).clicked() {
                                                 to_remove = Some(index);
                                             }
+                                            if !self.relay_settings.is_blocked(&relay.url)
+                                                && ui.small_button(RichText::new("🚫").color(theme_colors.warning))
+                                                    .on_hover_text("Block this relay")
+                                                    .clicked()
+                                            {
+                                                to_block = Some(relay.url.clone());
+                                            }
+                                            
+                                            let mut read = relay.read;
+                                            let mut write = relay.write;
+                                            let write_changed = ui.checkbox(&mut write, "Write")
+                                                .on_hover_text("Publish posts, profiles and deletions here")
+                                                .changed();
+                                            let read_changed = ui.checkbox(&mut read, "Read")
+                                                .on_hover_text("Look up events here")
+                                                .changed();
+                                            if read_changed || write_changed {
+                                                new_flags = Some((index, read, write));
+                                            }
                                         });
                                     });
                                 }
                                 
+                                if let Some((index, read, write)) = new_flags {
+                                    if let Some(relay) = self.relay_settings.custom_relays.get_mut(index) {
+                                        relay.read = read;
+                                        relay.write = write;
+                                        self.settings_changed = true;
+                                    }
+                                }
+                                
+                                if let Some(relay) = to_block {
+                                    self.relay_settings.block_relay(&relay);
+                                    self.settings_changed = true;
+                                    self.success_message = Some("Relay blocked".to_string());
+                                }
+                                
                                 // Remove relay if requested
                                 if let Some(index) = to_remove {
                                     self.relay_settings.custom_relays.remove(index);
@@ -163,6 +450,32 @@ impl RelayDialog {
                         ui.label(RichText::new("No custom relays configured").color(theme_colors.text_muted).italics());
                     }
                     
+                    // Blocked relays section
+                    if !self.relay_settings.blocked_relays.is_empty() {
+                        ui.add_space(12.0);
+                        ui.label(RichText::new("Blocked Relays:").strong().color(theme_colors.text));
+                        ui.add_space(4.0);
+                        
+                        let mut to_unblock = None;
+                        for relay in &self.relay_settings.blocked_relays {
+                            ui.horizontal(|ui| {
+                                ui.label("⛔");
+                                ui.label(RichText::new(relay).color(theme_colors.text_muted));
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if ui.small_button("Unblock").clicked() {
+                                        to_unblock = Some(relay.clone());
+                                    }
+                                });
+                            });
+                        }
+                        
+                        if let Some(relay) = to_unblock {
+                            self.relay_settings.unblock_relay(&relay);
+                            self.settings_changed = true;
+                            self.success_message = Some("Relay unblocked".to_string());
+                        }
+                    }
+                    
                     ui.add_space(16.0);
                     
                     // Show error/success messages
@@ -181,7 +494,8 @@ impl RelayDialog {
                     ui.add_space(8.0);
                     
                     let active_relays = self.relay_settings.get_active_relays();
-                    ui.label(RichText::new(format!("Active relays: {}", active_relays.len())).strong().color(theme_colors.info));
+                    let write_relays = self.relay_settings.get_write_relays();
+                    ui.label(RichText::new(format!("Active relays: {} ({} for publishing)", active_relays.len(), write_relays.len())).strong().color(theme_colors.info));
                     
                     ui.add_space(16.0);
                     
@@ -225,6 +539,14 @@ impl RelayDialog {
             self.add_relay();
         }
 
+        if let Some(relay_url) = test_relay {
+            self.start_connection_test(relay_url, runtime);
+        }
+
+        if import_clicked {
+            self.start_relay_list_import(nostr_client, runtime);
+        }
+
         // Clear messages after a delay
         if self.error_message.is_some() || self.success_message.is_some() {
             ctx.request_repaint_after(std::time::Duration::from_secs(3));
@@ -241,6 +563,13 @@ impl RelayDialog {
             return;
         }
         
+        let url = RelaySettings::normalize_relay_url(&url);
+        if let Err(e) = RelaySettings::validate_relay_url(&url) {
+            self.error_message = Some(e);
+            self.success_message = None;
+            return;
+        }
+        
         match self.relay_settings.add_relay(url) {
             Ok(()) => {
                 self.new_relay_url.clear();
@@ -0,0 +1,217 @@
+use crate::activity::ActivityTracker;
+use crate::nostr_client::{NostrClient, RelayTestReport};
+use crate::post::BlogPost;
+use crate::relay_settings::RelaySettings;
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, TextEdit, Window};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Developer tool: publish the current post to one relay and show its exact
+/// answer, to find out why a particular relay rejects an article
+#[derive(Default)]
+pub struct RelayTestDialog {
+    open: bool,
+    post: Option<BlogPost>,
+    relay_url: String,
+    report: Option<RelayTestReport>,
+    error_message: Option<String>,
+    pending: Option<mpsc::Receiver<Result<RelayTestReport, String>>>,
+}
+
+impl RelayTestDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, post: BlogPost) {
+        self.open = true;
+        self.post = Some(post);
+        self.report = None;
+        self.error_message = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        relay_settings: &RelaySettings,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+        theme_colors: &ThemeColors,
+    ) {
+        // Poll an in-flight test
+        if let Some(receiver) = &self.pending {
+            match receiver.try_recv() {
+                Ok(Ok(report)) => {
+                    self.pending = None;
+                    self.report = Some(report);
+                }
+                Ok(Err(e)) => {
+                    self.pending = None;
+                    self.error_message = Some(e);
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.pending = None;
+                    self.error_message = Some("Test task stopped unexpectedly".to_string());
+                }
+            }
+        }
+
+        if !self.open {
+            return;
+        }
+
+        let Some(post_title) = self.post.as_ref().map(|p| p.title.clone()) else {
+            self.open = false;
+            return;
+        };
+
+        let mut window_open = self.open;
+        let mut start_test = false;
+        let is_testing = self.pending.is_some();
+
+        Window::new("🧪 Test Publish to One Relay")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.label(RichText::new(&post_title).strong().color(theme_colors.primary));
+                ui.label(
+                    RichText::new("Sends the same event a normal publish would, to this relay only. If the relay accepts it, the article is really published there. Test publishes aren't tracked: the post isn't marked as published and deleting it from relays won't reach this one.")
+                        .small()
+                        .color(theme_colors.warning),
+                );
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Relay:");
+                    ui.add_enabled(
+                        !is_testing,
+                        TextEdit::singleline(&mut self.relay_url)
+                            .hint_text("wss://relay.example.com")
+                            .desired_width(260.0),
+                    );
+                    ui.add_enabled_ui(!is_testing, |ui| {
+                        egui::ComboBox::from_id_source("relay_test_active_relays")
+                            .selected_text("Active relays")
+                            .show_ui(ui, |ui| {
+                                for relay in relay_settings.get_active_relays() {
+                                    if ui.selectable_label(self.relay_url == relay, &relay).clicked() {
+                                        self.relay_url = relay;
+                                    }
+                                }
+                            });
+                    });
+                });
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if is_testing {
+                        ui.spinner();
+                        ui.label("Waiting for the relay...");
+                    } else if ui.button("🧪 Send test").clicked() {
+                        start_test = true;
+                    }
+                });
+
+                if let Some(error) = &self.error_message {
+                    ui.add_space(8.0);
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                }
+
+                if let Some(report) = &self.report {
+                    ui.separator();
+                    if report.accepted {
+                        ui.colored_label(theme_colors.success, format!("✅ {} accepted the event", report.relay_url));
+                        ui.label(
+                            RichText::new("This copy isn't recorded on the post. Note the event id below if you need to delete it later.")
+                                .small()
+                                .color(theme_colors.warning),
+                        );
+                    } else {
+                        ui.colored_label(theme_colors.error, format!("❌ {} did not accept the event", report.relay_url));
+                    }
+
+                    egui::Grid::new("relay_test_report").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                        ui.label(RichText::new("Response").strong());
+                        ui.add(egui::Label::new(RichText::new(&report.response).monospace()).wrap());
+                        ui.end_row();
+
+                        ui.label(RichText::new("Time").strong());
+                        ui.label(format!("{} ms", report.elapsed.as_millis()));
+                        ui.end_row();
+
+                        ui.label(RichText::new("Event size").strong());
+                        ui.label(format!("{} bytes", report.event_size));
+                        ui.end_row();
+
+                        ui.label(RichText::new("Event id").strong());
+                        ui.add(egui::Label::new(RichText::new(&report.event_id).monospace().small()).wrap());
+                        ui.end_row();
+                    });
+
+                    ui.label(
+                        RichText::new("Relays prefix rejections with a reason such as \"blocked:\", \"pow:\", \"auth-required:\" or \"invalid:\".")
+                            .small()
+                            .color(theme_colors.text_muted),
+                    );
+                }
+            });
+
+        self.open = window_open;
+
+        if start_test && !is_testing {
+            self.start_test(nostr_client, runtime, activity);
+        }
+    }
+
+    fn start_test(
+        &mut self,
+        nostr_client: &Arc<Mutex<NostrClient>>,
+        runtime: &tokio::runtime::Runtime,
+        activity: &ActivityTracker,
+    ) {
+        let Some(post) = self.post.clone() else {
+            return;
+        };
+
+        let relay_url = RelaySettings::normalize_relay_url(&self.relay_url);
+        if let Err(e) = RelaySettings::validate_relay_url(&relay_url) {
+            self.error_message = Some(e);
+            return;
+        }
+        self.relay_url = relay_url.clone();
+
+        self.error_message = None;
+        self.report = None;
+
+        let (sender, receiver) = mpsc::channel();
+        self.pending = Some(receiver);
+
+        let client = nostr_client.clone();
+        let activity_guard = activity.begin(format!("Test publishing to {}", relay_url));
+
+        runtime.spawn(async move {
+            let _activity_guard = activity_guard;
+            let result = {
+                let client_guard = client.lock().await;
+                client_guard
+                    .test_publish_to_relay(&post, &relay_url)
+                    .await
+                    .map_err(|e| format!("{:#}", e))
+            };
+
+            let _ = sender.send(result);
+        });
+    }
+}
@@ -0,0 +1,135 @@
+use crate::post::BlogPost;
+use crate::search::{SearchIndex, SearchResult};
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, ScrollArea, TextEdit, Window};
+
+/// Full-text search across every post (Ctrl+Shift+F)
+pub struct SearchDialog {
+    open: bool,
+    query: String,
+    index: SearchIndex,
+    results: Vec<SearchResult>,
+    last_query: String,
+    focus_query: bool,
+}
+
+impl Default for SearchDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            query: String::new(),
+            index: SearchIndex::new(),
+            results: Vec::new(),
+            last_query: String::new(),
+            focus_query: false,
+        }
+    }
+}
+
+impl SearchDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.focus_query = true;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns the post to open and the character offset of the clicked match
+    pub fn show(&mut self, ctx: &Context, posts: &[BlogPost], theme_colors: &ThemeColors) -> Option<(uuid::Uuid, Option<usize>)> {
+        let mut result = None;
+
+        if !self.open {
+            return result;
+        }
+
+        // Refresh the index and results whenever posts or the query change
+        if self.index.update(posts) || self.query != self.last_query {
+            self.results = self.index.search(&self.query);
+            self.last_query = self.query.clone();
+        }
+
+        let mut window_open = self.open;
+
+        Window::new("🔎 Search All Posts")
+            .open(&mut window_open)
+            .resizable(true)
+            .default_width(520.0)
+            .default_height(420.0)
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.query)
+                        .hint_text("Search titles and content...")
+                        .desired_width(f32::INFINITY),
+                );
+                if self.focus_query {
+                    response.request_focus();
+                    self.focus_query = false;
+                }
+
+                ui.add_space(4.0);
+
+                if self.last_query.trim().is_empty() {
+                    ui.label(RichText::new("Type to search across all posts").color(theme_colors.text_muted));
+                    return;
+                }
+
+                let total: usize = self.results.iter().map(|r| r.match_count).sum();
+                ui.label(
+                    RichText::new(format!("{} matches in {} posts", total, self.results.len()))
+                        .small()
+                        .color(theme_colors.text_secondary),
+                );
+                ui.separator();
+
+                ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    for search_result in &self.results {
+                        let title = if search_result.title.is_empty() { "Untitled" } else { &search_result.title };
+
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add(egui::Label::new(RichText::new(title).strong().color(theme_colors.primary)).sense(egui::Sense::click()))
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .clicked()
+                            {
+                                result = Some((search_result.post_id, search_result.snippets.first().map(|s| s.char_offset)));
+                            }
+                            ui.label(
+                                RichText::new(format!("{} matches", search_result.match_count))
+                                    .small()
+                                    .color(theme_colors.text_muted),
+                            );
+                        });
+
+                        for snippet in &search_result.snippets {
+                            if ui
+                                .add(
+                                    egui::Label::new(
+                                        RichText::new(format!("  {}: {}", snippet.line_number, snippet.text))
+                                            .small()
+                                            .color(theme_colors.text_secondary),
+                                    )
+                                    .sense(egui::Sense::click()),
+                                )
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .clicked()
+                            {
+                                result = Some((search_result.post_id, Some(snippet.char_offset)));
+                            }
+                        }
+
+                        ui.add_space(6.0);
+                    }
+                });
+            });
+
+        self.open = window_open;
+
+        result
+    }
+}
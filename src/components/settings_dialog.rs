@@ -10,6 +10,9 @@ pub struct SettingsDialog {
     custom_colors: CustomThemeColors,
     custom_colors_changed: bool,
     show_custom_colors: bool,
+    /// Outcome of the last theme file export or import
+    error_message: Option<String>,
+    success_message: Option<String>,
 }
 
 impl Default for SettingsDialog {
@@ -22,6 +25,8 @@ impl Default for SettingsDialog {
             custom_colors: CustomThemeColors::default(),
             custom_colors_changed: false,
             show_custom_colors: false,
+            error_message: None,
+            success_message: None,
         }
     }
 }
@@ -39,6 +44,57 @@ impl SettingsDialog {
         self.custom_colors = custom_colors.clone();
         self.custom_colors_changed = false;
         self.show_custom_colors = current_theme == Theme::Custom;
+        self.error_message = None;
+        self.success_message = None;
+    }
+
+    /// Save the custom colors to a theme file the user picks
+    fn export_theme(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Blogster theme", &["json"])
+            .set_file_name("blogster-theme.json")
+            .save_file()
+        else {
+            return;
+        };
+
+        match Storage::export_custom_theme(&self.custom_colors, &path) {
+            Ok(()) => {
+                self.error_message = None;
+                self.success_message = Some(format!("Theme exported to {}", path.display()));
+            }
+            Err(e) => {
+                self.success_message = None;
+                self.error_message = Some(format!("Failed to export theme: {:#}", e));
+            }
+        }
+    }
+
+    /// Load custom colors from a theme file the user picks; they take effect on Apply
+    fn import_theme(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Blogster theme", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        match Storage::import_custom_theme(&path) {
+            Ok((colors, fallbacks)) => {
+                self.custom_colors = colors;
+                self.custom_colors_changed = true;
+                self.error_message = None;
+                self.success_message = Some(if fallbacks.is_empty() {
+                    "Theme imported. Apply to use it.".to_string()
+                } else {
+                    format!("Theme imported, using default colors for: {}. Apply to use it.", fallbacks.join(", "))
+                });
+            }
+            Err(e) => {
+                self.success_message = None;
+                self.error_message = Some(format!("Failed to import theme: {:#}", e));
+            }
+        }
     }
 
     pub fn is_open(&self) -> bool {
@@ -49,6 +105,8 @@ impl SettingsDialog {
     pub fn show(&mut self, ctx: &Context, storage: &Storage, theme_colors: &ThemeColors, current_theme: &Theme, current_custom_colors: &CustomThemeColors) -> Option<(Theme, Option<CustomThemeColors>)> {
         let mut result = None;
         let mut should_close = false;
+        let mut export_clicked = false;
+        let mut import_clicked = false;
         
         if !self.open {
             return result;
@@ -224,12 +282,26 @@ impl SettingsDialog {
                             
                             if ui.button("📋 Load from Current Theme").clicked() {
                                 if self.current_theme != Theme::Custom {
-                                    let theme_colors = self.current_theme.colors(None);
-                                    self.custom_colors = CustomThemeColors::from_theme_colors(&theme_colors);
+                                    // The app's colors are the current theme's, with System already resolved
+                                    self.custom_colors = CustomThemeColors::from_theme_colors(theme_colors);
                                     self.custom_colors_changed = true;
                                 }
                             }
                         });
+                        ui.horizontal(|ui| {
+                            if ui.button("📤 Export theme…").on_hover_text("Save these colors to a file you can share").clicked() {
+                                export_clicked = true;
+                            }
+                            if ui.button("📥 Import theme…").on_hover_text("Load colors from a shared theme file").clicked() {
+                                import_clicked = true;
+                            }
+                        });
+                        if let Some(error) = &self.error_message {
+                            ui.colored_label(theme_colors.error, error);
+                        }
+                        if let Some(success) = &self.success_message {
+                            ui.colored_label(theme_colors.success, success);
+                        }
                     }
                     
                     ui.add_space(16.0);
@@ -286,6 +358,13 @@ impl SettingsDialog {
             self.open = false;
         }
 
+        if export_clicked {
+            self.export_theme();
+        }
+        if import_clicked {
+            self.import_theme();
+        }
+
         result
     }
 }
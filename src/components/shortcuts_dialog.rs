@@ -0,0 +1,85 @@
+use crate::theme::ThemeColors;
+use egui::{Context, Key, KeyboardShortcut, Modifiers, RichText, Window};
+
+const fn command(key: Key) -> KeyboardShortcut {
+    KeyboardShortcut::new(Modifiers::COMMAND, key)
+}
+
+const fn command_shift(key: Key) -> KeyboardShortcut {
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), key)
+}
+
+/// Every keyboard shortcut, grouped by where it works
+pub const SHORTCUTS: &[(&str, &[(KeyboardShortcut, &str)])] = &[
+    (
+        "Anywhere",
+        &[
+            (command(Key::N), "New post"),
+            (command(Key::S), "Save the open post"),
+            (command(Key::P), "Publish the open post"),
+            (command(Key::E), "Switch between editing and preview"),
+            (command_shift(Key::F), "Search all posts"),
+            (command_shift(Key::P), "Run any command"),
+            (KeyboardShortcut::new(Modifiers::CTRL, Key::Tab), "Go back to the previous post"),
+            (KeyboardShortcut::new(Modifiers::NONE, Key::F1), "Show these shortcuts"),
+        ],
+    ),
+    (
+        "Editor",
+        &[
+            (command(Key::F), "Find and replace"),
+            (command(Key::B), "Bold"),
+            (command(Key::I), "Italic"),
+            (command(Key::K), "Link"),
+            (command_shift(Key::D), "Insert today's date"),
+        ],
+    ),
+];
+
+/// Lists the keyboard shortcuts
+#[derive(Default)]
+pub struct ShortcutsDialog {
+    open: bool,
+}
+
+impl ShortcutsDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn show(&mut self, ctx: &Context, theme_colors: &ThemeColors) {
+        if !self.open {
+            return;
+        }
+
+        let mut window_open = self.open;
+
+        Window::new("⌨ Keyboard Shortcuts")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                for (section, shortcuts) in SHORTCUTS {
+                    ui.label(RichText::new(*section).strong().color(theme_colors.primary));
+                    egui::Grid::new(*section).num_columns(2).spacing([24.0, 4.0]).show(ui, |ui| {
+                        for (shortcut, description) in shortcuts.iter() {
+                            ui.label(RichText::new(ctx.format_shortcut(shortcut)).monospace().color(theme_colors.text));
+                            ui.label(RichText::new(*description).color(theme_colors.text_secondary));
+                            ui.end_row();
+                        }
+                    });
+                    ui.add_space(8.0);
+                }
+            });
+
+        self.open = window_open;
+    }
+}
@@ -1,17 +1,33 @@
-use crate::post::{BlogPost, PostStatus};
-use crate::theme::ThemeColors;
+use crate::post::{BlogPost, PostSort, PostSortKey, PostStatus, WordCounts};
+use crate::search::TokenIndex;
+use crate::theme::{tag_color, ThemeColors};
 use egui::{Color32, RichText, Ui, Vec2};
+use std::collections::HashMap;
 
 pub struct Sidebar {
     search_query: String,
+    search_index: TokenIndex,
+    sort: PostSort,
+    word_counts: WordCounts,
+    /// Tags picked in the tag list; posts must have all of them
+    tag_filters: Vec<String>,
     selected_post_id: Option<uuid::Uuid>,
+    tag_colors: HashMap<String, [u8; 3]>,
+    /// Also ask relays to delete a published post when deleting it
+    delete_from_relays: bool,
 }
 
 impl Default for Sidebar {
     fn default() -> Self {
         Self {
             search_query: String::new(),
+            search_index: TokenIndex::new(),
+            sort: PostSort::default(),
+            word_counts: WordCounts::default(),
+            tag_filters: Vec::new(),
             selected_post_id: None,
+            tag_colors: HashMap::new(),
+            delete_from_relays: false,
         }
     }
 }
@@ -29,6 +45,14 @@ impl Sidebar {
         self.selected_post_id = id;
     }
 
+    pub fn set_sort(&mut self, sort: PostSort) {
+        self.sort = sort;
+    }
+
+    pub fn set_tag_colors(&mut self, tag_colors: HashMap<String, [u8; 3]>) {
+        self.tag_colors = tag_colors;
+    }
+
     pub fn show(&mut self, ui: &mut Ui, posts: &[BlogPost], theme_colors: &ThemeColors) -> SidebarAction {
         let mut action = SidebarAction::None;
 
@@ -37,7 +61,7 @@ impl Sidebar {
             ui.horizontal(|ui| {
                 ui.heading(RichText::new("📝 Blogster").color(theme_colors.primary));
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.button(RichText::new("➕").size(16.0)).clicked() {
+                    if ui.button(RichText::new("➕").size(16.0)).on_hover_text("New post (Ctrl+N)").clicked() {
                         action = SidebarAction::NewPost;
                     }
                 });
@@ -48,32 +72,100 @@ impl Sidebar {
             // Search bar
             ui.horizontal(|ui| {
                 ui.label(RichText::new("🔍").color(theme_colors.text));
-                ui.text_edit_singleline(&mut self.search_query);
+                ui.add(egui::TextEdit::singleline(&mut self.search_query).hint_text("Words or tag:name"))
+                    .on_hover_text("Posts containing every word (or a word starting with it). Use tag:rust to filter by tag.");
+            });
+
+            // Sort order, applied within each group
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Sort:").color(theme_colors.text));
+                let previous_sort = self.sort;
+                egui::ComboBox::from_id_source("post_sort")
+                    .selected_text(self.sort.key.label())
+                    .show_ui(ui, |ui| {
+                        for key in PostSortKey::ALL {
+                            ui.selectable_value(&mut self.sort.key, key, key.label());
+                        }
+                    });
+                let (direction, hint) = if self.sort.descending { ("⬇", "Descending") } else { ("⬆", "Ascending") };
+                if ui.small_button(direction).on_hover_text(hint).clicked() {
+                    self.sort.descending = !self.sort.descending;
+                }
+                if self.sort != previous_sort {
+                    action = SidebarAction::SortChanged(self.sort);
+                }
             });
 
+            // Tag list; clicking a tag adds it to the filters
+            self.search_index.update(posts);
+            let tag_counts = self.search_index.tag_counts();
+            if !tag_counts.is_empty() {
+                egui::CollapsingHeader::new(RichText::new(format!("🏷 Tags ({})", tag_counts.len())).color(theme_colors.text))
+                    .id_source("sidebar_tags")
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical().id_source("sidebar_tag_list").max_height(120.0).show(ui, |ui| {
+                            ui.horizontal_wrapped(|ui| {
+                                for (tag, count) in &tag_counts {
+                                    let active = self.tag_filters.contains(tag);
+                                    let label = RichText::new(format!("#{} {}", tag, count)).small().color(tag_color(tag, &self.tag_colors));
+                                    if ui.selectable_label(active, label).clicked() {
+                                        if active {
+                                            self.tag_filters.retain(|t| t != tag);
+                                        } else {
+                                            self.tag_filters.push(tag.clone());
+                                        }
+                                    }
+                                }
+                            });
+                        });
+                    });
+            }
+
+            // Active tag filters as removable chips
+            if !self.tag_filters.is_empty() {
+                let mut removed = None;
+                ui.horizontal_wrapped(|ui| {
+                    for tag in &self.tag_filters {
+                        let chip = RichText::new(format!("#{} ✖", tag)).small().color(tag_color(tag, &self.tag_colors));
+                        if ui.button(chip).on_hover_text("Remove this filter").clicked() {
+                            removed = Some(tag.clone());
+                        }
+                    }
+                    if self.tag_filters.len() > 1 && ui.small_button("Clear").clicked() {
+                        self.tag_filters.clear();
+                    }
+                });
+                if let Some(tag) = removed {
+                    self.tag_filters.retain(|t| *t != tag);
+                }
+            }
+
             ui.separator();
 
             // Filter and display posts
-            let filtered_posts: Vec<&BlogPost> = posts
+            let matches = self.search_index.filter(&self.search_query, &self.tag_filters);
+            let mut filtered_posts: Vec<&BlogPost> = posts
                 .iter()
-                .filter(|post| {
-                    if self.search_query.is_empty() {
-                        true
-                    } else {
-                        let query = self.search_query.to_lowercase();
-                        post.title.to_lowercase().contains(&query)
-                            || post.content.to_lowercase().contains(&query)
-                            || post.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
-                    }
-                })
+                .filter(|post| matches.as_ref().map_or(true, |ids| ids.contains(&post.id)))
                 .collect();
+            self.sort.sort(&mut filtered_posts, &mut self.word_counts);
 
-            // Group posts by status
+            // Group posts by status, pinned posts get their own group on top
+            let mut pinned = Vec::new();
+            let mut scheduled = Vec::new();
             let mut drafts = Vec::new();
             let mut published = Vec::new();
             let mut failed = Vec::new();
 
             for post in filtered_posts {
+                if post.pinned {
+                    pinned.push(post);
+                    continue;
+                }
+                if post.scheduled_at.is_some() {
+                    scheduled.push(post);
+                    continue;
+                }
                 match post.status {
                     PostStatus::Draft => drafts.push(post),
                     PostStatus::Published => published.push(post),
@@ -81,10 +173,23 @@ impl Sidebar {
                 }
             }
 
+            // Next to go out first
+            scheduled.sort_by_key(|post| post.scheduled_at);
+
             // Display post groups
             egui::ScrollArea::vertical()
                 .auto_shrink([false, true])
                 .show(ui, |ui| {
+                    if !pinned.is_empty() {
+                        self.show_post_group(ui, "📌 Pinned", &pinned, theme_colors.primary, &mut action, theme_colors);
+                        ui.separator();
+                    }
+
+                    if !scheduled.is_empty() {
+                        self.show_post_group(ui, "⏰ Scheduled", &scheduled, theme_colors.primary, &mut action, theme_colors);
+                        ui.separator();
+                    }
+
                     if !drafts.is_empty() {
                         self.show_post_group(ui, "📄 Drafts", &drafts, theme_colors.warning, &mut action, theme_colors);
                         ui.separator();
@@ -99,7 +204,7 @@ impl Sidebar {
                         self.show_post_group(ui, "❌ Failed", &failed, theme_colors.error, &mut action, theme_colors);
                     }
 
-                    if drafts.is_empty() && published.is_empty() && failed.is_empty() {
+                    if pinned.is_empty() && scheduled.is_empty() && drafts.is_empty() && published.is_empty() && failed.is_empty() {
                         ui.vertical_centered(|ui| {
                             ui.add_space(50.0);
                             ui.label(RichText::new("No posts found").color(theme_colors.text_secondary));
@@ -147,6 +252,12 @@ impl Sidebar {
                 );
             }
 
+            // Accent stripe in the color of the post's first tag
+            if let Some(tag) = post.tags.first() {
+                let stripe = egui::Rect::from_min_size(response.rect.min, Vec2::new(4.0, response.rect.height()));
+                ui.painter().rect_filled(stripe, 2.0, tag_color(tag, &self.tag_colors));
+            }
+
             // Content
             ui.allocate_ui_at_rect(response.rect.shrink(8.0), |ui| {
                 ui.vertical(|ui| {
@@ -156,6 +267,11 @@ impl Sidebar {
                     } else {
                         &post.title
                     };
+                    let title_text = if post.private {
+                        format!("🔒 {}", title_text)
+                    } else {
+                        title_text.to_string()
+                    };
                     
                     ui.label(
                         RichText::new(title_text)
@@ -169,8 +285,13 @@ impl Sidebar {
 
                     // Metadata
                     ui.horizontal(|ui| {
+                        let words = if post.is_locked() {
+                            "Encrypted".to_string()
+                        } else {
+                            format!("{} words", self.word_counts.get(post))
+                        };
                         ui.label(
-                            RichText::new(format!("{} words", post.word_count()))
+                            RichText::new(words)
                                 .small()
                                 .color(theme_colors.text_muted)
                         );
@@ -185,8 +306,13 @@ impl Sidebar {
                         }
 
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            // Scheduled posts show when they go out instead of when they were edited
+                            let date = match post.scheduled_at {
+                                Some(scheduled_at) => format!("⏰ {}", scheduled_at.with_timezone(&chrono::Local).format("%m/%d %H:%M")),
+                                None => post.updated_at.format("%m/%d").to_string(),
+                            };
                             ui.label(
-                                RichText::new(post.updated_at.format("%m/%d").to_string())
+                                RichText::new(date)
                                     .small()
                                     .color(theme_colors.text_muted)
                             );
@@ -201,21 +327,64 @@ impl Sidebar {
                 *action = SidebarAction::SelectPost(post.id);
             }
 
-            // Context menu
+            // Context menu; deleting from relays is opted into each time
+            if response.secondary_clicked() {
+                self.delete_from_relays = false;
+            }
             response.context_menu(|ui| {
+                let pin_label = if post.pinned { "☆ Unpin" } else { "⭐ Pin to top" };
+                if ui.button(pin_label).clicked() {
+                    *action = SidebarAction::TogglePin(post.id);
+                    ui.close_menu();
+                }
+                let published = post.status == PostStatus::Published && post.nostr_event_id.is_some();
+                if published {
+                    ui.checkbox(&mut self.delete_from_relays, "Delete from relays too")
+                        .on_hover_text("Send a deletion request (NIP-09) to the relays this post was published to. Relays and clients that already have it may keep it.");
+                }
                 if ui.button("🗑️ Delete").clicked() {
-                    *action = SidebarAction::DeletePost(post.id);
+                    *action = SidebarAction::DeletePost {
+                        id: post.id,
+                        from_relays: published && self.delete_from_relays,
+                    };
+                    ui.close_menu();
+                }
+                if ui.button("📑 Duplicate").on_hover_text("Start a new draft from a copy of this post").clicked() {
+                    *action = SidebarAction::DuplicatePost(post.id);
                     ui.close_menu();
                 }
                 if ui.button("📤 Export").clicked() {
                     *action = SidebarAction::ExportPost(post.id);
                     ui.close_menu();
                 }
+                if ui.button("🌐 Export as HTML").on_hover_text("A standalone web page you can share or open in a browser").clicked() {
+                    *action = SidebarAction::ExportPostHtml(post.id);
+                    ui.close_menu();
+                }
+                if !post.private
+                    && ui.button("☁ Sync draft to Nostr")
+                        .on_hover_text("Save an encrypted copy to your relays so your other devices can pull it (NIP-37)")
+                        .clicked()
+                {
+                    *action = SidebarAction::SyncDraft(post.id);
+                    ui.close_menu();
+                }
+                if post.status == PostStatus::Published && post.nostr_event_id.is_some() {
+                    if ui.button("🔗 Copy naddr…").on_hover_text("Address for editing this article in other Nostr clients").clicked() {
+                        *action = SidebarAction::ShowAddress(post.id);
+                        ui.close_menu();
+                    }
+                }
                 if post.status == PostStatus::Draft {
                     if ui.button("🚀 Publish").clicked() {
                         *action = SidebarAction::PublishPost(post.id);
                         ui.close_menu();
                     }
+                } else if post.has_been_published() {
+                    if ui.button("🔄 Update published post").on_hover_text("Publish this version in place of the article on relays").clicked() {
+                        *action = SidebarAction::PublishPost(post.id);
+                        ui.close_menu();
+                    }
                 }
             });
 
@@ -229,7 +398,14 @@ pub enum SidebarAction {
     None,
     NewPost,
     SelectPost(uuid::Uuid),
-    DeletePost(uuid::Uuid),
+    DeletePost { id: uuid::Uuid, from_relays: bool },
+    DuplicatePost(uuid::Uuid),
     ExportPost(uuid::Uuid),
+    ExportPostHtml(uuid::Uuid),
     PublishPost(uuid::Uuid),
+    TogglePin(uuid::Uuid),
+    ShowAddress(uuid::Uuid),
+    SyncDraft(uuid::Uuid),
+    /// The user picked a different order, which should be remembered
+    SortChanged(PostSort),
 }
@@ -0,0 +1,126 @@
+use crate::table_import::{detect_delimiter, looks_like_header, parse_rows, to_markdown_table, Delimiter};
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, ScrollArea, TextEdit, Window};
+
+/// Converts pasted spreadsheet data (CSV/TSV) into a markdown table
+pub struct TableDialog {
+    open: bool,
+    input: String,
+    delimiter: Option<Delimiter>,
+    has_header: bool,
+    last_detected_input: String,
+}
+
+impl Default for TableDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            delimiter: None,
+            has_header: true,
+            last_detected_input: String::new(),
+        }
+    }
+}
+
+impl TableDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        *self = Self::default();
+        self.open = true;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns the markdown table when the user clicks Insert
+    pub fn show(&mut self, ctx: &Context, theme_colors: &ThemeColors) -> Option<String> {
+        let mut result = None;
+
+        if !self.open {
+            return result;
+        }
+
+        // Re-run detection whenever the pasted data changes
+        if self.input != self.last_detected_input {
+            self.delimiter = detect_delimiter(&self.input);
+            if let Some(delimiter) = self.delimiter {
+                self.has_header = looks_like_header(&parse_rows(&self.input, delimiter));
+            }
+            self.last_detected_input = self.input.clone();
+        }
+
+        let table = self
+            .delimiter
+            .map(|delimiter| to_markdown_table(&parse_rows(&self.input, delimiter), self.has_header))
+            .unwrap_or_default();
+
+        let mut window_open = self.open;
+        let mut insert_clicked = false;
+
+        Window::new("📊 Paste Table")
+            .open(&mut window_open)
+            .resizable(true)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                ui.label(RichText::new("Paste rows copied from a spreadsheet (CSV or TSV):").color(theme_colors.text));
+                ui.add(
+                    TextEdit::multiline(&mut self.input)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_rows(6)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("Name\tValue\nAlice\t1000"),
+                );
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label("Delimiter:");
+                    egui::ComboBox::from_id_source("table_delimiter")
+                        .selected_text(self.delimiter.map(|d| d.name()).unwrap_or("Not detected"))
+                        .show_ui(ui, |ui| {
+                            for delimiter in Delimiter::all() {
+                                ui.selectable_value(&mut self.delimiter, Some(delimiter), delimiter.name());
+                            }
+                        });
+
+                    ui.checkbox(&mut self.has_header, "First row is a header");
+                });
+
+                ui.add_space(8.0);
+                if table.is_empty() {
+                    ui.label(RichText::new("No table detected yet").italics().color(theme_colors.text_muted));
+                } else {
+                    ui.label(RichText::new("Preview:").strong().color(theme_colors.text));
+                    ScrollArea::both().max_height(150.0).show(ui, |ui| {
+                        ui.label(RichText::new(&table).monospace().color(theme_colors.text_secondary));
+                    });
+                }
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!table.is_empty(), egui::Button::new(RichText::new("➕ Insert").color(theme_colors.success)))
+                        .clicked()
+                    {
+                        insert_clicked = true;
+                    }
+                    if ui.button("❌ Cancel").clicked() {
+                        self.open = false;
+                    }
+                });
+            });
+
+        self.open = self.open && window_open;
+
+        if insert_clicked {
+            self.open = false;
+            result = Some(table);
+        }
+
+        result
+    }
+}
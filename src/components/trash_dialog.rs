@@ -0,0 +1,148 @@
+use crate::storage::{Storage, TrashedPost};
+use crate::theme::ThemeColors;
+use egui::{Context, RichText, Window};
+
+/// Lists deleted posts so they can be restored or removed for good
+#[derive(Default)]
+pub struct TrashDialog {
+    open: bool,
+    trashed: Vec<TrashedPost>,
+    error_message: Option<String>,
+    success_message: Option<String>,
+    /// Emptying the trash is waiting for the user to confirm it
+    confirm_empty: bool,
+}
+
+impl TrashDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, storage: &Storage) {
+        self.open = true;
+        self.error_message = None;
+        self.success_message = None;
+        self.confirm_empty = false;
+        self.refresh(storage);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn refresh(&mut self, storage: &Storage) {
+        match storage.list_trash() {
+            Ok(trashed) => self.trashed = trashed,
+            Err(e) => {
+                tracing::warn!("Failed to list the trash: {:#}", e);
+                self.error_message = Some(format!("{:#}", e));
+            }
+        }
+    }
+
+    /// Returns a post once it has been restored to the library
+    pub fn show(&mut self, ctx: &Context, storage: &Storage, theme_colors: &ThemeColors) -> Option<crate::post::BlogPost> {
+        if !self.open {
+            return None;
+        }
+
+        let mut result = None;
+        let mut window_open = self.open;
+        let mut to_restore = None;
+        let mut to_delete = None;
+        let mut empty = false;
+
+        Window::new("🗑 Trash")
+            .open(&mut window_open)
+            .collapsible(false)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new("Deleted posts stay here until you empty the trash.")
+                        .color(theme_colors.text),
+                );
+
+                if let Some(error) = &self.error_message {
+                    ui.colored_label(theme_colors.error, format!("❌ {}", error));
+                }
+                if let Some(success) = &self.success_message {
+                    ui.colored_label(theme_colors.success, format!("✅ {}", success));
+                }
+                ui.add_space(6.0);
+
+                if self.trashed.is_empty() {
+                    ui.label(RichText::new("The trash is empty").color(theme_colors.text_muted));
+                }
+
+                egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    for trashed in &self.trashed {
+                        ui.horizontal(|ui| {
+                            let title = if trashed.post.title.is_empty() { "Untitled" } else { &trashed.post.title };
+                            ui.label(RichText::new(title).strong().color(theme_colors.text));
+                            ui.label(
+                                RichText::new(format!("deleted {}", trashed.deleted_at.format("%Y-%m-%d %H:%M")))
+                                    .small()
+                                    .color(theme_colors.text_muted),
+                            );
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.small_button("❌ Delete forever").clicked() {
+                                    to_delete = Some(trashed.post.id);
+                                }
+                                if ui.small_button("↩ Restore").clicked() {
+                                    to_restore = Some(trashed.post.id);
+                                }
+                            });
+                        });
+                    }
+                });
+
+                if !self.trashed.is_empty() {
+                    ui.separator();
+                    if self.confirm_empty {
+                        ui.colored_label(
+                            theme_colors.warning,
+                            format!("⚠ Permanently delete {} post(s)? This can't be undone.", self.trashed.len()),
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button(RichText::new("🗑 Empty Trash").color(theme_colors.error)).clicked() {
+                                empty = true;
+                                self.confirm_empty = false;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                self.confirm_empty = false;
+                            }
+                        });
+                    } else if ui.button("🗑 Empty Trash…").clicked() {
+                        self.confirm_empty = true;
+                    }
+                }
+            });
+
+        if let Some(id) = to_restore {
+            match storage.restore_post(id) {
+                Ok(post) => {
+                    self.success_message = Some(format!("Restored \"{}\"", post.title));
+                    self.error_message = None;
+                    result = Some(post);
+                }
+                Err(e) => self.error_message = Some(format!("Failed to restore post: {:#}", e)),
+            }
+            self.refresh(storage);
+        } else if let Some(id) = to_delete {
+            match storage.delete_from_trash(id) {
+                Ok(()) => self.success_message = Some("Post deleted for good".to_string()),
+                Err(e) => self.error_message = Some(format!("Failed to delete post: {:#}", e)),
+            }
+            self.refresh(storage);
+        } else if empty {
+            match storage.empty_trash() {
+                Ok(count) => self.success_message = Some(format!("Deleted {} post(s) for good", count)),
+                Err(e) => self.error_message = Some(format!("Failed to empty the trash: {:#}", e)),
+            }
+            self.refresh(storage);
+        }
+
+        self.open = window_open;
+        result
+    }
+}
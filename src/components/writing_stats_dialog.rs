@@ -0,0 +1,163 @@
+use crate::theme::ThemeColors;
+use crate::writing_stats::WritingStats;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use egui::{Context, RichText, Sense, Vec2, Window};
+
+/// Number of weeks shown in the activity heatmap
+const HEATMAP_WEEKS: i64 = 16;
+const CELL_SIZE: f32 = 12.0;
+const CELL_GAP: f32 = 3.0;
+
+/// Daily word counts, the current streak and a calendar heatmap
+#[derive(Default)]
+pub struct WritingStatsDialog {
+    open: bool,
+    confirm_reset: bool,
+}
+
+impl WritingStatsDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.confirm_reset = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns true when the stats were changed (goal edited or history reset)
+    pub fn show(&mut self, ctx: &Context, stats: &mut WritingStats, theme_colors: &ThemeColors) -> bool {
+        let mut changed = false;
+
+        if !self.open {
+            return changed;
+        }
+
+        let today = Local::now().date_naive();
+        let mut window_open = self.open;
+
+        Window::new("📊 Writing Stats")
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let streak = stats.streak(today);
+                let streak_text = match streak {
+                    0 => "No streak yet. Write something today!".to_string(),
+                    1 => "🔥 1 day in a row".to_string(),
+                    n => format!("🔥 {} days in a row", n),
+                };
+                ui.heading(RichText::new(streak_text).color(theme_colors.primary));
+                ui.add_space(8.0);
+
+                // Today's progress
+                let written = stats.words_on(today);
+                if stats.daily_goal > 0 {
+                    let progress = (written as f32 / stats.daily_goal as f32).min(1.0);
+                    ui.add(
+                        egui::ProgressBar::new(progress)
+                            .text(format!("{} / {} words today", written, stats.daily_goal))
+                            .fill(if written >= stats.daily_goal { theme_colors.success } else { theme_colors.info }),
+                    );
+                } else {
+                    ui.label(format!("{} words today", written));
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Daily goal:");
+                    if ui
+                        .add(egui::DragValue::new(&mut stats.daily_goal).range(0..=20000).suffix(" words"))
+                        .on_hover_text("Set to 0 to turn the goal off")
+                        .changed()
+                    {
+                        changed = true;
+                    }
+                });
+
+                ui.add_space(8.0);
+                ui.label(RichText::new(format!("Last {} weeks", HEATMAP_WEEKS)).small().color(theme_colors.text_muted));
+                Self::show_heatmap(ui, stats, today, theme_colors);
+
+                ui.add_space(8.0);
+                ui.label(
+                    RichText::new("Counts words added each time a post is saved. Stats are kept on this device only.")
+                        .small()
+                        .color(theme_colors.text_muted),
+                );
+
+                ui.separator();
+                if self.confirm_reset {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Delete all writing history?").color(theme_colors.warning));
+                        if ui.button(RichText::new("Reset").color(theme_colors.error)).clicked() {
+                            stats.reset();
+                            changed = true;
+                            self.confirm_reset = false;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.confirm_reset = false;
+                        }
+                    });
+                } else if ui.button("🗑 Reset stats").clicked() {
+                    self.confirm_reset = true;
+                }
+            });
+
+        self.open = window_open;
+
+        changed
+    }
+
+    /// One column per week, one row per weekday (Monday on top)
+    fn show_heatmap(ui: &mut egui::Ui, stats: &WritingStats, today: NaiveDate, theme_colors: &ThemeColors) {
+        let days_since_monday = today.weekday().num_days_from_monday() as i64;
+        let start = today - Duration::days(days_since_monday + (HEATMAP_WEEKS - 1) * 7);
+        let busiest = (0..HEATMAP_WEEKS * 7)
+            .map(|offset| stats.words_on(start + Duration::days(offset)))
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let size = Vec2::new(
+            HEATMAP_WEEKS as f32 * (CELL_SIZE + CELL_GAP),
+            7.0 * (CELL_SIZE + CELL_GAP),
+        );
+        let (response, painter) = ui.allocate_painter(size, Sense::hover());
+        let origin = response.rect.min;
+        let mut hovered = None;
+
+        for week in 0..HEATMAP_WEEKS {
+            for weekday in 0..7 {
+                let date = start + Duration::days(week * 7 + weekday);
+                if date > today {
+                    continue;
+                }
+
+                let words = stats.words_on(date);
+                let color = if words == 0 {
+                    theme_colors.surface
+                } else {
+                    // Scale the accent color with how much was written
+                    let intensity = 0.3 + 0.7 * (words as f32 / busiest as f32);
+                    theme_colors.success.gamma_multiply(intensity)
+                };
+
+                let min = origin + Vec2::new(week as f32, weekday as f32) * (CELL_SIZE + CELL_GAP);
+                let cell = egui::Rect::from_min_size(min, Vec2::splat(CELL_SIZE));
+                painter.rect_filled(cell, 2.0, color);
+
+                if response.hover_pos().is_some_and(|pos| cell.contains(pos)) {
+                    hovered = Some((date, words));
+                }
+            }
+        }
+
+        if let Some((date, words)) = hovered {
+            response.on_hover_text(format!("{}: {} words", date.format("%a %b %-d"), words));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::{anyhow, Context, Result};
+use argon2::Argon2;
+use base64::prelude::*;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+/// Marks an encrypted credentials file; followed by the salt, nonce and ciphertext
+const ENCRYPTED_PREFIX: &str = "blogster-credentials:v1:";
+
+/// Returned when saved credentials are encrypted and no passphrase was given
+#[derive(Debug)]
+pub struct CredentialsLocked;
+
+impl std::fmt::Display for CredentialsLocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Saved credentials are encrypted; enter the passphrase to unlock them")
+    }
+}
+
+impl std::error::Error for CredentialsLocked {}
+
+/// Whether a credentials file holds an encrypted payload rather than the
+/// old base64-only format
+pub fn is_encrypted(content: &str) -> bool {
+    content.trim_start().starts_with(ENCRYPTED_PREFIX)
+}
+
+/// A key derived from a passphrase with Argon2, and the salt it was derived
+/// with. Deriving is slow on purpose, so keep one around to encrypt or
+/// decrypt several things.
+#[derive(Clone)]
+pub struct PassphraseKey {
+    salt: Vec<u8>,
+    key: [u8; 32],
+}
+
+impl PassphraseKey {
+    /// Derive a key with a fresh salt
+    pub fn generate(passphrase: &str) -> Result<Self> {
+        Self::derive(passphrase, uuid::Uuid::new_v4().as_bytes())
+    }
+
+    pub fn derive(passphrase: &str, salt: &[u8]) -> Result<Self> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| anyhow!("Failed to derive a key from the passphrase: {}", e))?;
+        Ok(Self { salt: salt.to_vec(), key })
+    }
+
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+}
+
+/// Encrypt `plaintext` into `prefix` followed by the key's salt, a fresh
+/// nonce and the ciphertext. There is no limit on the plaintext's length.
+pub fn seal(prefix: &str, plaintext: &str, key: &PassphraseKey) -> Result<String> {
+    let cipher = ChaCha20Poly1305::new(&key.key.into());
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| anyhow!("Failed to encrypt"))?;
+
+    Ok(format!(
+        "{}{}:{}:{}",
+        prefix,
+        BASE64_STANDARD.encode(&key.salt),
+        BASE64_STANDARD.encode(nonce),
+        BASE64_STANDARD.encode(ciphertext)
+    ))
+}
+
+/// Split content written by `seal` into its salt, nonce and ciphertext
+fn parse_sealed(prefix: &str, content: &str) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    let encrypted = content
+        .trim()
+        .strip_prefix(prefix)
+        .ok_or_else(|| anyhow!("Content is not encrypted"))?;
+    let mut parts = encrypted.split(':');
+    let (Some(salt), Some(nonce), Some(ciphertext), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(anyhow!("Encrypted content is damaged"));
+    };
+
+    let salt = BASE64_STANDARD.decode(salt).context("Encrypted content has an invalid salt")?;
+    let nonce = BASE64_STANDARD.decode(nonce).context("Encrypted content has an invalid nonce")?;
+    let ciphertext = BASE64_STANDARD.decode(ciphertext).context("Encrypted content is damaged")?;
+    if nonce.len() != 12 {
+        return Err(anyhow!("Encrypted content has an invalid nonce"));
+    }
+    Ok((salt, nonce, ciphertext))
+}
+
+/// The salt the key for content written by `seal` was derived with
+pub fn sealed_salt(prefix: &str, content: &str) -> Result<Vec<u8>> {
+    parse_sealed(prefix, content).map(|(salt, _, _)| salt)
+}
+
+/// Decrypt content written by `seal`. `key` must have been derived with the
+/// content's salt.
+pub fn open(prefix: &str, content: &str, key: &PassphraseKey) -> Result<String> {
+    let (salt, nonce, ciphertext) = parse_sealed(prefix, content)?;
+    if salt != key.salt {
+        return Err(anyhow!("Encrypted with a different key"));
+    }
+
+    let cipher = ChaCha20Poly1305::new(&key.key.into());
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("Wrong passphrase, or the encrypted content is damaged"))?;
+
+    String::from_utf8(plaintext).context("Invalid UTF-8 in decrypted content")
+}
+
+/// Encrypt serialized credentials with a key derived from `passphrase`
+pub fn encrypt(plaintext: &str, passphrase: &str) -> Result<String> {
+    seal(ENCRYPTED_PREFIX, plaintext, &PassphraseKey::generate(passphrase)?)
+}
+
+/// Decrypt content written by `encrypt`
+pub fn decrypt(content: &str, passphrase: &str) -> Result<String> {
+    let salt = sealed_salt(ENCRYPTED_PREFIX, content)?;
+    open(ENCRYPTED_PREFIX, content, &PassphraseKey::derive(passphrase, &salt)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let json = r#"{"private_key":"nsec1secret","public_key":"abc"}"#;
+        let encrypted = encrypt(json, "correct horse").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("nsec1secret"));
+
+        assert_eq!(decrypt(&encrypted, "correct horse").unwrap(), json);
+        assert!(decrypt(&encrypted, "wrong horse").is_err());
+        assert!(!is_encrypted(&BASE64_STANDARD.encode(json)));
+    }
+}
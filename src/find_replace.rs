@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+/// How the find bar matches text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FindOptions {
+    pub case_sensitive: bool,
+    /// Only match text that isn't part of a longer word
+    pub whole_word: bool,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn chars_match(a: char, b: char, case_sensitive: bool) -> bool {
+    a == b || (!case_sensitive && a.to_lowercase().eq(b.to_lowercase()))
+}
+
+/// Length in bytes of the text matching `query` at the start of `text`
+fn match_at(text: &str, query: &str, case_sensitive: bool) -> Option<usize> {
+    let mut text_chars = text.char_indices();
+    for q in query.chars() {
+        let (_, c) = text_chars.next()?;
+        if !chars_match(c, q, case_sensitive) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(index, _)| index))
+}
+
+/// Byte ranges of the non-overlapping matches of `query` in `content`
+pub fn find_matches(content: &str, query: &str, options: FindOptions) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+
+    let mut start = 0;
+    while start < content.len() {
+        let rest = &content[start..];
+        let Some(length) = match_at(rest, query, options.case_sensitive) else {
+            start += rest.chars().next().map_or(1, char::len_utf8);
+            continue;
+        };
+
+        let end = start + length;
+        let bounded = !options.whole_word
+            || (!content[..start].chars().next_back().is_some_and(is_word_char)
+                && !content[end..].chars().next().is_some_and(is_word_char));
+        if bounded {
+            matches.push(start..end);
+            start = end;
+        } else {
+            start += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+
+    matches
+}
+
+/// Replace every match of `query`, returning how many were replaced
+pub fn replace_all(content: &mut String, query: &str, replacement: &str, options: FindOptions) -> usize {
+    let matches = find_matches(content, query, options);
+    for range in matches.iter().rev() {
+        content.replace_range(range.clone(), replacement);
+    }
+    matches.len()
+}
+
+/// Convert a byte range of `content` to a character range
+pub fn char_range(content: &str, range: Range<usize>) -> Range<usize> {
+    let start = content[..range.start].chars().count();
+    start..start + content[range].chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_options() {
+        let content = "Nostr notes: nostr, NOSTRICH and nostr_sdk";
+
+        let any = FindOptions::default();
+        assert_eq!(find_matches(content, "nostr", any).len(), 4);
+
+        let case_sensitive = FindOptions { case_sensitive: true, ..any };
+        assert_eq!(find_matches(content, "nostr", case_sensitive), vec![13..18, 33..38]);
+
+        let whole_word = FindOptions { whole_word: true, ..any };
+        assert_eq!(find_matches(content, "nostr", whole_word), vec![0..5, 13..18]);
+        assert!(find_matches(content, "", any).is_empty());
+    }
+
+    #[test]
+    fn test_replace_all_and_char_ranges() {
+        let mut content = "Café, café, CAFÉ".to_string();
+        let options = FindOptions::default();
+
+        let matches = find_matches(&content, "café", options);
+        assert_eq!(char_range(&content, matches[1].clone()), 6..10);
+
+        assert_eq!(replace_all(&mut content, "café", "tea", options), 3);
+        assert_eq!(content, "tea, tea, tea");
+    }
+}
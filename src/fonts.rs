@@ -0,0 +1,145 @@
+use ab_glyph::Font as _;
+use anyhow::{anyhow, Context, Result};
+use egui::{FontData, FontDefinitions, FontFamily, FontId, TextStyle};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// egui's body text size; the other text styles keep their ratio to it
+pub const DEFAULT_FONT_SIZE: f32 = 12.5;
+
+/// Range offered for the base font size
+pub const MIN_FONT_SIZE: f32 = 9.0;
+pub const MAX_FONT_SIZE: f32 = 24.0;
+
+/// A font for the interface or the editor
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FontChoice {
+    /// egui's font for the family: Ubuntu Light for text, Hack for code
+    #[default]
+    Default,
+    /// The bundled Ubuntu Light font
+    Ubuntu,
+    /// The bundled Hack monospace font
+    Hack,
+    /// A TTF or OTF font file, e.g. one installed on the system
+    File(PathBuf),
+}
+
+impl FontChoice {
+    /// Fonts offered without picking a file
+    pub const BUNDLED: [FontChoice; 3] = [FontChoice::Default, FontChoice::Ubuntu, FontChoice::Hack];
+
+    pub fn label(&self) -> String {
+        match self {
+            FontChoice::Default => "Default".to_string(),
+            FontChoice::Ubuntu => "Ubuntu Light".to_string(),
+            FontChoice::Hack => "Hack".to_string(),
+            FontChoice::File(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string()),
+        }
+    }
+}
+
+/// Read a font file and check egui can draw with it, since a font it can't
+/// parse panics the first time text is laid out
+pub fn load_font_file(path: &Path) -> Result<Vec<u8>> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read font {}", path.display()))?;
+    let font = ab_glyph::FontRef::try_from_slice(&bytes)
+        .map_err(|_| anyhow!("{} is not a TTF or OTF font", path.display()))?;
+    if font.units_per_em().is_none() {
+        return Err(anyhow!("{} uses an unsupported font unit size", path.display()));
+    }
+    Ok(bytes)
+}
+
+/// Name of the font to put first for a choice, loading font files into `fonts`
+fn font_name(choice: &FontChoice, fonts: &mut FontDefinitions) -> Result<Option<String>> {
+    match choice {
+        FontChoice::Default => Ok(None),
+        FontChoice::Ubuntu => Ok(Some("Ubuntu-Light".to_string())),
+        FontChoice::Hack => Ok(Some("Hack".to_string())),
+        FontChoice::File(path) => {
+            let bytes = load_font_file(path)?;
+            let name = path.display().to_string();
+            fonts.font_data.insert(name.clone(), FontData::from_owned(bytes));
+            Ok(Some(name))
+        }
+    }
+}
+
+/// egui's fonts with the chosen ones in front, so the defaults still cover
+/// missing glyphs and emoji. Fonts that can't be loaded are left out and
+/// reported instead.
+pub fn font_definitions(ui_font: &FontChoice, editor_font: &FontChoice) -> (FontDefinitions, Vec<String>) {
+    let mut fonts = FontDefinitions::default();
+    let mut errors = Vec::new();
+
+    for (choice, family) in [(ui_font, FontFamily::Proportional), (editor_font, FontFamily::Monospace)] {
+        match font_name(choice, &mut fonts) {
+            Ok(Some(name)) => {
+                let names = fonts.families.entry(family).or_default();
+                names.retain(|existing| existing != &name);
+                names.insert(0, name);
+            }
+            Ok(None) => {}
+            Err(e) => errors.push(format!("{:#}", e)),
+        }
+    }
+
+    (fonts, errors)
+}
+
+/// egui's text styles scaled so body text is `font_size` points
+pub fn scaled_text_styles(font_size: f32) -> BTreeMap<TextStyle, FontId> {
+    let scale = font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE) / DEFAULT_FONT_SIZE;
+    egui::Style::default()
+        .text_styles
+        .into_iter()
+        .map(|(style, font)| (style, FontId::new(font.size * scale, font.family)))
+        .collect()
+}
+
+/// Use the chosen fonts and base size; returns why any font couldn't be loaded
+pub fn apply_fonts(ctx: &egui::Context, ui_font: &FontChoice, editor_font: &FontChoice, font_size: f32) -> Vec<String> {
+    let (fonts, errors) = font_definitions(ui_font, editor_font);
+    for error in &errors {
+        tracing::warn!("Using the default font: {}", error);
+    }
+    ctx.set_fonts(fonts);
+
+    let text_styles = scaled_text_styles(font_size);
+    ctx.style_mut(|style| style.text_styles = text_styles);
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_styles_keep_their_ratios() {
+        assert_eq!(scaled_text_styles(DEFAULT_FONT_SIZE), egui::Style::default().text_styles);
+
+        let doubled = scaled_text_styles(DEFAULT_FONT_SIZE * 1.5);
+        assert_eq!(doubled[&TextStyle::Body].size, DEFAULT_FONT_SIZE * 1.5);
+        assert_eq!(doubled[&TextStyle::Heading].size, 18.0 * 1.5);
+        assert_eq!(doubled[&TextStyle::Monospace].family, FontFamily::Monospace);
+    }
+
+    #[test]
+    fn test_unreadable_font_falls_back() {
+        let path = std::env::temp_dir().join(format!("blogster-font-{}.ttf", uuid::Uuid::new_v4()));
+        std::fs::write(&path, b"not a font").unwrap();
+
+        let (fonts, errors) = font_definitions(&FontChoice::Hack, &FontChoice::File(path.clone()));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(fonts.families[&FontFamily::Proportional][0], "Hack");
+        assert_eq!(fonts.families[&FontFamily::Monospace], FontDefinitions::default().families[&FontFamily::Monospace]);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::post::{BlogPost, PostStatus};
+use crate::theme::ThemeColors;
+use pulldown_cmark::{html, Event, Options, Parser};
+
+/// Styling for standalone exports; kept small so the file reads well anywhere
+const STYLESHEET: &str = "body{max-width:42rem;margin:2rem auto;padding:0 1rem;font-family:Georgia,serif;line-height:1.6;color:#222}\
+header .meta{color:#666;font-size:.9rem}\
+header .summary{font-style:italic;color:#444}\
+.tags{list-style:none;padding:0;display:flex;flex-wrap:wrap;gap:.5rem;font-size:.9rem}\
+img{max-width:100%}\
+.gallery{display:grid;grid-template-columns:repeat(auto-fill,minmax(12rem,1fr));gap:.5rem}\
+pre{overflow-x:auto;background:#f4f4f4;padding:.75rem}";
+
+/// Line the editor inserts to start a new page. An HTML comment, so Nostr
+/// clients and the preview don't show it.
+pub const PAGE_BREAK_MARKER: &str = "<!-- pagebreak -->";
+
+/// File name of the stylesheet shared by the pages of an exported site
+pub const SITE_STYLESHEET: &str = "style.css";
+
+#[derive(Debug, Clone, Copy)]
+pub struct HtmlExportOptions {
+    /// Add date, word count, reading time and tags to the document header
+    pub include_stats: bool,
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Start a document, up to and including the opening `<body>`. Site pages
+/// link the shared stylesheet, standalone documents carry their own.
+fn push_head(document: &mut String, title: &str, description: Option<&str>, keywords: &[String], site: bool) {
+    document.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    document.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    document.push_str(&format!("<title>{}</title>\n", escape_html(title)));
+    if let Some(description) = description {
+        document.push_str(&format!("<meta name=\"description\" content=\"{}\">\n", escape_html(description)));
+    }
+    if !keywords.is_empty() {
+        document.push_str(&format!("<meta name=\"keywords\" content=\"{}\">\n", escape_html(&keywords.join(", "))));
+    }
+    document.push_str("<meta name=\"generator\" content=\"Blogster\">\n");
+    if site {
+        document.push_str(&format!("<link rel=\"stylesheet\" href=\"{}\">\n", SITE_STYLESHEET));
+    } else {
+        document.push_str(&format!("<style>{}</style>\n", STYLESHEET));
+    }
+    document.push_str("</head>\n<body>\n");
+}
+
+fn push_tags(document: &mut String, tags: &[String]) {
+    if !tags.is_empty() {
+        document.push_str("<ul class=\"tags\">");
+        for tag in tags {
+            document.push_str(&format!("<li>#{}</li>", escape_html(tag)));
+        }
+        document.push_str("</ul>\n");
+    }
+}
+
+/// When a post went out, or was written if it hasn't been published
+fn post_date(post: &BlogPost) -> chrono::DateTime<chrono::Utc> {
+    post.published_at.unwrap_or(post.created_at)
+}
+
+/// Render a post as a standalone HTML document
+pub fn render_html(post: &BlogPost, options: HtmlExportOptions) -> String {
+    render_post(post, options, false)
+}
+
+/// Render a post as a page of an exported site, linking back to its index
+pub fn render_site_page(post: &BlogPost, options: HtmlExportOptions) -> String {
+    render_post(post, options, true)
+}
+
+fn render_post(post: &BlogPost, options: HtmlExportOptions, site: bool) -> String {
+    let mut document = String::new();
+    let keywords: &[String] = if options.include_stats { &post.tags } else { &[] };
+    push_head(&mut document, &post.title, post.summary.as_deref(), keywords, site);
+    if site {
+        document.push_str("<nav><a href=\"index.html\">← All posts</a></nav>\n");
+    }
+    document.push_str("<article>\n<header>\n");
+    document.push_str(&format!("<h1>{}</h1>\n", escape_html(&post.title)));
+    if let Some(summary) = &post.summary {
+        document.push_str(&format!("<p class=\"summary\">{}</p>\n", escape_html(summary)));
+    }
+
+    if options.include_stats {
+        let date_label = if post.status == PostStatus::Published { "Published" } else { "Written" };
+        let reading_time = post.reading_time();
+        let date = post_date(post);
+        document.push_str(&format!(
+            "<p class=\"meta\">{} <time datetime=\"{}\">{}</time> · {} words · {} min read</p>\n",
+            date_label,
+            date.to_rfc3339(),
+            date.format("%B %-d, %Y"),
+            post.word_count(),
+            reading_time,
+        ));
+        push_tags(&mut document, &post.tags);
+    }
+    document.push_str("</header>\n");
+
+    if let Some(image_url) = &post.image_url {
+        document.push_str(&format!(
+            "<figure class=\"cover\"><img src=\"{}\" alt=\"\"></figure>\n",
+            escape_html(image_url)
+        ));
+    }
+
+    let markdown_options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let events = Parser::new_ext(&post.content, markdown_options).map(|event| match event {
+        Event::Html(html) if html.trim() == PAGE_BREAK_MARKER => Event::Html("<div style=\"break-after: page\"></div>\n".into()),
+        event => event,
+    });
+    html::push_html(&mut document, events);
+
+    if !post.gallery_images.is_empty() {
+        document.push_str("<section class=\"gallery\">\n");
+        for image_url in &post.gallery_images {
+            document.push_str(&format!("<img src=\"{}\" alt=\"\" loading=\"lazy\">\n", escape_html(image_url)));
+        }
+        document.push_str("</section>\n");
+    }
+
+    document.push_str("</article>\n</body>\n</html>\n");
+    document
+}
+
+/// File name of a post's page in an exported site
+pub fn site_page_name(post: &BlogPost) -> String {
+    std::path::Path::new(&post.generate_filename())
+        .with_extension("html")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Render the front page of an exported site: every listed post, newest
+/// first, with its summary and tags. Unlisted posts only get their own page.
+pub fn render_site_index(posts: &[BlogPost]) -> String {
+    let mut posts: Vec<&BlogPost> = posts.iter().filter(|post| !post.unlisted).collect();
+    posts.sort_by_key(|post| std::cmp::Reverse(post_date(post)));
+
+    let mut document = String::new();
+    push_head(&mut document, "Posts", None, &[], true);
+    document.push_str("<main>\n<h1>Posts</h1>\n");
+    for post in posts {
+        let date = post_date(post);
+        document.push_str("<article class=\"entry\">\n");
+        document.push_str(&format!(
+            "<h2><a href=\"{}\">{}</a></h2>\n",
+            escape_html(&site_page_name(post)),
+            escape_html(&post.title)
+        ));
+        document.push_str(&format!(
+            "<p class=\"meta\"><time datetime=\"{}\">{}</time></p>\n",
+            date.to_rfc3339(),
+            date.format("%B %-d, %Y")
+        ));
+        if let Some(summary) = &post.summary {
+            document.push_str(&format!("<p class=\"summary\">{}</p>\n", escape_html(summary)));
+        }
+        push_tags(&mut document, &post.tags);
+        document.push_str("</article>\n");
+    }
+    document.push_str("</main>\n</body>\n</html>\n");
+    document
+}
+
+fn css_color(color: egui::Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// Stylesheet for an exported site in the colors of the current theme
+pub fn site_stylesheet(colors: &ThemeColors) -> String {
+    format!(
+        "body{{max-width:42rem;margin:2rem auto;padding:0 1rem;font-family:Georgia,serif;line-height:1.6;background:{background};color:{text}}}\n\
+a{{color:{primary}}}\n\
+nav{{margin-bottom:1rem;font-size:.9rem}}\n\
+.meta{{color:{muted};font-size:.9rem}}\n\
+.summary{{font-style:italic;color:{secondary}}}\n\
+.tags{{list-style:none;padding:0;display:flex;flex-wrap:wrap;gap:.5rem;font-size:.9rem;color:{accent}}}\n\
+.entry{{border-bottom:1px solid {border};padding-bottom:1rem}}\n\
+img{{max-width:100%}}\n\
+.gallery{{display:grid;grid-template-columns:repeat(auto-fill,minmax(12rem,1fr));gap:.5rem}}\n\
+pre,code{{background:{surface}}}\n\
+pre{{overflow-x:auto;padding:.75rem}}\n",
+        background = css_color(colors.background),
+        text = css_color(colors.text),
+        primary = css_color(colors.primary),
+        muted = css_color(colors.text_muted),
+        secondary = css_color(colors.text_secondary),
+        accent = css_color(colors.secondary),
+        border = css_color(colors.border),
+        surface = css_color(colors.surface),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_in_header() {
+        let mut post = BlogPost::new()
+            .with_title("Fish & <Chips>".to_string())
+            .with_content("## Intro\n\nSome words here".to_string());
+        post.add_tag("food".to_string());
+        post.summary = Some("Crispy & hot".to_string());
+
+        let exported = render_html(&post, HtmlExportOptions { include_stats: true });
+        assert!(exported.contains("<p class=\"summary\">Crispy &amp; hot</p>"));
+        assert!(exported.contains("<title>Fish &amp; &lt;Chips&gt;</title>"));
+        assert!(exported.contains("4 words · 1 min read"));
+        assert!(exported.contains(&format!("<time datetime=\"{}\">", post.created_at.to_rfc3339())));
+
+        // Published posts show when they went out, not when they were started
+        post.created_at -= chrono::Duration::days(10);
+        post.set_published("event".to_string(), vec![], Some(chrono::Utc::now()));
+        let exported = render_html(&post, HtmlExportOptions { include_stats: true });
+        assert!(exported.contains(&format!("Published <time datetime=\"{}\">", post.published_at.unwrap().to_rfc3339())));
+        assert!(exported.contains("<li>#food</li>"));
+        assert!(exported.contains("<h2>Intro</h2>"));
+
+        let exported = render_html(&post, HtmlExportOptions { include_stats: false });
+        assert!(!exported.contains("min read"));
+        assert!(!exported.contains("#food"));
+    }
+
+    #[test]
+    fn test_page_break_marker() {
+        let post = BlogPost::new()
+            .with_title("Two pages".to_string())
+            .with_content(format!("First page\n\n{}\n\nSecond page", PAGE_BREAK_MARKER));
+
+        let exported = render_html(&post, HtmlExportOptions { include_stats: false });
+        assert!(exported.contains("<p>First page</p>\n<div style=\"break-after: page\"></div>\n<p>Second page</p>"));
+        assert!(!exported.contains("pagebreak"));
+    }
+
+    #[test]
+    fn test_site_index_lists_newest_first() {
+        let mut older = BlogPost::new().with_title("Older".to_string());
+        older.summary = Some("First post".to_string());
+        older.published_at = Some(chrono::Utc::now() - chrono::Duration::days(3));
+        let mut newer = BlogPost::new().with_title("Newer".to_string());
+        newer.published_at = Some(chrono::Utc::now());
+        newer.add_tag("nostr".to_string());
+
+        let mut hidden = BlogPost::new().with_title("Hidden".to_string());
+        hidden.unlisted = true;
+
+        let index = render_site_index(&[older.clone(), newer.clone(), hidden.clone()]);
+        assert!(!index.contains(&site_page_name(&hidden)));
+        let newer_at = index.find(&site_page_name(&newer)).unwrap();
+        let older_at = index.find(&site_page_name(&older)).unwrap();
+        assert!(newer_at < older_at);
+        assert!(index.contains("<p class=\"summary\">First post</p>"));
+        assert!(index.contains("<li>#nostr</li>"));
+        assert!(index.contains(SITE_STYLESHEET));
+
+        let page = render_site_page(&newer, HtmlExportOptions { include_stats: true });
+        assert!(page.contains("href=\"index.html\""));
+        assert!(!page.contains("<style>"));
+    }
+}
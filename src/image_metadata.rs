@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use image::{DynamicImage, ImageFormat};
+use std::io::Cursor;
+
+/// JPEG quality used when re-encoding a stripped photo
+const JPEG_QUALITY: u8 = 90;
+
+/// Read the EXIF orientation tag (1-8), if the image has EXIF at all
+fn exif_orientation(bytes: &[u8]) -> Option<Option<u32>> {
+    let exif = exif::Reader::new().read_from_container(&mut Cursor::new(bytes)).ok()?;
+    let orientation = exif
+        .get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0));
+    Some(orientation)
+}
+
+/// Bake the EXIF orientation into the pixels, since the tag itself is removed
+fn apply_orientation(image: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+/// Remove EXIF and other metadata (GPS position, camera model, ...) from an
+/// image by decoding and re-encoding it.
+///
+/// Returns `None` when there is nothing to strip: the image has no EXIF, or
+/// its format is one we don't re-encode (GIF animations, SVG).
+pub fn strip_metadata(bytes: &[u8], format: ImageFormat) -> Result<Option<Vec<u8>>> {
+    if !matches!(format, ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP) {
+        return Ok(None);
+    }
+
+    let Some(orientation) = exif_orientation(bytes) else {
+        return Ok(None);
+    };
+
+    let image = image::load_from_memory_with_format(bytes, format).context("Failed to decode image")?;
+    let image = apply_orientation(image, orientation.unwrap_or(1));
+
+    encode(&image, format, JPEG_QUALITY).map(Some)
+}
+
+/// Shrink an image so neither side is longer than `max_dimension` and
+/// re-encode it in its own format, JPEGs at `jpeg_quality`.
+///
+/// Returns `None` when the image is already small enough, its format is one
+/// we don't re-encode (GIF animations, SVG), or the result isn't smaller.
+/// Re-encoding drops metadata, so the EXIF orientation is applied first.
+pub fn downscale(bytes: &[u8], format: ImageFormat, max_dimension: u32, jpeg_quality: u8) -> Result<Option<Vec<u8>>> {
+    if !matches!(format, ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP) {
+        return Ok(None);
+    }
+
+    let image = image::load_from_memory_with_format(bytes, format).context("Failed to decode image")?;
+    if image.width().max(image.height()) <= max_dimension {
+        return Ok(None);
+    }
+
+    let orientation = exif_orientation(bytes).flatten().unwrap_or(1);
+    let image = apply_orientation(image, orientation).resize(
+        max_dimension,
+        max_dimension,
+        image::imageops::FilterType::Lanczos3,
+    );
+
+    let output = encode(&image, format, jpeg_quality)?;
+    Ok((output.len() < bytes.len()).then_some(output))
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat, jpeg_quality: u8) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    match format {
+        ImageFormat::Jpeg => {
+            let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut output, jpeg_quality);
+            // JPEG has no alpha channel
+            image.to_rgb8().write_with_encoder(encoder).context("Failed to encode JPEG")?;
+        }
+        _ => {
+            image
+                .write_to(&mut Cursor::new(&mut output), format)
+                .context("Failed to encode image")?;
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A JPEG with an APP1 segment holding a minimal EXIF block (orientation = 6)
+    fn jpeg_with_exif() -> Vec<u8> {
+        let mut jpeg = Vec::new();
+        DynamicImage::new_rgb8(4, 2)
+            .write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg)
+            .unwrap();
+
+        let mut payload = b"Exif\0\0".to_vec();
+        payload.extend_from_slice(&[0x49, 0x49, 0x2A, 0x00, 0x08, 0x00, 0x00, 0x00]); // little-endian TIFF header
+        payload.extend_from_slice(&[0x01, 0x00]); // one IFD entry
+        payload.extend_from_slice(&[0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00]); // orientation
+        payload.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // no next IFD
+
+        let length = (payload.len() + 2) as u16;
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend_from_slice(&length.to_be_bytes());
+        segment.extend_from_slice(&payload);
+
+        // Insert right after the SOI marker
+        jpeg.splice(2..2, segment);
+        jpeg
+    }
+
+    #[test]
+    fn test_exif_removed_and_orientation_applied() {
+        let original = jpeg_with_exif();
+        assert_eq!(exif_orientation(&original), Some(Some(6)));
+
+        let stripped = strip_metadata(&original, ImageFormat::Jpeg).unwrap().unwrap();
+        assert_eq!(exif_orientation(&stripped), None);
+
+        // Rotated 90°, so width and height swap
+        let image = image::load_from_memory(&stripped).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 4));
+    }
+
+    #[test]
+    fn test_images_without_metadata_untouched() {
+        let mut png = Vec::new();
+        DynamicImage::new_rgb8(2, 2)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        assert!(strip_metadata(&png, ImageFormat::Png).unwrap().is_none());
+        assert!(strip_metadata(b"GIF89a", ImageFormat::Gif).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_downscale_large_images_only() {
+        let mut jpeg = Vec::new();
+        let noisy = image::RgbImage::from_fn(400, 200, |x, y| image::Rgb([(x * 7 % 256) as u8, (y * 13 % 256) as u8, ((x + y) % 256) as u8]));
+        DynamicImage::ImageRgb8(noisy)
+            .write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg)
+            .unwrap();
+
+        let resized = downscale(&jpeg, ImageFormat::Jpeg, 100, 80).unwrap().unwrap();
+        assert!(resized.len() < jpeg.len());
+        let image = image::load_from_memory(&resized).unwrap();
+        assert_eq!((image.width(), image.height()), (100, 50));
+
+        assert!(downscale(&jpeg, ImageFormat::Jpeg, 400, 80).unwrap().is_none());
+        assert!(downscale(b"GIF89a", ImageFormat::Gif, 100, 80).unwrap().is_none());
+    }
+}
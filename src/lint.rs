@@ -0,0 +1,217 @@
+/// A line that is long enough to render badly in some Nostr clients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LongLine {
+    /// 1-based line number
+    pub line_number: usize,
+    /// Length in characters
+    pub length: usize,
+    /// Whether the line is prose that can be split at spaces
+    pub can_wrap: bool,
+}
+
+/// The fence character and run length if the line opens or closes a code block
+fn fence(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.chars().take_while(|c| *c == marker).count();
+    (length >= 3).then_some((marker, length))
+}
+
+/// Whether `byte_index` is inside a fenced code block or an inline code span
+pub(crate) fn in_code(content: &str, byte_index: usize) -> bool {
+    let before = &content[..byte_index];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    let mut open_fence: Option<(char, usize)> = None;
+    for line in before[..line_start].lines() {
+        match (open_fence, fence(line)) {
+            (None, Some(opening)) => open_fence = Some(opening),
+            (Some((marker, length)), Some((closing, closing_length))) if closing == marker && closing_length >= length => {
+                open_fence = None;
+            }
+            _ => {}
+        }
+    }
+
+    // An odd number of backticks earlier on the line leaves a code span open
+    open_fence.is_some() || fence(&before[line_start..]).is_some() || before[line_start..].matches('`').count() % 2 == 1
+}
+
+/// Split a line into the indentation, blockquote and list markers it starts
+/// with and the text after them. Also returns what continuation lines need in
+/// front of them to stay in the same quote or list item.
+fn split_prefix(line: &str) -> (&str, String, &str) {
+    let mut end = line.len() - line.trim_start().len();
+    let mut continuation = line[..end].to_string();
+
+    loop {
+        let rest = &line[end..];
+        if let Some(after) = rest.strip_prefix('>') {
+            let marker_len = if after.starts_with(' ') { 2 } else { 1 };
+            continuation.push_str(&rest[..marker_len]);
+            end += marker_len;
+            continue;
+        }
+
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        let marker_len = if rest.starts_with("- ") || rest.starts_with("* ") || rest.starts_with("+ ") {
+            1
+        } else if digits > 0 && (rest[digits..].starts_with(". ") || rest[digits..].starts_with(") ")) {
+            digits + 1
+        } else {
+            break;
+        };
+        let spaces = rest[marker_len..].len() - rest[marker_len..].trim_start().len();
+        continuation.push_str(&" ".repeat(marker_len + spaces));
+        end += marker_len + spaces;
+        break;
+    }
+
+    (&line[..end], continuation, &line[end..])
+}
+
+/// Headings and table rows must stay on one line to keep their meaning
+fn is_wrappable(line: &str) -> bool {
+    let (_, _, text) = split_prefix(line);
+    !text.starts_with('#') && !text.starts_with('|') && text.split_whitespace().nth(1).is_some()
+}
+
+/// Find lines longer than `max_length` characters, ignoring fenced code blocks
+pub fn find_long_lines(content: &str, max_length: usize) -> Vec<LongLine> {
+    // Only a fence of the same character, at least as long, closes a block
+    let mut open_fence: Option<(char, usize)> = None;
+    let mut long_lines = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        match (open_fence, fence(line)) {
+            (None, Some(opening)) => {
+                open_fence = Some(opening);
+                continue;
+            }
+            (Some((marker, length)), Some((closing, closing_length))) if closing == marker && closing_length >= length => {
+                open_fence = None;
+                continue;
+            }
+            (Some(_), _) => continue,
+            (None, None) => {}
+        }
+
+        let length = line.chars().count();
+        if length > max_length {
+            long_lines.push(LongLine {
+                line_number: index + 1,
+                length,
+                can_wrap: is_wrappable(line),
+            });
+        }
+    }
+
+    long_lines
+}
+
+/// Split a line of prose at spaces so no piece is longer than `width`,
+/// unless a single word is. Markdown treats the resulting newlines as
+/// soft breaks, so the rendered paragraph is unchanged. Continuation lines
+/// keep the line's indentation and stay inside its quote or list item, and a
+/// trailing hard break stays at the end.
+pub fn wrap_prose(line: &str, width: usize) -> String {
+    let (prefix, continuation, text) = split_prefix(line);
+    let hard_break = text.ends_with("  ");
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = prefix.to_string();
+    let mut current_len = prefix.chars().count();
+    let mut has_words = false;
+
+    for word in text.split_whitespace() {
+        let word_len = word.chars().count();
+        if has_words && current_len + 1 + word_len > width {
+            lines.push(std::mem::replace(&mut current, continuation.clone()));
+            current_len = continuation.chars().count();
+            has_words = false;
+        }
+        if has_words {
+            current.push(' ');
+            current_len += 1;
+        }
+        current.push_str(word);
+        current_len += word_len;
+        has_words = true;
+    }
+    if has_words {
+        lines.push(current);
+    }
+
+    let mut wrapped = lines.join("\n");
+    if hard_break {
+        wrapped.push_str("  ");
+    }
+    wrapped
+}
+
+/// Wrap a single line of `content` (1-based) in place
+pub fn wrap_line(content: &mut String, line_number: usize, width: usize) {
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let Some(line) = lines.get_mut(line_number.wrapping_sub(1)) else {
+        return;
+    };
+    if !is_wrappable(line) {
+        return;
+    }
+
+    *line = wrap_prose(line, width);
+
+    let had_trailing_newline = content.ends_with('\n');
+    *content = lines.join("\n");
+    if had_trailing_newline {
+        content.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_fences_are_exempt() {
+        let long = "word ".repeat(30);
+        let content = format!("{long}\n```\n{long}\n```\nshort\n# {long}");
+
+        let found = find_long_lines(&content, 80);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].line_number, 1);
+        assert!(found[0].can_wrap);
+        assert_eq!(found[1].line_number, 6);
+        assert!(!found[1].can_wrap);
+    }
+
+    #[test]
+    fn test_only_matching_fence_closes_block() {
+        let long = "word ".repeat(30);
+        let content = format!("~~~\n```\n{long}\n~~~\n{long}\n````\n{long}\n```\n{long}\n````");
+
+        let found = find_long_lines(&content, 80);
+        assert_eq!(found.iter().map(|l| l.line_number).collect::<Vec<_>>(), vec![5]);
+    }
+
+    #[test]
+    fn test_wrap_keeps_markers_and_hard_breaks() {
+        let mut content = "  - one two three four five six  \n> quoted one two three four\n".to_string();
+
+        wrap_line(&mut content, 1, 18);
+        wrap_line(&mut content, 3, 18);
+        assert_eq!(
+            content,
+            "  - one two three\n    four five six  \n> quoted one two\n> three four\n"
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_keeps_words_intact() {
+        let url = format!("https://example.com/{}", "a".repeat(50));
+        let mut content = format!("intro\nsee {url} for the full details\n");
+
+        wrap_line(&mut content, 2, 20);
+        assert_eq!(content, format!("intro\nsee\n{url}\nfor the full details\n"));
+    }
+}
@@ -1,11 +1,26 @@
+mod activity;
 mod app;
+mod app_settings;
 mod blossom_client;
 mod components;
+mod credentials_file;
+mod find_replace;
+mod fonts;
+mod html_export;
+mod image_metadata;
+mod lint;
+mod markdown_format;
 mod nostr_client;
 mod post;
+mod preview;
+mod private_post;
+mod qr;
 mod relay_settings;
+mod search;
 mod storage;
+mod table_import;
 mod theme;
+mod writing_stats;
 
 use app::BlogsterApp;
 use tracing_subscriber;
@@ -18,7 +33,10 @@ fn main() -> eframe::Result {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1200.0, 800.0])
             .with_min_inner_size([800.0, 600.0])
-            .with_title("Blogster - Nostr Blog Publisher"),
+            .with_title("Blogster - Nostr Blog Publisher")
+            // Needed for the window opacity preference; ignored where the
+            // platform can't composite transparent windows
+            .with_transparent(true),
         ..Default::default()
     };
 
@@ -26,18 +44,9 @@ fn main() -> eframe::Result {
         "Blogster",
         options,
         Box::new(|cc| {
-            // Setup custom fonts if needed
-            setup_custom_fonts(&cc.egui_ctx);
+            // Lets `egui::Image` load remote images (featured image previews)
+            egui_extras::install_image_loaders(&cc.egui_ctx);
             Ok(Box::new(BlogsterApp::new(cc)))
         }),
     )
 }
-
-fn setup_custom_fonts(ctx: &egui::Context) {
-    let fonts = egui::FontDefinitions::default();
-    
-    // Add custom fonts here if needed
-    // For now, we'll use the default fonts
-    
-    ctx.set_fonts(fonts);
-}
@@ -0,0 +1,235 @@
+use crate::lint::in_code;
+use std::ops::Range;
+
+/// Formatting the editor toolbar applies to the selected text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownFormat {
+    Bold,
+    Italic,
+    Heading,
+    Link,
+    Code,
+    BulletList,
+}
+
+impl MarkdownFormat {
+    pub const ALL: [MarkdownFormat; 6] = [
+        MarkdownFormat::Bold,
+        MarkdownFormat::Italic,
+        MarkdownFormat::Heading,
+        MarkdownFormat::Link,
+        MarkdownFormat::Code,
+        MarkdownFormat::BulletList,
+    ];
+
+    /// Toolbar button text
+    pub fn label(&self) -> &'static str {
+        match self {
+            MarkdownFormat::Bold => "B",
+            MarkdownFormat::Italic => "I",
+            MarkdownFormat::Heading => "H",
+            MarkdownFormat::Link => "🔗",
+            MarkdownFormat::Code => "</>",
+            MarkdownFormat::BulletList => "•",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarkdownFormat::Bold => "Bold",
+            MarkdownFormat::Italic => "Italic",
+            MarkdownFormat::Heading => "Heading",
+            MarkdownFormat::Link => "Link",
+            MarkdownFormat::Code => "Code",
+            MarkdownFormat::BulletList => "Bullet list",
+        }
+    }
+}
+
+/// Text inserted when nothing is selected, left selected so typing replaces it
+const BOLD_PLACEHOLDER: &str = "bold text";
+const ITALIC_PLACEHOLDER: &str = "italic text";
+const HEADING_PLACEHOLDER: &str = "Heading";
+const LINK_TEXT_PLACEHOLDER: &str = "link text";
+const LINK_URL_PLACEHOLDER: &str = "https://";
+const CODE_PLACEHOLDER: &str = "code";
+const LIST_PLACEHOLDER: &str = "List item";
+
+fn byte_index(content: &str, char_index: usize) -> usize {
+    content
+        .char_indices()
+        .nth(char_index)
+        .map(|(i, _)| i)
+        .unwrap_or(content.len())
+}
+
+fn char_count(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Apply `format` to the characters in `selection` (character indices, as
+/// egui's cursors use). With an empty selection a placeholder is inserted.
+/// Returns the character range to select afterwards.
+pub fn apply_format(content: &mut String, selection: Range<usize>, format: MarkdownFormat) -> Range<usize> {
+    let total = char_count(content);
+    let selection = selection.start.min(total)..selection.end.min(total);
+
+    match format {
+        MarkdownFormat::Bold => wrap(content, selection, "**", "**", BOLD_PLACEHOLDER),
+        MarkdownFormat::Italic => wrap(content, selection, "*", "*", ITALIC_PLACEHOLDER),
+        MarkdownFormat::Code => {
+            let selected = &content[byte_index(content, selection.start)..byte_index(content, selection.end)];
+            if selected.contains('\n') {
+                wrap(content, selection, "```\n", "\n```", CODE_PLACEHOLDER)
+            } else {
+                wrap(content, selection, "`", "`", CODE_PLACEHOLDER)
+            }
+        }
+        MarkdownFormat::Link => link(content, selection),
+        MarkdownFormat::Heading => prefix_lines(content, selection, "## ", HEADING_PLACEHOLDER),
+        MarkdownFormat::BulletList => prefix_lines(content, selection, "- ", LIST_PLACEHOLDER),
+    }
+}
+
+/// Surround the selection with `before` and `after`, keeping it selected
+fn wrap(content: &mut String, selection: Range<usize>, before: &str, after: &str, placeholder: &str) -> Range<usize> {
+    let start = byte_index(content, selection.start);
+    let end = byte_index(content, selection.end);
+    let inner = if start == end { placeholder.to_string() } else { content[start..end].to_string() };
+
+    content.replace_range(start..end, &format!("{}{}{}", before, inner, after));
+
+    let inner_start = selection.start + char_count(before);
+    inner_start..inner_start + char_count(&inner)
+}
+
+/// Turn the selection into a link. Selected text becomes the link text and
+/// the URL is selected for typing, unless the selection already is a URL.
+fn link(content: &mut String, selection: Range<usize>) -> Range<usize> {
+    let start = byte_index(content, selection.start);
+    let end = byte_index(content, selection.end);
+    let selected = content[start..end].to_string();
+
+    if selected.starts_with("http://") || selected.starts_with("https://") {
+        content.replace_range(start..end, &format!("[{}]({})", LINK_TEXT_PLACEHOLDER, selected));
+        let text_start = selection.start + 1;
+        return text_start..text_start + char_count(LINK_TEXT_PLACEHOLDER);
+    }
+
+    let text = if selected.is_empty() { LINK_TEXT_PLACEHOLDER } else { selected.as_str() };
+    content.replace_range(start..end, &format!("[{}]({})", text, LINK_URL_PLACEHOLDER));
+    let url_start = selection.start + char_count(text) + 3;
+    url_start..url_start + char_count(LINK_URL_PLACEHOLDER)
+}
+
+/// Put `prefix` in front of every line the selection touches. Existing
+/// heading markers are replaced and lines that already have the prefix are
+/// left alone, so applying twice changes nothing.
+fn prefix_lines(content: &mut String, selection: Range<usize>, prefix: &str, placeholder: &str) -> Range<usize> {
+    let start = byte_index(content, selection.start);
+    let end = byte_index(content, selection.end);
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[end..].find('\n').map_or(content.len(), |i| end + i);
+    let lines = &content[line_start..line_end];
+    let first_char = char_count(&content[..line_start]);
+
+    if lines.trim().is_empty() {
+        let replacement = format!("{}{}", prefix, placeholder);
+        content.replace_range(line_start..line_end, &replacement);
+        let placeholder_start = first_char + char_count(prefix);
+        return placeholder_start..placeholder_start + char_count(placeholder);
+    }
+
+    let is_heading = prefix.starts_with('#');
+    let replacement = lines
+        .split('\n')
+        .map(|line| {
+            if line.trim().is_empty() || line.starts_with(prefix) {
+                return line.to_string();
+            }
+            let text = if is_heading { line.trim_start_matches('#').trim_start() } else { line };
+            format!("{}{}", prefix, text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    content.replace_range(line_start..line_end, &replacement);
+    first_char..first_char + char_count(&replacement)
+}
+
+/// Replace `placeholder` if it ends right at `cursor` (a character index),
+/// i.e. it was just typed, unless it's written inside code. Returns where the
+/// cursor goes after the replacement.
+pub fn expand_placeholder_at(content: &mut String, cursor: usize, placeholder: &str, replacement: &str) -> Option<usize> {
+    let end = byte_index(content, cursor);
+    let start = end.checked_sub(placeholder.len())?;
+    if content.get(start..end) != Some(placeholder) || in_code(content, start) {
+        return None;
+    }
+
+    content.replace_range(start..end, replacement);
+    Some(cursor - char_count(placeholder) + char_count(replacement))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(content: &str, selection: Range<usize>, format: MarkdownFormat) -> (String, String) {
+        let mut content = content.to_string();
+        let selected = apply_format(&mut content, selection, format);
+        let selected_text = content.chars().skip(selected.start).take(selected.len()).collect();
+        (content, selected_text)
+    }
+
+    #[test]
+    fn test_wrap_selection_and_placeholder() {
+        assert_eq!(format("say hello", 4..9, MarkdownFormat::Bold), ("say **hello**".to_string(), "hello".to_string()));
+        assert_eq!(format("café ok", 5..7, MarkdownFormat::Italic), ("café *ok*".to_string(), "ok".to_string()));
+        assert_eq!(format("a", 1..1, MarkdownFormat::Bold), ("a**bold text**".to_string(), "bold text".to_string()));
+        assert_eq!(format("x\ny", 0..3, MarkdownFormat::Code), ("```\nx\ny\n```".to_string(), "x\ny".to_string()));
+    }
+
+    #[test]
+    fn test_links_select_what_is_missing() {
+        assert_eq!(format("see docs", 4..8, MarkdownFormat::Link), ("see [docs](https://)".to_string(), "https://".to_string()));
+        assert_eq!(
+            format("https://example.com", 0..19, MarkdownFormat::Link),
+            ("[link text](https://example.com)".to_string(), "link text".to_string())
+        );
+    }
+
+    #[test]
+    fn test_prefix_whole_lines() {
+        assert_eq!(format("one\ntwo\n\nthree", 1..5, MarkdownFormat::BulletList).0, "- one\n- two\n\nthree");
+        assert_eq!(format("# Title", 2..2, MarkdownFormat::Heading).0, "## Title");
+        assert_eq!(format("## Title", 0..0, MarkdownFormat::Heading).0, "## Title");
+        assert_eq!(format("first\n\n", 7..7, MarkdownFormat::Heading), ("first\n\n## Heading".to_string(), "Heading".to_string()));
+    }
+
+    #[test]
+    fn test_placeholder_expands_only_where_typed() {
+        let mut content = "Written {{date}}".to_string();
+        assert_eq!(expand_placeholder_at(&mut content, 16, "{{date}}", "2024-05-01"), Some(18));
+        assert_eq!(content, "Written 2024-05-01");
+
+        // One typed elsewhere later on is left alone
+        let mut content = "{{date}} and more".to_string();
+        assert_eq!(expand_placeholder_at(&mut content, 17, "{{date}}", "2024-05-01"), None);
+        assert_eq!(content, "{{date}} and more");
+    }
+
+    #[test]
+    fn test_placeholder_in_code_is_kept() {
+        let mut content = "Use `{{date}}".to_string();
+        assert_eq!(expand_placeholder_at(&mut content, 13, "{{date}}", "2024-05-01"), None);
+
+        let mut content = "```\ntemplate: {{date}}".to_string();
+        assert_eq!(expand_placeholder_at(&mut content, 22, "{{date}}", "2024-05-01"), None);
+
+        let mut content = "```\ncode\n```\nPosted {{date}}".to_string();
+        let cursor = content.chars().count();
+        assert!(expand_placeholder_at(&mut content, cursor, "{{date}}", "2024-05-01").is_some());
+        assert_eq!(content, "```\ncode\n```\nPosted 2024-05-01");
+    }
+}
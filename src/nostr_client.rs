@@ -1,12 +1,152 @@
 use crate::post::{BlogPost, NostrCredentials};
-use crate::relay_settings::RelaySettings;
+use crate::relay_settings::{CustomRelay, RelaySettings};
 use anyhow::{Context, Result};
 use nostr_sdk::prelude::*;
+use std::collections::BTreeMap;
 use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// Name reported in the `client` tag of published events
+pub const CLIENT_NAME: &str = "Blogster";
+
+/// Relays asked for a user's NIP-65 relay list; they index relay lists widely
+const RELAY_LIST_BOOTSTRAP_RELAYS: &[&str] = &[
+    "wss://purplepag.es",
+    "wss://relay.damus.io",
+    "wss://nos.lol",
+];
+
+/// Longest a relay connection test may take
+const RELAY_TEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait for a NIP-46 remote signer to answer
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long to wait for a NIP-05 server to answer
+const NIP05_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// NIP-37 draft wrapper event kind
+pub const DRAFT_KIND: u16 = 31234;
+
+/// How events are signed for the active identity
+#[derive(Debug, Clone, PartialEq)]
+pub enum SigningMethod {
+    /// Blogster holds the private key
+    LocalKey,
+    /// Signing is delegated to a NIP-46 remote signer; the private key never
+    /// leaves it
+    Nip46 { bunker_uri: String },
+}
+
+impl SigningMethod {
+    pub fn for_credentials(credentials: &NostrCredentials) -> Self {
+        match &credentials.bunker_uri {
+            Some(bunker_uri) => Self::Nip46 { bunker_uri: bunker_uri.clone() },
+            None => Self::LocalKey,
+        }
+    }
+}
+
+/// A client signing for an identity, ready to replace the active one
+pub struct ConnectedSigner {
+    client: Client,
+    credentials: NostrCredentials,
+    signing_method: SigningMethod,
+}
+
+impl ConnectedSigner {
+    /// The credentials signed with, including what the remote signer reported
+    pub fn credentials(&self) -> &NostrCredentials {
+        &self.credentials
+    }
+}
 
 pub struct NostrClient {
     client: Client,
     credentials: Option<NostrCredentials>,
+    signing_method: SigningMethod,
+    include_client_tag: bool,
+    publish_timeout: Duration,
+}
+
+/// Returned when the profile to update isn't on any relay, so publishing
+/// would replace whatever other clients know with just Blogster's fields
+#[derive(Debug)]
+pub struct ProfileNotFound;
+
+impl std::fmt::Display for ProfileNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No profile found on your relays")
+    }
+}
+
+impl std::error::Error for ProfileNotFound {}
+
+/// Outcome of broadcasting an event to a set of relays
+#[derive(Debug, Clone)]
+pub struct PublishReport {
+    pub event_id: EventId,
+    pub successful_relays: Vec<String>,
+    /// Relays that rejected the event, with the reason they gave
+    pub failed_relays: Vec<(String, String)>,
+    /// Relays that didn't answer before the publish timeout; worth retrying
+    pub timed_out_relays: Vec<String>,
+    /// Relays the post opted out of, so nothing was sent to them
+    pub skipped_relays: Vec<String>,
+    /// The `published_at` an article was signed with
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl PublishReport {
+    /// Every relay the event was sent to, by URL, with the reason it failed
+    /// if it did. Skipped relays aren't included.
+    pub fn relay_results(&self) -> BTreeMap<String, Result<(), String>> {
+        let mut results = BTreeMap::new();
+        for relay in &self.successful_relays {
+            results.insert(relay.clone(), Ok(()));
+        }
+        for (relay, reason) in &self.failed_relays {
+            results.insert(relay.clone(), Err(reason.clone()));
+        }
+        for relay in &self.timed_out_relays {
+            results.insert(relay.clone(), Err("Didn't answer in time".to_string()));
+        }
+        results
+    }
+}
+
+/// Connection state of a relay in the pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelayConnection {
+    Connected,
+    /// Connecting or waiting to retry
+    Connecting,
+    Disconnected,
+    /// Not in the pool yet; nothing has been sent to it this session
+    NotAdded,
+}
+
+impl RelayConnection {
+    fn from_status(status: RelayStatus) -> Self {
+        match status {
+            RelayStatus::Connected => Self::Connected,
+            RelayStatus::Initialized | RelayStatus::Pending | RelayStatus::Connecting => Self::Connecting,
+            RelayStatus::Disconnected | RelayStatus::Terminated => Self::Disconnected,
+        }
+    }
+}
+
+/// How a single relay answered a test publish
+#[derive(Debug, Clone)]
+pub struct RelayTestReport {
+    pub relay_url: String,
+    pub event_id: String,
+    /// Size of the serialized event in bytes
+    pub event_size: usize,
+    pub accepted: bool,
+    /// The relay's OK message, error or timeout
+    pub response: String,
+    pub elapsed: Duration,
 }
 
 impl NostrClient {
@@ -15,28 +155,91 @@ impl NostrClient {
         Self {
             client,
             credentials: None,
+            signing_method: SigningMethod::LocalKey,
+            include_client_tag: true,
+            publish_timeout: Duration::from_secs(30),
         }
     }
 
-    pub fn set_credentials(&mut self, credentials: NostrCredentials) -> Result<()> {
-        let secret_key = if credentials.private_key.starts_with("nsec") {
-            SecretKey::from_bech32(&credentials.private_key)
-                .context("Invalid nsec format")?
-        } else {
-            SecretKey::from_hex(&credentials.private_key)
-                .context("Invalid private key format")?
-        };
-        
-        let keys = Keys::new(secret_key);
-        
-        self.client = Client::new(&keys);
-        
-        tracing::info!("Set Nostr credentials for pubkey: {}", credentials.public_key);
-        self.credentials = Some(credentials);
-        
+    /// Set the overall time to wait for relays to acknowledge a publish
+    pub fn set_publish_timeout(&mut self, timeout: Duration) {
+        self.publish_timeout = timeout;
+    }
+
+    /// Enable or disable the `client` attribution tag on published events
+    pub fn set_include_client_tag(&mut self, enabled: bool) {
+        self.include_client_tag = enabled;
+    }
+
+    /// Sign with `credentials` from now on. For a NIP-46 identity this
+    /// connects to the remote signer and fills in the public key it reports.
+    pub async fn set_credentials(&mut self, credentials: NostrCredentials) -> Result<()> {
+        let signer = Self::connect_signer(credentials).await?;
+        self.use_signer(signer);
         Ok(())
     }
 
+    /// Get a signer ready for `credentials` without touching the client.
+    /// Reaching a NIP-46 remote signer can take up to a minute, so callers
+    /// connect first and only lock the client for `use_signer`.
+    pub async fn connect_signer(mut credentials: NostrCredentials) -> Result<ConnectedSigner> {
+        let signing_method = SigningMethod::for_credentials(&credentials);
+
+        let client = match &signing_method {
+            SigningMethod::LocalKey => {
+                let secret_key = if credentials.private_key.starts_with("nsec") {
+                    SecretKey::from_bech32(&credentials.private_key)
+                        .context("Invalid nsec format")?
+                } else {
+                    SecretKey::from_hex(&credentials.private_key)
+                        .context("Invalid private key format")?
+                };
+                
+                let keys = Keys::new(secret_key);
+                
+                Client::new(&keys)
+            }
+            SigningMethod::Nip46 { bunker_uri } => {
+                let uri = NostrConnectURI::parse(bunker_uri)
+                    .context("Invalid bunker URI")?;
+                // The signer approves this app by its key, so keep using the account's
+                credentials.ensure_bunker_app_key();
+                let app_key = credentials.bunker_app_key.as_deref().unwrap_or_default();
+                let app_keys = Keys::parse(app_key).context("Invalid remote signer app key")?;
+                let signer = Nip46Signer::new(uri, app_keys, REMOTE_SIGNER_TIMEOUT, None)
+                    .await
+                    .context("Failed to connect to the remote signer")?;
+                let signer = NostrSigner::nip46(signer);
+                let public_key = signer.public_key().await
+                    .context("The remote signer didn't share its public key")?;
+
+                credentials.private_key.clear();
+                credentials.public_key = public_key.to_hex();
+                Client::new(signer)
+            }
+        };
+
+        Ok(ConnectedSigner { client, credentials, signing_method })
+    }
+
+    /// Sign with a signer from `connect_signer` from now on
+    pub fn use_signer(&mut self, signer: ConnectedSigner) {
+        tracing::info!("Set Nostr credentials for pubkey: {}", signer.credentials.public_key);
+        self.client = signer.client;
+        self.signing_method = signer.signing_method;
+        self.credentials = Some(signer.credentials);
+    }
+
+    /// Forget the active identity. The client falls back to a throwaway key
+    /// so nothing can be signed as the old identity.
+    pub fn clear_credentials(&mut self) {
+        self.client = Client::new(&Keys::generate());
+        self.signing_method = SigningMethod::LocalKey;
+        if let Some(credentials) = self.credentials.take() {
+            tracing::info!("Cleared Nostr credentials for pubkey: {}", credentials.public_key);
+        }
+    }
+
     pub fn get_credentials(&self) -> Option<&NostrCredentials> {
         self.credentials.as_ref()
     }
@@ -45,17 +248,62 @@ impl NostrClient {
         self.credentials.is_some()
     }
 
+    pub fn signing_method(&self) -> &SigningMethod {
+        &self.signing_method
+    }
+
+    /// The active identity's public key in npub format
+    pub fn npub(&self) -> Option<String> {
+        let credentials = self.credentials.as_ref()?;
+        PublicKey::from_hex(&credentials.public_key).ok()?.to_bech32().ok()
+    }
+
+    /// The active identity's private key in nsec format. Handle with care.
+    /// `None` when a remote signer holds the key.
+    pub fn nsec(&self) -> Option<String> {
+        let credentials = self.credentials.as_ref()?;
+        let secret_key = if credentials.private_key.starts_with("nsec") {
+            SecretKey::from_bech32(&credentials.private_key).ok()?
+        } else {
+            SecretKey::from_hex(&credentials.private_key).ok()?
+        };
+        secret_key.to_bech32().ok()
+    }
+
+    /// Make sure every active relay is in the pool and connected.
+    ///
+    /// Connections are kept between publishes, so relays that are already
+    /// connected are skipped and we only wait when something new had to connect.
     pub async fn connect_to_relays(&self, relay_settings: &RelaySettings) -> Result<()> {
-        let relays = relay_settings.get_active_relays();
+        self.connect_to(relay_settings.get_active_relays()).await
+    }
+
+    /// Connect to the given relays, skipping any that are already connected
+    async fn connect_to(&self, relay_urls: Vec<String>) -> Result<()> {
+        let pool = self.client.relays().await;
+        let mut connecting = 0;
         
-        for relay_url in relays {
+        for relay_url in relay_urls {
+            let existing = Url::parse(&relay_url).ok().and_then(|url| pool.get(&url).cloned());
+            if let Some(relay) = existing {
+                if relay.is_connected().await {
+                    continue;
+                }
+            }
+            
             if let Err(e) = self.client.add_relay(&relay_url).await {
                 tracing::warn!("Failed to add relay {}: {}", relay_url, e);
             } else {
-                tracing::info!("Added relay: {}", relay_url);
+                tracing::info!("Connecting to relay: {}", relay_url);
+                connecting += 1;
             }
         }
 
+        if connecting == 0 {
+            tracing::debug!("Reusing existing relay connections");
+            return Ok(());
+        }
+
         self.client.connect().await;
         
         // Wait a bit for connections to establish
@@ -64,7 +312,21 @@ impl NostrClient {
         Ok(())
     }
 
-    pub async fn publish_long_form_post(&self, post: &BlogPost, relay_settings: &RelaySettings) -> Result<(EventId, Vec<String>)> {
+    /// Disconnect from every relay and empty the relay pool.
+    ///
+    /// Call this after the identity or relay list changes so the next publish
+    /// connects fresh instead of reusing stale connections.
+    pub async fn reset_connections(&self) -> Result<()> {
+        self.client.disconnect().await
+            .context("Failed to disconnect from relays")?;
+        self.client.remove_all_relays().await
+            .context("Failed to clear relay pool")?;
+        
+        tracing::info!("Reset Nostr relay connections");
+        Ok(())
+    }
+
+    pub async fn publish_long_form_post(&self, post: &BlogPost, relay_settings: &RelaySettings) -> Result<PublishReport> {
         if self.credentials.is_none() {
             return Err(anyhow::anyhow!("No Nostr credentials configured"));
         }
@@ -73,71 +335,168 @@ impl NostrClient {
             return Err(anyhow::anyhow!("Post is not ready to publish (missing title or content)"));
         }
 
-        // Create long-form content event (NIP-23)
-        let mut tags = vec![
-            Tag::title(&post.title),
-        ];
-
-        // Add summary if available
-        if let Some(summary) = &post.summary {
-            tags.push(Tag::custom(TagKind::Custom("summary".into()), vec![summary.clone()]));
+        let relays = post_relays(post, relay_settings)?;
+        if !post.excluded_relays.is_empty() {
+            tracing::info!("Skipping relays excluded for this post: {:?}", post.excluded_relays);
         }
 
-        // Add hashtags
-        for tag in &post.tags {
-            tags.push(Tag::hashtag(tag));
-        }
+        // Connect to relays before publishing, including the post's one-off relays
+        self.connect_to(relays.clone()).await.context("Failed to connect to relays")?;
+
+        // Settle the publication date once, so the post records what was signed
+        let published_at = post.first_published_at();
+        let mut post = post.clone();
+        post.published_at = Some(published_at);
+        let post = &post;
 
-        // Add image if available
-        if let Some(image_url) = &post.image_url {
-            tags.push(Tag::custom(TagKind::Custom("image".into()), vec![image_url.clone()]));
+        let event = self.sign_article(post).await?;
+
+        tracing::info!("Publishing event: kind={}, tags={:?}", event.kind, event.tags);
+
+        let mut report = self.send_to_relays(event, relays).await;
+        report.skipped_relays = post.excluded_relays.clone();
+        report.published_at = Some(published_at);
+
+        if report.successful_relays.is_empty() {
+            // Say why each relay failed, so a misconfigured relay set can be fixed
+            let reasons: Vec<String> = report
+                .relay_results()
+                .into_iter()
+                .filter_map(|(relay, result)| result.err().map(|reason| format!("{}: {}", relay, reason)))
+                .collect();
+            return Err(anyhow::anyhow!("Failed to publish to any relay\n{}", reasons.join("\n")));
         }
 
-        // Add published_at timestamp
-        tags.push(Tag::custom(
-            TagKind::Custom("published_at".into()),
-            vec![post.created_at.timestamp().to_string()]
-        ));
+        tracing::info!(
+            "Published post '{}' with event ID {} to {} relays",
+            post.title,
+            report.event_id,
+            report.successful_relays.len()
+        );
 
-        // Add identifier for replaceable event (NIP-33)
-        let identifier = format!("blogster-{}", post.id);
-        tags.push(Tag::identifier(&identifier));
+        Ok(report)
+    }
 
-        // Connect to relays before publishing
-        self.connect_to_relays(relay_settings).await?;
+    /// Build and sign the kind 30023 event for a post
+    async fn sign_article(&self, post: &BlogPost) -> Result<Event> {
+        let tags = build_long_form_tags(post, self.include_client_tag);
 
         // Create the event with kind 30023 for parameterized replaceable long-form content
         let kind = Kind::ParameterizedReplaceable(30023);
         let event_builder = EventBuilder::new(kind, &post.content, tags);
-        let event = self.client.sign_event_builder(event_builder).await
-            .context("Failed to sign event")?;
+        self.client.sign_event_builder(event_builder).await
+            .context("Failed to sign event")
+    }
 
-        tracing::info!("Publishing event: kind={}, tags={:?}", event.kind, event.tags);
+    /// Sign a post's article event without sending it anywhere and return
+    /// the event JSON, for broadcasting with another tool
+    pub async fn sign_article_json(&self, post: &BlogPost) -> Result<String> {
+        if self.credentials.is_none() {
+            return Err(anyhow::anyhow!("No Nostr credentials configured"));
+        }
+        if !post.is_ready_to_publish() {
+            return Err(anyhow::anyhow!("Post is not ready to publish (missing title or content)"));
+        }
 
-        // Publish to relays
-        let event_id = event.id;
-        let output = self.client.send_event(event).await
-            .context("Failed to publish event")?;
+        let event = self.sign_article(post).await?;
+        event.verify().context("Signed event failed verification")?;
 
-        // Collect successful relays
-        let successful_relays: Vec<String> = output
-            .success
-            .into_iter()
-            .map(|url| url.to_string())
-            .collect();
+        tracing::info!("Signed event {} for manual broadcast", event.id);
+        Ok(event.as_json())
+    }
 
-        if successful_relays.is_empty() {
-            return Err(anyhow::anyhow!("Failed to publish to any relay"));
+    /// Publish a post to one relay only and report exactly how it answered.
+    ///
+    /// The event is built the same way as a normal publish, so a rejection
+    /// here is what the relay would say to the real thing. It is a real
+    /// publish: an accepted event replaces the article on that relay.
+    pub async fn test_publish_to_relay(&self, post: &BlogPost, relay_url: &str) -> Result<RelayTestReport> {
+        if self.credentials.is_none() {
+            return Err(anyhow::anyhow!("No Nostr credentials configured"));
         }
+        RelaySettings::validate_relay_url(relay_url).map_err(|e| anyhow::anyhow!(e))?;
 
-        tracing::info!(
-            "Published post '{}' with event ID {} to {} relays",
-            post.title,
+        self.connect_to(vec![relay_url.to_string()]).await?;
+        let event = self.sign_article(post).await?;
+        let event_size = event.as_json().len();
+        let event_id = event.id.to_hex();
+
+        let started = std::time::Instant::now();
+        let result = tokio::time::timeout(
+            self.publish_timeout,
+            self.client.send_event_to([relay_url], event),
+        ).await;
+        let elapsed = started.elapsed();
+
+        let (accepted, response) = match result {
+            Ok(Ok(output)) if !output.success.is_empty() => (true, "OK".to_string()),
+            Ok(Ok(output)) => (
+                false,
+                output
+                    .failed
+                    .values()
+                    .flatten()
+                    .next()
+                    .cloned()
+                    .unwrap_or_else(|| "Rejected without a message".to_string()),
+            ),
+            Ok(Err(e)) => (false, e.to_string()),
+            Err(_) => (false, format!("No response within {}s", self.publish_timeout.as_secs())),
+        };
+
+        tracing::info!("Test publish to {}: accepted={}, response={}", relay_url, accepted, response);
+
+        Ok(RelayTestReport {
+            relay_url: relay_url.to_string(),
             event_id,
-            successful_relays.len()
-        );
+            event_size,
+            accepted,
+            response,
+            elapsed,
+        })
+    }
 
-        Ok((event_id, successful_relays))
+    /// Send an event to each relay concurrently, giving up on relays that
+    /// haven't answered within the publish timeout
+    async fn send_to_relays(&self, event: Event, relays: Vec<String>) -> PublishReport {
+        let client = self.client.clone();
+        let event_id = event.id;
+        collect_relay_results(event_id, relays, self.publish_timeout, move |relay_url| {
+            let client = client.clone();
+            let event = event.clone();
+            async move {
+                match client.send_event_to([relay_url.as_str()], event).await {
+                    Ok(output) if output.success.is_empty() => Err(output
+                        .failed
+                        .values()
+                        .flatten()
+                        .next()
+                        .cloned()
+                        .unwrap_or_else(|| "Rejected by relay".to_string())),
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+        })
+        .await
+    }
+
+    /// Fetch a single event referenced by an naddr, nevent, note or hex event id
+    pub async fn fetch_event(&self, reference: &str, relay_settings: &RelaySettings) -> Result<Event> {
+        let filter = parse_event_reference(reference)?;
+
+        self.connect_to_relays(relay_settings).await?;
+
+        let events = self.client
+            .get_events_of(vec![filter], EventSource::relays(Some(Duration::from_secs(10))))
+            .await
+            .context("Failed to fetch event from relays")?;
+
+        // Replaceable articles may come back in several versions; keep the newest
+        events
+            .into_iter()
+            .max_by_key(|event| event.created_at)
+            .ok_or_else(|| anyhow::anyhow!("Event not found on any active relay"))
     }
 
     pub async fn sign_event(&self, event_builder: EventBuilder) -> Result<Event> {
@@ -145,52 +504,376 @@ impl NostrClient {
             .context("Failed to sign event")
     }
 
-    pub async fn update_profile(&self, credentials: &NostrCredentials) -> Result<EventId> {
-        let mut metadata = Metadata::new();
-        
-        if let Some(display_name) = &credentials.display_name {
-            metadata = metadata.display_name(display_name);
-        }
-        
-        if let Some(about) = &credentials.about {
-            metadata = metadata.about(about);
+    /// Update the profile on the relays with the fields set in `credentials`.
+    /// The current profile is fetched first and every other field in it, such
+    /// as `name` or `lud16` set in another client, is kept. Fails with
+    /// `ProfileNotFound` when no relay has a profile, unless `create_if_missing`.
+    pub async fn update_profile(&self, credentials: &NostrCredentials, relay_settings: &RelaySettings, create_if_missing: bool) -> Result<PublishReport> {
+        let current = match self.fetch_profile_event(relay_settings).await? {
+            Some(event) => event.content,
+            None if create_if_missing => "{}".to_string(),
+            None => return Err(ProfileNotFound.into()),
+        };
+        let content = merge_profile(&current, credentials)?;
+
+        let event_builder = EventBuilder::new(Kind::Metadata, content, []);
+        let event = self.client.sign_event_builder(event_builder).await
+            .context("Failed to sign metadata event")?;
+
+        let report = self.send_to_relays(event, relay_settings.get_write_relays()).await;
+        if report.successful_relays.is_empty() {
+            return Err(anyhow::anyhow!("Failed to publish profile to any relay"));
         }
-        
-        if let Some(picture) = &credentials.picture {
-            if let Ok(picture_url) = picture.parse::<nostr_sdk::Url>() {
-                metadata = metadata.picture(picture_url);
+
+        tracing::info!("Updated profile metadata with event ID: {}", report.event_id);
+        Ok(report)
+    }
+
+    /// Broadcast the profile to every active relay, e.g. after adding relays.
+    ///
+    /// The newest kind-0 event found on the relays is re-sent unchanged so a
+    /// profile edited in another client isn't overwritten; the local profile
+    /// is only signed afresh when no relay has one.
+    pub async fn republish_profile(&self, relay_settings: &RelaySettings) -> Result<PublishReport> {
+        let credentials = self.credentials.as_ref()
+            .context("No Nostr credentials configured")?;
+
+        match self.fetch_profile_event(relay_settings).await? {
+            Some(event) => {
+                tracing::info!("Rebroadcasting existing profile event {}", event.id);
+                Ok(self.send_to_relays(event, relay_settings.get_write_relays()).await)
+            }
+            None => {
+                tracing::info!("No profile found on relays, publishing local profile");
+                self.update_profile(credentials, relay_settings, true).await
             }
         }
-        
-        if let Some(nip05) = &credentials.nip05 {
-            metadata = metadata.nip05(nip05);
+    }
+
+    /// The newest profile (kind 0) event of the signed-in identity on the relays
+    async fn fetch_profile_event(&self, relay_settings: &RelaySettings) -> Result<Option<Event>> {
+        let credentials = self.credentials.as_ref()
+            .context("No Nostr credentials configured")?;
+        let public_key = PublicKey::from_hex(&credentials.public_key)
+            .context("Invalid public key")?;
+
+        self.connect_to_relays(relay_settings).await?;
+
+        let filter = Filter::new().author(public_key).kind(Kind::Metadata);
+        Ok(self.client
+            .get_events_of(vec![filter], EventSource::relays(Some(Duration::from_secs(10))))
+            .await
+            .context("Failed to fetch profile from relays")?
+            .into_iter()
+            .max_by_key(|event| event.created_at))
+    }
+
+    /// The signed-in identity's profile as last published, e.g. from another client
+    pub async fn fetch_profile(&self, relay_settings: &RelaySettings) -> Result<Option<Metadata>> {
+        let Some(event) = self.fetch_profile_event(relay_settings).await? else {
+            return Ok(None);
+        };
+        let metadata = Metadata::from_json(&event.content)
+            .context("The profile on the relays isn't valid")?;
+        tracing::info!("Fetched profile event {}", event.id);
+        Ok(Some(metadata))
+    }
+
+    /// Publish the BUD-03 user server list (kind 10063) so other clients
+    /// know which Blossom servers hold this user's files, most preferred first
+    pub async fn publish_blossom_servers(&self, servers: &[String], relay_settings: &RelaySettings) -> Result<PublishReport> {
+        if self.credentials.is_none() {
+            return Err(anyhow::anyhow!("No Nostr credentials configured"));
         }
+        if servers.is_empty() {
+            return Err(anyhow::anyhow!("No Blossom servers to publish"));
+        }
+
+        let tags = servers
+            .iter()
+            .map(|server| Tag::custom(TagKind::Custom("server".into()), vec![server.clone()]))
+            .collect::<Vec<_>>();
 
-        let event_builder = EventBuilder::metadata(&metadata);
+        self.connect_to_relays(relay_settings).await?;
+
+        let event_builder = EventBuilder::new(Kind::Custom(10063), "", tags);
         let event = self.client.sign_event_builder(event_builder).await
-            .context("Failed to sign metadata event")?;
+            .context("Failed to sign Blossom server list")?;
 
-        let event_id = event.id;
-        self.client.send_event(event).await
-            .context("Failed to publish metadata event")?;
+        let report = self.send_to_relays(event, relay_settings.get_write_relays()).await;
+        if report.successful_relays.is_empty() {
+            return Err(anyhow::anyhow!("Failed to publish the server list to any relay"));
+        }
+
+        tracing::info!("Published Blossom server list to {} relays", report.successful_relays.len());
+        Ok(report)
+    }
+
+    /// Ask relays to delete a published article (NIP-09). The request names
+    /// both the event id and the article's address, so earlier versions of the
+    /// article are covered too. Relays may ignore it.
+    pub async fn delete_article(&self, post: &BlogPost, relay_settings: &RelaySettings) -> Result<PublishReport> {
+        let credentials = self.credentials.as_ref()
+            .context("No Nostr credentials configured")?;
+        let event_id = post.nostr_event_id.as_deref()
+            .context("This post has not been published")?;
+        let event_id = EventId::from_hex(event_id).context("Invalid event id")?;
+
+        // Relays only honour deletions signed by the article's author
+        let npub = self.npub();
+        if let Some(published_by) = post.published_by.as_ref().filter(|published_by| npub.as_ref() != Some(*published_by)) {
+            return Err(anyhow::anyhow!(
+                "It was published with {}. Switch to that account to delete it from relays.",
+                published_by
+            ));
+        }
+        let address = article_address(post, &credentials.public_key, &[])?;
+
+        // Send to where the post went; fall back to the write relays for older posts
+        let relays = if post.published_relays.is_empty() {
+            relay_settings.get_write_relays()
+        } else {
+            post.published_relays.clone()
+        };
+        self.connect_to(relays.clone()).await.context("Failed to connect to relays")?;
+
+        let tags = vec![
+            Tag::event(event_id),
+            Tag::parse(&["a", &address.coordinate])?,
+            Tag::parse(&["k", &Kind::LongFormTextNote.as_u16().to_string()])?,
+        ];
+        let event_builder = EventBuilder::new(Kind::EventDeletion, "Deleted by the author", tags);
+        let event = self.client.sign_event_builder(event_builder).await
+            .context("Failed to sign deletion request")?;
+
+        let report = self.send_to_relays(event, relays).await;
+        if report.successful_relays.is_empty() {
+            return Err(anyhow::anyhow!("No relay accepted the deletion request"));
+        }
+
+        tracing::info!("Sent deletion request for \"{}\" to {} relays", post.title, report.successful_relays.len());
+        Ok(report)
+    }
+
+    /// Save a post to relays as a NIP-37 draft (kind 31234). The article
+    /// event is left unsigned and NIP-44 encrypted to our own key, so only
+    /// this identity can read it back. Its timestamp is the post's last edit,
+    /// which is what `fetch_drafts` compares against local copies.
+    pub async fn publish_draft(&self, post: &BlogPost, relay_settings: &RelaySettings) -> Result<PublishReport> {
+        let credentials = self.credentials.as_ref()
+            .context("No Nostr credentials configured")?;
+        let public_key = PublicKey::from_hex(&credentials.public_key)
+            .context("Invalid public key")?;
+        // The draft carries no private flag, so a pull would save it as plaintext
+        if post.private {
+            return Err(anyhow::anyhow!("Private posts aren't synced to relays"));
+        }
+
+        // Unlisted only matters when publishing; the draft keeps every field
+        let mut listed = post.clone();
+        listed.unlisted = false;
+        let draft = EventBuilder::new(Kind::LongFormTextNote, &post.content, build_long_form_tags(&listed, false))
+            .custom_created_at(Timestamp::from(post.updated_at.timestamp().max(0) as u64))
+            .to_unsigned_event(public_key);
+
+        let signer = self.client.signer().await.context("No signer available")?;
+        let encrypted = signer.nip44_encrypt(public_key, draft.as_json()).await
+            .context("Failed to encrypt draft")?;
+
+        let tags = vec![
+            Tag::identifier(article_identifier(post)),
+            Tag::parse(&["k", &Kind::LongFormTextNote.as_u16().to_string()])?,
+        ];
+        let event_builder = EventBuilder::new(Kind::ParameterizedReplaceable(DRAFT_KIND), encrypted, tags);
+        let event = self.client.sign_event_builder(event_builder).await
+            .context("Failed to sign draft")?;
+
+        let relays = post_relays(post, relay_settings)?;
+        self.connect_to(relays.clone()).await.context("Failed to connect to relays")?;
+
+        let report = self.send_to_relays(event, relays).await;
+        if report.successful_relays.is_empty() {
+            return Err(anyhow::anyhow!("No relay accepted the draft"));
+        }
+
+        tracing::info!("Synced draft \"{}\" to {} relays", post.title, report.successful_relays.len());
+        Ok(report)
+    }
+
+    /// Fetch and decrypt this identity's NIP-37 drafts of long-form articles.
+    /// Only the newest version of each draft is returned; drafts that can't
+    /// be decrypted or were deleted (empty content) are skipped.
+    pub async fn fetch_drafts(&self, relay_settings: &RelaySettings) -> Result<Vec<BlogPost>> {
+        let credentials = self.credentials.as_ref()
+            .context("No Nostr credentials configured")?;
+        let public_key = PublicKey::from_hex(&credentials.public_key)
+            .context("Invalid public key")?;
+
+        self.connect_to_relays(relay_settings).await?;
+
+        let filter = Filter::new()
+            .author(public_key)
+            .kind(Kind::ParameterizedReplaceable(DRAFT_KIND))
+            .custom_tag(SingleLetterTag::lowercase(Alphabet::K), [Kind::LongFormTextNote.as_u16().to_string()]);
+        let mut events = self.client
+            .get_events_of(vec![filter], EventSource::relays(Some(Duration::from_secs(10))))
+            .await
+            .context("Failed to fetch drafts from relays")?;
+        events.sort_by_key(|event| std::cmp::Reverse(event.created_at));
+
+        let signer = self.client.signer().await.context("No signer available")?;
+        let mut seen = std::collections::HashSet::new();
+        let mut drafts = Vec::new();
+        for event in events {
+            if !seen.insert(event.identifier().map(str::to_string)) || event.content.is_empty() {
+                continue;
+            }
+
+            let decrypted = match signer.nip44_decrypt(public_key, &event.content).await {
+                Ok(decrypted) => decrypted,
+                Err(e) => {
+                    tracing::warn!("Skipping draft {} that could not be decrypted: {}", event.id, e);
+                    continue;
+                }
+            };
+            match UnsignedEvent::from_json(decrypted).map_err(anyhow::Error::from).and_then(|draft| post_from_draft(&draft)) {
+                Ok(post) => drafts.push(post),
+                Err(e) => tracing::warn!("Skipping unreadable draft {}: {:#}", event.id, e),
+            }
+        }
+
+        tracing::info!("Fetched {} drafts from relays", drafts.len());
+        Ok(drafts)
+    }
+
+    /// Fetch the newest NIP-65 relay list (kind 10002) published by the
+    /// active identity. A `None` marker means the relay is used for both
+    /// reading and writing.
+    pub async fn fetch_relay_list(&self) -> Result<Vec<(String, Option<RelayMetadata>)>> {
+        let credentials = self.credentials.as_ref()
+            .context("No Nostr credentials configured")?;
+        let public_key = PublicKey::from_hex(&credentials.public_key)
+            .context("Invalid public key")?;
+
+        let relays: Vec<String> = RELAY_LIST_BOOTSTRAP_RELAYS.iter().map(|r| r.to_string()).collect();
+        self.connect_to(relays.clone()).await?;
+
+        let filter = Filter::new().author(public_key).kind(Kind::RelayList);
+        let newest = self.client
+            .get_events_from(relays, vec![filter], Some(Duration::from_secs(10)))
+            .await
+            .context("Failed to fetch relay list from relays")?
+            .into_iter()
+            .max_by_key(|event| event.created_at)
+            .context("No relay list found for your profile")?;
+
+        Ok(parse_relay_list(&newest))
+    }
 
-        tracing::info!("Updated profile metadata with event ID: {}", event_id);
-        Ok(event_id)
+    /// The newest BUD-03 server list published by the current identity, if any
+    pub async fn fetch_blossom_servers(&self, relay_settings: &RelaySettings) -> Result<Option<Vec<String>>> {
+        let credentials = self.credentials.as_ref()
+            .context("No Nostr credentials configured")?;
+        let public_key = PublicKey::from_hex(&credentials.public_key)
+            .context("Invalid public key")?;
+
+        self.connect_to_relays(relay_settings).await?;
+
+        let filter = Filter::new().author(public_key).kind(Kind::Custom(10063));
+        let newest = self.client
+            .get_events_of(vec![filter], EventSource::relays(Some(Duration::from_secs(10))))
+            .await
+            .context("Failed to fetch Blossom server list from relays")?
+            .into_iter()
+            .max_by_key(|event| event.created_at);
+
+        Ok(newest.map(|event| {
+            event
+                .tags
+                .iter()
+                .filter_map(|tag| match tag.as_vec() {
+                    [name, server, ..] if name == "server" => Some(server.clone()),
+                    _ => None,
+                })
+                .collect()
+        }))
     }
 
-    pub async fn get_relay_status(&self, relay_settings: &RelaySettings) -> Vec<(String, bool)> {
+    /// Connection state of every active relay, as reported by the relay pool
+    pub async fn get_relay_status(&self, relay_settings: &RelaySettings) -> Vec<(String, RelayConnection)> {
+        let pool = self.client.relays().await;
         let mut status = Vec::new();
         
         for relay_url in relay_settings.get_active_relays() {
-            // For now, just assume connected. In a real implementation,
-            // you would check the actual connection status
-            let is_connected = true;
-            status.push((relay_url, is_connected));
+            let relay = Url::parse(&relay_url).ok().and_then(|url| pool.get(&url).cloned());
+            let connection = match relay {
+                Some(relay) => RelayConnection::from_status(relay.status().await),
+                None => RelayConnection::NotAdded,
+            };
+            status.push((relay_url, connection));
         }
         
         status
     }
 
+    /// Open a throwaway connection to a relay, outside the pool, and return
+    /// how long the websocket handshake took. Gives up after `RELAY_TEST_TIMEOUT`.
+    pub async fn test_relay(url: &str) -> Result<Duration> {
+        RelaySettings::validate_relay_url(url).map_err(|e| anyhow::anyhow!(e))?;
+        let parsed = Url::parse(url).context("Invalid relay URL")?;
+        let relay = Relay::new(parsed);
+
+        let started = std::time::Instant::now();
+        let connected = tokio::time::timeout(RELAY_TEST_TIMEOUT, async {
+            relay.connect(Some(RELAY_TEST_TIMEOUT)).await;
+            relay.is_connected().await
+        })
+        .await;
+        let elapsed = started.elapsed();
+
+        if let Err(e) = relay.disconnect().await {
+            tracing::debug!("Failed to close test connection to {}: {}", url, e);
+        }
+
+        match connected {
+            Ok(true) => {
+                tracing::info!("Relay {} connected in {} ms", url, elapsed.as_millis());
+                Ok(elapsed)
+            }
+            Ok(false) => Err(anyhow::anyhow!("{} refused the connection", url)),
+            Err(_) => Err(anyhow::anyhow!("{} didn't answer within {}s", url, RELAY_TEST_TIMEOUT.as_secs())),
+        }
+    }
+
+    /// Check that a NIP-05 identifier resolves to `pubkey` (hex or npub) by
+    /// fetching the domain's `/.well-known/nostr.json`. Errors when the server
+    /// can't be reached or doesn't list the name at all.
+    pub async fn verify_nip05(nip05: &str, pubkey: &str) -> Result<bool> {
+        let (name, url) = nip05_lookup_url(nip05)?;
+        let expected = PublicKey::parse(pubkey).context("Invalid public key")?;
+
+        // NIP-05 asks clients not to follow redirects
+        let client = reqwest::Client::builder()
+            .timeout(NIP05_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .context("Failed to create HTTP client")?;
+        let document: serde_json::Value = client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to fetch {}", url))?
+            .json()
+            .await
+            .context("The NIP-05 server didn't return valid JSON")?;
+
+        let listed = nip05_public_key(&document, &name)
+            .with_context(|| format!("{} isn't listed in {}", name, url))?;
+        tracing::info!("NIP-05 {} resolves to {}", nip05, listed.to_hex());
+        Ok(listed == expected)
+    }
+
     /// Generate new Nostr credentials
     pub fn generate_credentials() -> NostrCredentials {
         let keys = Keys::generate();
@@ -246,3 +929,688 @@ impl NostrClient {
             .context("Failed to sign event")
     }
 }
+
+/// Split a NIP-05 identifier into its lowercased name and the URL that looks
+/// the name up. A bare domain stands for `_@domain`.
+pub fn nip05_lookup_url(nip05: &str) -> Result<(String, String)> {
+    let nip05 = nip05.trim();
+    let (name, domain) = nip05.split_once('@').unwrap_or(("_", nip05));
+    let name = name.to_lowercase();
+    let valid_name = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c));
+    if !valid_name || domain.is_empty() || domain.contains(['/', '@', '?', '#']) || domain.contains(char::is_whitespace) {
+        anyhow::bail!("\"{}\" isn't an identifier like name@example.com", nip05);
+    }
+
+    let mut url = Url::parse(&format!("https://{}/.well-known/nostr.json", domain)).context("Invalid NIP-05 domain")?;
+    url.query_pairs_mut().append_pair("name", &name);
+    Ok((name, url.to_string()))
+}
+
+/// The public key a `nostr.json` document lists for `name`
+fn nip05_public_key(document: &serde_json::Value, name: &str) -> Option<PublicKey> {
+    let hex = document.get("names")?.get(name)?.as_str()?;
+    PublicKey::from_hex(hex).ok()
+}
+
+/// Maximum number of relay hints embedded in an naddr
+const MAX_NADDR_RELAYS: usize = 3;
+
+/// The relays a post is sent to. These are checked exactly as the publish
+/// dialog checks them, so they never fall back to the default relays.
+fn post_relays(post: &BlogPost, relay_settings: &RelaySettings) -> Result<Vec<String>> {
+    relay_settings
+        .publish_relays(&post.excluded_relays, &post.extra_relays)
+        .map_err(|e| anyhow::anyhow!(e))
+}
+
+/// The `d` tag a post is published under. It stays the same across edits, so
+/// every version of the article replaces the previous one.
+pub fn article_identifier(post: &BlogPost) -> String {
+    post.identifier.clone().unwrap_or_else(|| format!("blogster-{}", post.id))
+}
+
+/// Whether two posts are the same article. Drafts pulled from other clients
+/// get a new local id each time, so their `d` tag identifies them too.
+pub fn is_same_article(a: &BlogPost, b: &BlogPost) -> bool {
+    a.id == b.id || article_identifier(a) == article_identifier(b)
+}
+
+/// Where a published article lives: the `kind:pubkey:d` coordinate and its
+/// shareable naddr form
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArticleAddress {
+    pub naddr: String,
+    pub coordinate: String,
+}
+
+/// Hex form of an npub, as `article_address` takes it
+pub fn public_key_hex(npub: &str) -> Result<String> {
+    Ok(PublicKey::from_bech32(npub).context("Invalid author npub")?.to_hex())
+}
+
+/// Address of a post published by `public_key_hex`, with up to a few of the
+/// relays it was seen on as hints
+pub fn article_address(post: &BlogPost, public_key_hex: &str, relays: &[String]) -> Result<ArticleAddress> {
+    let public_key = PublicKey::from_hex(public_key_hex).context("Invalid author public key")?;
+    let identifier = article_identifier(post);
+
+    let mut coordinate = Coordinate::new(Kind::LongFormTextNote, public_key).identifier(&identifier);
+    coordinate.relays = relays.iter().take(MAX_NADDR_RELAYS).cloned().collect();
+
+    Ok(ArticleAddress {
+        naddr: coordinate.to_bech32().context("Failed to encode naddr")?,
+        coordinate: format!("{}:{}:{}", Kind::LongFormTextNote.as_u16(), public_key.to_hex(), identifier),
+    })
+}
+
+/// Turn a user-supplied event reference into a relay filter.
+///
+/// Accepts `naddr1...`, `nevent1...`, `note1...` (optionally prefixed with `nostr:`)
+/// or a 64 character hex event id.
+pub fn parse_event_reference(reference: &str) -> Result<Filter> {
+    let reference = reference.trim();
+    let reference = reference.strip_prefix("nostr:").unwrap_or(reference);
+
+    if reference.is_empty() {
+        return Err(anyhow::anyhow!("Please enter an naddr, nevent, note or event id"));
+    }
+
+    if let Ok(event_id) = EventId::from_hex(reference) {
+        return Ok(Filter::new().id(event_id));
+    }
+
+    match Nip19::from_bech32(reference).context("Not a valid NIP-19 entity or hex event id")? {
+        Nip19::EventId(event_id) => Ok(Filter::new().id(event_id)),
+        Nip19::Event(event) => Ok(Filter::new().id(event.event_id)),
+        Nip19::Coordinate(coordinate) => Ok(Filter::new()
+            .kind(coordinate.kind)
+            .author(coordinate.public_key)
+            .identifier(coordinate.identifier)),
+        _ => Err(anyhow::anyhow!("Unsupported reference: expected naddr, nevent, note or event id")),
+    }
+}
+
+/// A fetched event, flattened for display
+#[derive(Debug, Clone)]
+pub struct ResolvedEvent {
+    pub id: String,
+    pub kind: u16,
+    /// Author's npub, or the hex key if it can't be encoded
+    pub author: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub content: String,
+    pub signature_valid: bool,
+    /// Set for long-form articles (kind 30023)
+    pub article: Option<BlogPost>,
+}
+
+impl ResolvedEvent {
+    pub fn from_event(event: &Event, relays: Vec<String>) -> Self {
+        let article = if event.kind.as_u16() == 30023 {
+            post_from_article_event(event, relays).ok()
+        } else {
+            None
+        };
+
+        Self {
+            id: event.id.to_hex(),
+            kind: event.kind.as_u16(),
+            author: event.pubkey.to_bech32().unwrap_or_else(|_| event.pubkey.to_hex()),
+            created_at: chrono::DateTime::from_timestamp(event.created_at.as_u64() as i64, 0),
+            content: event.content.clone(),
+            signature_valid: event.verify().is_ok(),
+            article,
+        }
+    }
+}
+
+/// Rebuild a local `BlogPost` from a published NIP-23 article event
+pub fn post_from_article_event(event: &Event, relays: Vec<String>) -> Result<BlogPost> {
+    event.verify().context("Event signature is invalid")?;
+
+    if event.kind.as_u16() != 30023 {
+        return Err(anyhow::anyhow!("Event is kind {}, not a long-form article (30023)", event.kind.as_u16()));
+    }
+
+    let mut post = post_from_article_parts(&event.content, &event.tags);
+
+    post.updated_at = chrono::DateTime::from_timestamp(event.created_at.as_u64() as i64, 0)
+        .unwrap_or_else(chrono::Utc::now);
+    let published_at = post.published_at;
+    post.set_published(event.id.to_hex(), relays, published_at);
+    post.published_by = event.pubkey.to_bech32().ok();
+
+    Ok(post)
+}
+
+/// Rebuild a local draft from the unsigned article inside a NIP-37 draft
+pub fn post_from_draft(draft: &UnsignedEvent) -> Result<BlogPost> {
+    if draft.kind.as_u16() != 30023 {
+        return Err(anyhow::anyhow!("Draft is kind {}, not a long-form article (30023)", draft.kind.as_u16()));
+    }
+
+    let mut post = post_from_article_parts(&draft.content, &draft.tags);
+    // Drafts carry the creation date as published_at; it isn't a real publication
+    post.published_at = None;
+    post.updated_at = chrono::DateTime::from_timestamp(draft.created_at.as_u64() as i64, 0)
+        .unwrap_or_else(chrono::Utc::now);
+
+    Ok(post)
+}
+
+/// Fill in a post from NIP-23 article content and tags
+fn post_from_article_parts(content: &str, tags: &[Tag]) -> BlogPost {
+    let mut post = BlogPost::new();
+    post.content = content.to_string();
+
+    for tag in tags.iter() {
+        let values = tag.as_vec();
+        let (Some(name), Some(value)) = (values.first(), values.get(1)) else {
+            continue;
+        };
+
+        match name.as_str() {
+            "title" => post.title = value.clone(),
+            "summary" => post.summary = Some(value.clone()),
+            // The first image is the featured one; any others form the gallery
+            "image" if post.image_url.is_none() => post.image_url = Some(value.clone()),
+            "image" => post.gallery_images.push(value.clone()),
+            "t" => post.add_tag(value.clone()),
+            "d" => {
+                // Articles published by Blogster carry the local post id in their
+                // identifier; any other identifier is kept so updates replace the article
+                match value.strip_prefix("blogster-").and_then(|id| uuid::Uuid::parse_str(id).ok()) {
+                    Some(id) => post.id = id,
+                    None => post.identifier = Some(value.clone()),
+                }
+            }
+            "published_at" => {
+                if let Some(published_at) = value
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                {
+                    post.created_at = published_at;
+                    post.published_at = Some(published_at);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if post.title.trim().is_empty() {
+        post.title = "Recovered Post".to_string();
+    }
+
+    post
+}
+
+/// Relays and their read/write markers from the `r` tags of a NIP-65 event
+pub fn parse_relay_list(event: &Event) -> Vec<(String, Option<RelayMetadata>)> {
+    event
+        .tags
+        .iter()
+        .filter_map(|tag| match tag.as_vec() {
+            [name, url] if name == "r" => Some((url.clone(), None)),
+            [name, url, marker, ..] if name == "r" => match marker.as_str() {
+                "read" => Some((url.clone(), Some(RelayMetadata::Read))),
+                "write" => Some((url.clone(), Some(RelayMetadata::Write))),
+                _ => Some((url.clone(), None)),
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Turn NIP-65 relay list entries into custom relays with matching read/write flags
+pub fn relay_list_to_custom_relays(relays: Vec<(String, Option<RelayMetadata>)>) -> Vec<CustomRelay> {
+    relays
+        .into_iter()
+        .map(|(url, marker)| CustomRelay {
+            url,
+            read: !matches!(marker, Some(RelayMetadata::Write)),
+            write: !matches!(marker, Some(RelayMetadata::Read)),
+        })
+        .collect()
+}
+
+/// Run `send` for every relay at once and sort the outcomes into a report.
+/// `send` resolves to the relay's rejection reason if it didn't accept the
+/// event; relays still going at `timeout` are given up on as timed out.
+async fn collect_relay_results<F, Fut>(event_id: EventId, relays: Vec<String>, timeout: Duration, send: F) -> PublishReport
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<(), String>> + Send + 'static,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut report = PublishReport {
+        event_id,
+        successful_relays: Vec::new(),
+        failed_relays: Vec::new(),
+        timed_out_relays: Vec::new(),
+        skipped_relays: Vec::new(),
+        published_at: None,
+    };
+
+    let mut tasks = JoinSet::new();
+    for relay_url in relays {
+        let sending = send(relay_url.clone());
+        tasks.spawn(async move { (relay_url, tokio::time::timeout_at(deadline, sending).await) });
+    }
+
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((relay_url, Ok(Ok(())))) => report.successful_relays.push(relay_url),
+            Ok((relay_url, Ok(Err(reason)))) => report.failed_relays.push((relay_url, reason)),
+            Ok((relay_url, Err(_))) => {
+                tracing::warn!("Relay {} timed out after {:?}", relay_url, timeout);
+                report.timed_out_relays.push(relay_url);
+            }
+            Err(e) => {
+                tracing::error!("Publish task failed: {}", e);
+            }
+        }
+    }
+
+    report
+}
+
+/// Set the profile fields Blogster edits in a profile's JSON content, keeping
+/// every other field. Fields not set in `credentials` are left as they are.
+pub fn merge_profile(current: &str, credentials: &NostrCredentials) -> Result<String> {
+    let mut profile: serde_json::Map<String, serde_json::Value> = serde_json::from_str(current)
+        .context("The profile on the relays isn't valid")?;
+
+    // An invalid picture URL is left out, as clients couldn't show it anyway
+    let picture = credentials.picture.as_ref().filter(|picture| picture.parse::<nostr_sdk::Url>().is_ok());
+    let fields = [
+        ("display_name", credentials.display_name.as_ref()),
+        ("about", credentials.about.as_ref()),
+        ("picture", picture),
+        ("nip05", credentials.nip05.as_ref()),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            profile.insert(key.to_string(), serde_json::Value::String(value.clone()));
+        }
+    }
+
+    Ok(serde_json::Value::Object(profile).to_string())
+}
+
+/// Build the NIP-23 tags for a long-form article event
+pub fn build_long_form_tags(post: &BlogPost, include_client_tag: bool) -> Vec<Tag> {
+    // Create long-form content event (NIP-23)
+    let mut tags = vec![
+        Tag::title(&post.title),
+    ];
+
+    // Unlisted posts leave out everything clients use for discovery
+    if !post.unlisted {
+        // Add summary if available
+        if let Some(summary) = &post.summary {
+            tags.push(Tag::custom(TagKind::Custom("summary".into()), vec![summary.clone()]));
+        }
+
+        // Add hashtags
+        for tag in &post.tags {
+            tags.push(Tag::hashtag(tag));
+        }
+
+        // Add the featured image first, then any gallery images
+        for image_url in post.images() {
+            tags.push(Tag::custom(TagKind::Custom("image".into()), vec![image_url]));
+        }
+    }
+
+    // First publication time; updates keep it so clients show the original date
+    tags.push(Tag::custom(
+        TagKind::Custom("published_at".into()),
+        vec![post.first_published_at().timestamp().to_string()]
+    ));
+
+    // Add identifier for replaceable event (NIP-33)
+    tags.push(Tag::identifier(article_identifier(post)));
+
+    // Identify the publishing app (NIP-89)
+    if include_client_tag {
+        tags.push(Tag::custom(
+            TagKind::Custom("client".into()),
+            vec![format!("{} {}", CLIENT_NAME, env!("CARGO_PKG_VERSION"))]
+        ));
+    }
+
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has_tag(tags: &[Tag], name: &str) -> bool {
+        tags.iter().any(|t| t.as_vec().first().map(String::as_str) == Some(name))
+    }
+
+    #[test]
+    fn test_client_tag_present_when_enabled() {
+        let post = BlogPost::new()
+            .with_title("Hello".to_string())
+            .with_content("World".to_string());
+
+        let tags = build_long_form_tags(&post, true);
+        let client_tag = tags
+            .iter()
+            .find(|t| t.as_vec().first().map(String::as_str) == Some("client"))
+            .expect("client tag missing");
+        assert_eq!(
+            client_tag.as_vec()[1],
+            format!("Blogster {}", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn test_client_tag_absent_when_disabled() {
+        let post = BlogPost::new().with_title("Hello".to_string());
+
+        let tags = build_long_form_tags(&post, false);
+        assert!(!has_tag(&tags, "client"));
+        assert!(has_tag(&tags, "d"));
+    }
+
+    #[test]
+    fn test_post_relays_never_fall_back_to_defaults() {
+        let mut settings = RelaySettings::new();
+        settings.use_default_relays = false;
+        settings.use_custom_relays = true;
+        settings.custom_relays = vec![
+            CustomRelay::new("wss://one.relay.com".to_string()),
+            CustomRelay::new("wss://two.relay.com".to_string()),
+        ];
+
+        let mut post = BlogPost::new().with_title("Relays".to_string());
+        post.excluded_relays = vec!["wss://one.relay.com".to_string()];
+        assert_eq!(post_relays(&post, &settings).unwrap(), vec!["wss://two.relay.com".to_string()]);
+
+        post.excluded_relays.push("wss://two.relay.com".to_string());
+        assert!(post_relays(&post, &settings).is_err());
+
+        settings.use_custom_relays = false;
+        post.excluded_relays.clear();
+        assert!(post_relays(&post, &settings).is_err());
+    }
+
+    #[test]
+    fn test_post_relays_check_one_off_relays() {
+        let mut settings = RelaySettings::new();
+        settings.use_default_relays = false;
+
+        // Only the post's own relays, not the defaults
+        let mut post = BlogPost::new().with_title("Niche".to_string());
+        post.extra_relays = vec!["wss://niche.relay.com".to_string()];
+        assert_eq!(post_relays(&post, &settings).unwrap(), vec!["wss://niche.relay.com".to_string()]);
+
+        post.extra_relays.push("wss://blocked.relay.com".to_string());
+        settings.block_relay("wss://blocked.relay.com");
+        assert_eq!(post_relays(&post, &settings).unwrap(), vec!["wss://niche.relay.com".to_string()]);
+
+        post.extra_relays.push("not a relay".to_string());
+        assert!(post_relays(&post, &settings).unwrap_err().to_string().contains("not a relay"));
+    }
+
+    #[test]
+    fn test_parse_event_reference_rejects_garbage() {
+        assert!(parse_event_reference("").is_err());
+        assert!(parse_event_reference("not-an-event").is_err());
+        assert!(parse_event_reference(&"a".repeat(64)).is_ok());
+    }
+
+    #[test]
+    fn test_parse_relay_list_markers() {
+        let keys = Keys::generate();
+        let tags = vec![
+            Tag::parse(&["r", "wss://both.relay.com"]).unwrap(),
+            Tag::parse(&["r", "wss://read.relay.com", "read"]).unwrap(),
+            Tag::parse(&["r", "wss://write.relay.com", "write"]).unwrap(),
+            Tag::parse(&["p", "not-a-relay"]).unwrap(),
+        ];
+        let event = EventBuilder::new(Kind::RelayList, "", tags).to_event(&keys).unwrap();
+
+        assert_eq!(
+            parse_relay_list(&event),
+            vec![
+                ("wss://both.relay.com".to_string(), None),
+                ("wss://read.relay.com".to_string(), Some(RelayMetadata::Read)),
+                ("wss://write.relay.com".to_string(), Some(RelayMetadata::Write)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_post_from_article_event_round_trip() {
+        let keys = Keys::generate();
+        let mut post = BlogPost::new()
+            .with_title("Recovered".to_string())
+            .with_content("Body text".to_string());
+        post.add_tag("nostr".to_string());
+        post.set_images(vec!["https://example.com/cover.png".to_string(), "https://example.com/more.png".to_string()]);
+
+        let event = EventBuilder::new(
+            Kind::ParameterizedReplaceable(30023),
+            &post.content,
+            build_long_form_tags(&post, false),
+        )
+        .to_event(&keys)
+        .unwrap();
+
+        let recovered = post_from_article_event(&event, vec!["wss://relay.damus.io".to_string()]).unwrap();
+        assert_eq!(recovered.id, post.id);
+        assert_eq!(recovered.title, "Recovered");
+        assert_eq!(recovered.content, "Body text");
+        assert_eq!(recovered.tags, vec!["nostr".to_string()]);
+        assert_eq!(recovered.images(), post.images());
+        assert_eq!(recovered.status, crate::post::PostStatus::Published);
+        assert_eq!(recovered.nostr_event_id, Some(event.id.to_hex()));
+    }
+
+    #[test]
+    fn test_recovered_article_keeps_its_identifier() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new(
+            Kind::ParameterizedReplaceable(30023),
+            "Written elsewhere",
+            vec![Tag::identifier("my-first-article"), Tag::parse(&["title", "Elsewhere"]).unwrap()],
+        )
+        .to_event(&keys)
+        .unwrap();
+
+        let recovered = post_from_article_event(&event, vec![]).unwrap();
+        assert_eq!(recovered.identifier.as_deref(), Some("my-first-article"));
+        assert_eq!(article_identifier(&recovered), "my-first-article");
+        assert!(build_long_form_tags(&recovered, false)
+            .iter()
+            .any(|tag| tag.as_vec() == ["d", "my-first-article"]));
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&recovered.to_markdown_with_frontmatter(), None).unwrap();
+        assert_eq!(reloaded.identifier, recovered.identifier);
+    }
+
+    #[test]
+    fn test_post_from_draft_keeps_draft_status() {
+        let keys = Keys::generate();
+        let mut post = BlogPost::new()
+            .with_title("Work in progress".to_string())
+            .with_content("Half a thought".to_string());
+        post.updated_at = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let draft = EventBuilder::new(Kind::LongFormTextNote, &post.content, build_long_form_tags(&post, false))
+            .custom_created_at(Timestamp::from(post.updated_at.timestamp() as u64))
+            .to_unsigned_event(keys.public_key());
+        let draft = UnsignedEvent::from_json(draft.as_json()).unwrap();
+
+        let pulled = post_from_draft(&draft).unwrap();
+        assert_eq!(pulled.id, post.id);
+        assert_eq!(pulled.title, "Work in progress");
+        assert_eq!(pulled.content, "Half a thought");
+        assert_eq!(pulled.status, crate::post::PostStatus::Draft);
+        assert_eq!(pulled.published_at, None);
+        assert_eq!(pulled.updated_at, post.updated_at);
+    }
+
+    #[test]
+    fn test_pulling_a_foreign_draft_twice_keeps_one_post() {
+        let keys = Keys::generate();
+        let mut post = BlogPost::new().with_title("From another client".to_string());
+        post.identifier = Some("my-article".to_string());
+
+        let draft = EventBuilder::new(Kind::LongFormTextNote, &post.content, build_long_form_tags(&post, false))
+            .to_unsigned_event(keys.public_key());
+        let draft = UnsignedEvent::from_json(draft.as_json()).unwrap();
+
+        let mut posts: Vec<BlogPost> = Vec::new();
+        for _ in 0..2 {
+            let pulled = post_from_draft(&draft).unwrap();
+            if !posts.iter().any(|p| is_same_article(p, &pulled)) {
+                posts.push(pulled);
+            }
+        }
+        assert_eq!(posts.len(), 1);
+        assert_ne!(post_from_draft(&draft).unwrap().id, posts[0].id);
+    }
+
+    #[test]
+    fn test_unlisted_post_omits_discovery_tags() {
+        let mut post = BlogPost::new().with_title("Secret".to_string());
+        post.summary = Some("Summary".to_string());
+        post.image_url = Some("https://example.com/img.png".to_string());
+        post.add_tag("hidden".to_string());
+        post.unlisted = true;
+
+        let tags = build_long_form_tags(&post, false);
+        assert!(!has_tag(&tags, "t"));
+        assert!(!has_tag(&tags, "summary"));
+        assert!(!has_tag(&tags, "image"));
+        assert!(has_tag(&tags, "title"));
+        assert!(has_tag(&tags, "d"));
+    }
+
+    #[test]
+    fn test_article_address_round_trip() {
+        let keys = Keys::generate();
+        let post = BlogPost::new().with_title("Addressable".to_string());
+        let relays = vec!["wss://relay.damus.io".to_string()];
+
+        let npub = keys.public_key().to_bech32().unwrap();
+        let address = article_address(&post, &public_key_hex(&npub).unwrap(), &relays).unwrap();
+        assert_eq!(
+            address.coordinate,
+            format!("30023:{}:blogster-{}", keys.public_key().to_hex(), post.id)
+        );
+
+        match Nip19::from_bech32(&address.naddr).unwrap() {
+            Nip19::Coordinate(coordinate) => {
+                assert_eq!(coordinate.kind.as_u16(), 30023);
+                assert_eq!(coordinate.public_key, keys.public_key());
+                assert_eq!(coordinate.identifier, article_identifier(&post));
+            }
+            other => panic!("expected a coordinate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_nip05_lookup() {
+        let (name, url) = nip05_lookup_url("Alice@example.com").unwrap();
+        assert_eq!(name, "alice");
+        assert_eq!(url, "https://example.com/.well-known/nostr.json?name=alice");
+
+        let (name, url) = nip05_lookup_url("example.com").unwrap();
+        assert_eq!(name, "_");
+        assert_eq!(url, "https://example.com/.well-known/nostr.json?name=_");
+        assert!(nip05_lookup_url("alice@").is_err());
+        assert!(nip05_lookup_url("al ice@example.com").is_err());
+
+        let keys = Keys::generate();
+        let document = serde_json::json!({ "names": { "alice": keys.public_key().to_hex() } });
+        assert_eq!(nip05_public_key(&document, "alice"), Some(keys.public_key()));
+        assert_eq!(nip05_public_key(&document, "bob"), None);
+    }
+
+    #[test]
+    fn test_relay_results_cover_every_relay() {
+        let report = PublishReport {
+            event_id: EventId::all_zeros(),
+            successful_relays: vec!["wss://b.relay".to_string()],
+            failed_relays: vec![("wss://a.relay".to_string(), "blocked: paid relay".to_string())],
+            timed_out_relays: vec!["wss://c.relay".to_string()],
+            skipped_relays: vec!["wss://d.relay".to_string()],
+            published_at: None,
+        };
+
+        let results = report.relay_results();
+        assert_eq!(results.keys().collect::<Vec<_>>(), ["wss://a.relay", "wss://b.relay", "wss://c.relay"]);
+        assert_eq!(results["wss://a.relay"], Err("blocked: paid relay".to_string()));
+        assert_eq!(results["wss://b.relay"], Ok(()));
+        assert!(results["wss://c.relay"].is_err());
+    }
+
+    #[test]
+    fn test_profile_update_keeps_other_fields() {
+        let current = r#"{"name":"alice","display_name":"Alice","lud16":"alice@getalby.com","banner":"https://example.com/banner.png","website":"https://alice.blog"}"#;
+        let mut credentials = NostrCredentials::new(String::new(), String::new());
+        credentials.display_name = Some("Alice Writes".to_string());
+        credentials.about = Some("Long-form on Nostr".to_string());
+        credentials.picture = Some("not a url".to_string());
+
+        let merged: serde_json::Value = serde_json::from_str(&merge_profile(current, &credentials).unwrap()).unwrap();
+        assert_eq!(merged["display_name"], "Alice Writes");
+        assert_eq!(merged["about"], "Long-form on Nostr");
+        assert_eq!(merged["name"], "alice");
+        assert_eq!(merged["lud16"], "alice@getalby.com");
+        assert_eq!(merged["banner"], "https://example.com/banner.png");
+        assert_eq!(merged["website"], "https://alice.blog");
+        assert!(merged.get("picture").is_none());
+        assert!(merge_profile("not json", &credentials).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_signed_article_json_is_a_complete_event() {
+        let credentials = NostrClient::generate_credentials();
+        let public_key = credentials.public_key.clone();
+        let mut client = NostrClient::new();
+        client.set_credentials(credentials).await.unwrap();
+
+        let post = BlogPost::new()
+            .with_title("Broadcast elsewhere".to_string())
+            .with_content("Signed here, sent by another tool".to_string());
+        let json = client.sign_article_json(&post).await.unwrap();
+
+        let event = Event::from_json(&json).unwrap();
+        event.verify().unwrap();
+        assert_eq!(event.kind.as_u16(), 30023);
+        assert_eq!(event.pubkey.to_hex(), public_key);
+        assert_eq!(event.content, "Signed here, sent by another tool");
+        assert!(event.tags.iter().any(|tag| tag.as_vec() == ["title", "Broadcast elsewhere"]));
+        assert!(event.tags.iter().any(|tag| tag.as_vec() == ["d".to_string(), article_identifier(&post)]));
+
+        assert!(client.sign_article_json(&BlogPost::new()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_slow_relay_times_out() {
+        let relays = vec!["wss://fast.relay".to_string(), "wss://slow.relay".to_string(), "wss://picky.relay".to_string()];
+        let report = collect_relay_results(EventId::all_zeros(), relays, Duration::from_millis(200), |relay_url| async move {
+            match relay_url.as_str() {
+                "wss://slow.relay" => {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    Ok(())
+                }
+                "wss://picky.relay" => Err("blocked: paid relay".to_string()),
+                _ => Ok(()),
+            }
+        })
+        .await;
+
+        assert_eq!(report.successful_relays, vec!["wss://fast.relay".to_string()]);
+        assert_eq!(report.failed_relays, vec![("wss://picky.relay".to_string(), "blocked: paid relay".to_string())]);
+        assert_eq!(report.timed_out_relays, vec!["wss://slow.relay".to_string()]);
+    }
+}
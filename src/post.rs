@@ -1,5 +1,7 @@
 use chrono::{DateTime, Utc};
+use nostr_sdk::{Keys, ToBech32};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -17,6 +19,39 @@ pub struct BlogPost {
     pub nostr_event_id: Option<String>,
     pub published_relays: Vec<String>,
     pub file_path: Option<PathBuf>, // Path to the .md file
+    /// Publish without hashtags, summary or image so the article is only reachable by link.
+    /// Relays and clients ultimately decide what is discoverable; this only limits what we send.
+    #[serde(default)]
+    pub unlisted: bool,
+    /// Active relays the author chose not to publish this post to
+    #[serde(default)]
+    pub excluded_relays: Vec<String>,
+    /// One-off relays this post is also published to, on top of the active relays
+    #[serde(default)]
+    pub extra_relays: Vec<String>,
+    /// Shown in the sidebar's pinned section above all status groups
+    #[serde(default)]
+    pub pinned: bool,
+    /// Further gallery images after the featured `image_url`, published as
+    /// extra `image` tags
+    #[serde(default)]
+    pub gallery_images: Vec<String>,
+    /// Content is encrypted on disk with the private-posts passphrase
+    #[serde(default)]
+    pub private: bool,
+    /// Sent as the article's `published_at`; kept the same when an update is published
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    /// npub of the identity that published the post
+    #[serde(default)]
+    pub published_by: Option<String>,
+    /// `d` tag of an article recovered from relays that Blogster didn't
+    /// publish, so updates replace it instead of starting a new article
+    #[serde(default)]
+    pub identifier: Option<String>,
+    /// When the post should be published automatically
+    #[serde(default)]
+    pub scheduled_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -26,6 +61,80 @@ pub enum PostStatus {
     Failed,
 }
 
+/// What the post list is ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PostSortKey {
+    #[default]
+    Updated,
+    Created,
+    Title,
+    WordCount,
+}
+
+impl PostSortKey {
+    pub const ALL: [PostSortKey; 4] = [PostSortKey::Updated, PostSortKey::Created, PostSortKey::Title, PostSortKey::WordCount];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PostSortKey::Updated => "Updated",
+            PostSortKey::Created => "Created",
+            PostSortKey::Title => "Title (A–Z)",
+            PostSortKey::WordCount => "Word count",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostSort {
+    pub key: PostSortKey,
+    pub descending: bool,
+}
+
+impl Default for PostSort {
+    fn default() -> Self {
+        Self { key: PostSortKey::Updated, descending: true }
+    }
+}
+
+impl PostSort {
+    pub fn sort(&self, posts: &mut [&BlogPost], word_counts: &mut WordCounts) {
+        match self.key {
+            PostSortKey::Updated => posts.sort_by_key(|p| p.updated_at),
+            PostSortKey::Created => posts.sort_by_key(|p| p.created_at),
+            PostSortKey::Title => posts.sort_by_cached_key(|p| p.title.to_lowercase()),
+            PostSortKey::WordCount => posts.sort_by_cached_key(|p| word_counts.get(p)),
+        }
+        if self.descending {
+            posts.reverse();
+        }
+    }
+}
+
+/// Word counts kept between frames, since counting parses the Markdown.
+/// A post is recounted once its `updated_at` changes; locked posts count as
+/// zero and aren't remembered.
+#[derive(Debug, Default)]
+pub struct WordCounts {
+    counts: HashMap<Uuid, (DateTime<Utc>, usize)>,
+}
+
+impl WordCounts {
+    pub fn get(&mut self, post: &BlogPost) -> usize {
+        if post.is_locked() {
+            return 0;
+        }
+        match self.counts.get(&post.id) {
+            Some(&(updated_at, count)) if updated_at == post.updated_at => count,
+            _ => {
+                let count = post.word_count();
+                self.counts.insert(post.id, (post.updated_at, count));
+                count
+            }
+        }
+    }
+}
+
 impl Default for BlogPost {
     fn default() -> Self {
         let now = Utc::now();
@@ -42,6 +151,16 @@ impl Default for BlogPost {
             nostr_event_id: None,
             published_relays: Vec::new(),
             file_path: None,
+            unlisted: false,
+            excluded_relays: Vec::new(),
+            extra_relays: Vec::new(),
+            pinned: false,
+            private: false,
+            published_at: None,
+            published_by: None,
+            identifier: None,
+            scheduled_at: None,
+            gallery_images: Vec::new(),
         }
     }
 }
@@ -80,20 +199,121 @@ impl BlogPost {
         self.updated_at = Utc::now();
     }
 
-    pub fn set_published(&mut self, event_id: String, relays: Vec<String>) {
+    /// The featured image followed by the gallery images
+    pub fn images(&self) -> Vec<String> {
+        self.image_url.iter().chain(&self.gallery_images).cloned().collect()
+    }
+
+    /// Replace all images; the first becomes the featured image
+    pub fn set_images(&mut self, images: Vec<String>) {
+        let mut images = images.into_iter().filter(|url| !url.trim().is_empty());
+        self.image_url = images.next();
+        self.gallery_images = images.collect();
+        self.updated_at = Utc::now();
+    }
+
+    /// Move an image within `images()`, e.g. to make a gallery image the featured one
+    pub fn move_image(&mut self, from: usize, to: usize) {
+        let mut images = self.images();
+        if from < images.len() && to < images.len() {
+            let image = images.remove(from);
+            images.insert(to, image);
+            self.set_images(images);
+        }
+    }
+
+    /// Remove an image by its position in `images()`
+    pub fn remove_image(&mut self, index: usize) {
+        let mut images = self.images();
+        if index < images.len() {
+            images.remove(index);
+            self.set_images(images);
+        }
+    }
+
+    /// A new draft with this post's content and metadata, to start another
+    /// article from. It has its own id, so it is saved to its own file and
+    /// published as a separate article.
+    pub fn duplicate(&self) -> BlogPost {
+        let now = Utc::now();
+        BlogPost {
+            id: Uuid::new_v4(),
+            title: format!("{} (copy)", self.title),
+            created_at: now,
+            updated_at: now,
+            status: PostStatus::Draft,
+            nostr_event_id: None,
+            published_relays: Vec::new(),
+            file_path: None,
+            pinned: false,
+            published_at: None,
+            published_by: None,
+            identifier: None,
+            scheduled_at: None,
+            ..self.clone()
+        }
+    }
+
+    /// Whether an article for this post is already out there, so publishing
+    /// again replaces it instead of creating a new one
+    pub fn has_been_published(&self) -> bool {
+        self.nostr_event_id.is_some()
+    }
+
+    /// The `published_at` time to send: the first publication, or now for
+    /// posts that have never been published. Posts published before the
+    /// date was kept fall back to their creation date.
+    pub fn first_published_at(&self) -> DateTime<Utc> {
+        match self.published_at {
+            Some(published_at) => published_at,
+            None if self.has_been_published() => self.created_at,
+            None => Utc::now(),
+        }
+    }
+
+    /// Whether the post is scheduled and its time has come
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.scheduled_at.is_some_and(|scheduled_at| scheduled_at <= now)
+    }
+
+    /// Record a publication. `published_at` is the date the event was signed
+    /// with, when known, so the post keeps exactly what relays were sent.
+    pub fn set_published(&mut self, event_id: String, relays: Vec<String>, published_at: Option<DateTime<Utc>>) {
+        self.published_at = Some(published_at.unwrap_or_else(|| self.first_published_at()));
+        self.scheduled_at = None;
         self.status = PostStatus::Published;
         self.nostr_event_id = Some(event_id);
         self.published_relays = relays;
         self.updated_at = Utc::now();
     }
 
+    /// Take the text and metadata of a draft pulled from relays if it was
+    /// edited more recently than this copy. Local-only state such as the
+    /// publication record, pin and privacy is kept. Returns whether anything changed.
+    pub fn apply_draft(&mut self, draft: &BlogPost) -> bool {
+        if draft.updated_at <= self.updated_at {
+            return false;
+        }
+
+        self.title = draft.title.clone();
+        self.content = draft.content.clone();
+        self.summary = draft.summary.clone();
+        self.tags = draft.tags.clone();
+        self.image_url = draft.image_url.clone();
+        self.gallery_images = draft.gallery_images.clone();
+        self.updated_at = draft.updated_at;
+        true
+    }
+
     pub fn set_failed(&mut self) {
         self.status = PostStatus::Failed;
+        self.scheduled_at = None;
         self.updated_at = Utc::now();
     }
 
+    /// Words in the rendered article, not counting Markdown syntax or code blocks
     pub fn word_count(&self) -> usize {
-        self.content.split_whitespace().count()
+        crate::preview::count_words(&self.content)
     }
 
     pub fn reading_time(&self) -> usize {
@@ -102,6 +322,11 @@ impl BlogPost {
         (words / 200).max(1)
     }
 
+    /// A private post loaded from disk whose content hasn't been decrypted yet
+    pub fn is_locked(&self) -> bool {
+        self.private && crate::private_post::is_encrypted(&self.content)
+    }
+
     pub fn is_ready_to_publish(&self) -> bool {
         !self.title.trim().is_empty() && !self.content.trim().is_empty()
     }
@@ -112,35 +337,84 @@ impl BlogPost {
         
         // Add YAML frontmatter
         content.push_str("---\n");
-        content.push_str(&format!("title: \"{}\"\n", self.title.replace('"', "\\\"")));
+        content.push_str(&format!("title: \"{}\"\n", escape_quoted(&self.title)));
         content.push_str(&format!("id: \"{}\"\n", self.id));
         content.push_str(&format!("created_at: \"{}\"\n", self.created_at.to_rfc3339()));
         content.push_str(&format!("updated_at: \"{}\"\n", self.updated_at.to_rfc3339()));
         content.push_str(&format!("status: \"{:?}\"\n", self.status));
         
         if let Some(summary) = &self.summary {
-            content.push_str(&format!("summary: \"{}\"\n", summary.replace('"', "\\\"")));
+            content.push_str(&format!("summary: \"{}\"\n", escape_quoted(summary)));
         }
         
         if !self.tags.is_empty() {
             content.push_str("tags:\n");
             for tag in &self.tags {
-                content.push_str(&format!("  - \"{}\"\n", tag.replace('"', "\\\"")));
+                content.push_str(&format!("  - \"{}\"\n", escape_quoted(tag)));
             }
         }
         
         if let Some(image_url) = &self.image_url {
-            content.push_str(&format!("image: \"{}\"\n", image_url));
+            content.push_str(&format!("image: \"{}\"\n", escape_quoted(image_url)));
+        }
+        
+        if !self.gallery_images.is_empty() {
+            content.push_str("gallery:\n");
+            for image in &self.gallery_images {
+                content.push_str(&format!("  - \"{}\"\n", escape_quoted(image)));
+            }
         }
         
         if let Some(event_id) = &self.nostr_event_id {
             content.push_str(&format!("nostr_event_id: \"{}\"\n", event_id));
         }
         
+        if let Some(published_at) = &self.published_at {
+            content.push_str(&format!("published_at: \"{}\"\n", published_at.to_rfc3339()));
+        }
+        
+        if let Some(published_by) = &self.published_by {
+            content.push_str(&format!("published_by: \"{}\"\n", published_by));
+        }
+        
+        if let Some(identifier) = &self.identifier {
+            content.push_str(&format!("identifier: \"{}\"\n", escape_quoted(identifier)));
+        }
+        
+        if let Some(scheduled_at) = &self.scheduled_at {
+            content.push_str(&format!("scheduled_at: \"{}\"\n", scheduled_at.to_rfc3339()));
+        }
+        
+        if self.unlisted {
+            content.push_str("unlisted: true\n");
+        }
+        
+        if self.pinned {
+            content.push_str("pinned: true\n");
+        }
+        
+        if self.private {
+            content.push_str("private: true\n");
+        }
+        
         if !self.published_relays.is_empty() {
             content.push_str("published_relays:\n");
             for relay in &self.published_relays {
-                content.push_str(&format!("  - \"{}\"\n", relay));
+                content.push_str(&format!("  - \"{}\"\n", escape_quoted(relay)));
+            }
+        }
+        
+        if !self.excluded_relays.is_empty() {
+            content.push_str("excluded_relays:\n");
+            for relay in &self.excluded_relays {
+                content.push_str(&format!("  - \"{}\"\n", escape_quoted(relay)));
+            }
+        }
+        
+        if !self.extra_relays.is_empty() {
+            content.push_str("extra_relays:\n");
+            for relay in &self.extra_relays {
+                content.push_str(&format!("  - \"{}\"\n", escape_quoted(relay)));
             }
         }
         
@@ -154,54 +428,142 @@ impl BlogPost {
 
     /// Parse a markdown file with frontmatter into a BlogPost
     pub fn from_markdown_with_frontmatter(content: &str, file_path: Option<PathBuf>) -> anyhow::Result<Self> {
+        Self::from_markdown_with_report(content, file_path).map(|(post, _)| post)
+    }
+
+    /// Parse a markdown file and describe how it was interpreted: which
+    /// fields were found, defaulted or ignored
+    pub fn from_markdown_with_report(content: &str, file_path: Option<PathBuf>) -> anyhow::Result<(Self, ImportReport)> {
         let mut post = BlogPost::default();
         post.file_path = file_path;
+        let mut report = ImportReport::default();
         
-        if content.starts_with("---\n") {
-            if let Some(end_pos) = content[4..].find("\n---\n") {
-                let frontmatter = &content[4..end_pos + 4];
-                let markdown_content = &content[end_pos + 8..];
-                
-                // Parse YAML frontmatter (simplified parsing)
-                post.content = markdown_content.to_string();
+        let frontmatter_end = if content.starts_with("---\n") {
+            content[4..].find("\n---\n").map(|end_pos| end_pos + 4)
+        } else {
+            None
+        };
+        
+        if let Some(end_pos) = frontmatter_end {
+            let frontmatter = &content[4..end_pos];
+            // Skip the closing `---` line and the blank line written after it
+            let markdown_content = &content[end_pos + 5..];
+            let markdown_content = markdown_content.strip_prefix('\n').unwrap_or(markdown_content);
+            
+            // Parse YAML frontmatter (simplified parsing)
+            post.content = markdown_content.to_string();
+            
+            // Key of the list that `  - item` lines belong to
+            let mut current_list: Option<&str> = None;
+            let mut found_title = false;
+            let mut found_status = false;
+            
+            for line in frontmatter.lines() {
+                if let Some(item) = line.trim_start().strip_prefix("- ") {
+                    let item = unquote(item.trim());
+                    match current_list {
+                        Some("tags") => post.add_tag(item),
+                        Some("published_relays") => post.published_relays.push(item),
+                        Some("excluded_relays") => post.excluded_relays.push(item),
+                        Some("extra_relays") => post.extra_relays.push(item),
+                        Some("gallery") => post.gallery_images.push(item),
+                        _ => {}
+                    }
+                    continue;
+                }
                 
-                for line in frontmatter.lines() {
-                    if let Some((key, value)) = line.split_once(':') {
-                        let key = key.trim();
-                        let value = value.trim().trim_matches('"');
-                        
-                        match key {
-                            "title" => post.title = value.to_string(),
-                            "id" => {
-                                if let Ok(uuid) = Uuid::parse_str(value) {
-                                    post.id = uuid;
-                                }
-                            }
-                            "summary" => post.summary = Some(value.to_string()),
-                            "image" => post.image_url = Some(value.to_string()),
-                            "nostr_event_id" => post.nostr_event_id = Some(value.to_string()),
-                            "status" => {
-                                post.status = match value {
-                                    "Published" => PostStatus::Published,
-                                    "Failed" => PostStatus::Failed,
-                                    _ => PostStatus::Draft,
-                                };
+                if let Some((key, value)) = split_frontmatter_line(line) {
+                    let key = key.trim();
+                    let value = unquote(value.trim());
+                    let value = value.as_str();
+                    current_list = Some(key);
+                    
+                    match key {
+                        "title" => {
+                            post.title = value.to_string();
+                            found_title = !value.is_empty();
+                        }
+                        "id" => {
+                            if let Ok(uuid) = Uuid::parse_str(value) {
+                                post.id = uuid;
+                            } else {
+                                report.note(format!("Id \"{}\" is not valid, a new one was generated", value));
                             }
-                            _ => {}
                         }
+                        "summary" => post.summary = Some(value.to_string()),
+                        "image" => post.image_url = Some(value.to_string()),
+                        "nostr_event_id" => post.nostr_event_id = Some(value.to_string()),
+                        "published_by" => post.published_by = Some(value.to_string()),
+                        "identifier" => post.identifier = Some(value.to_string()),
+                        "unlisted" => post.unlisted = value == "true",
+                        "pinned" => post.pinned = value == "true",
+                        "private" => post.private = value == "true",
+                        "created_at" | "updated_at" | "published_at" | "scheduled_at" => match DateTime::parse_from_rfc3339(value) {
+                            Ok(date) if key == "created_at" => post.created_at = date.with_timezone(&Utc),
+                            Ok(date) if key == "published_at" => post.published_at = Some(date.with_timezone(&Utc)),
+                            Ok(date) if key == "scheduled_at" => post.scheduled_at = Some(date.with_timezone(&Utc)),
+                            Ok(date) => post.updated_at = date.with_timezone(&Utc),
+                            Err(_) => report.note(format!("Date \"{}\" in {} is not valid, used the current time", value, key)),
+                        },
+                        "status" => {
+                            found_status = true;
+                            post.status = match value {
+                                "Published" => PostStatus::Published,
+                                "Failed" => PostStatus::Failed,
+                                "Draft" => PostStatus::Draft,
+                                _ => {
+                                    report.note(format!("Status \"{}\" not recognized, defaulted to Draft", value));
+                                    PostStatus::Draft
+                                }
+                            };
+                        }
+                        // Lists, filled in by the `  - item` lines that follow
+                        "tags" | "gallery" | "published_relays" | "excluded_relays" | "extra_relays" => {}
+                        _ => report.note(format!("Ignored unknown field \"{}\"", key)),
+                    }
+                } else if !line.trim().is_empty() {
+                    report.note(format!("Ignored unreadable frontmatter line \"{}\"", line.trim()));
+                }
+            }
+            
+            if !found_title {
+                match first_heading(&post.content) {
+                    Some(heading) => {
+                        post.title = heading;
+                        report.note("No title in frontmatter, used the first heading");
                     }
+                    None => report.note("No title found"),
                 }
             }
+            if !found_status {
+                report.note("No status found, defaulted to Draft");
+            }
         } else {
+            if content.starts_with("---\n") {
+                report.note("Frontmatter is not closed with ---, imported the whole file as content");
+            } else {
+                report.note("No frontmatter found, imported the whole file as content");
+            }
+            
             // No frontmatter, treat entire content as markdown
             post.content = content.to_string();
             // Try to extract title from first heading
-            if let Some(title_line) = content.lines().find(|line| line.starts_with("# ")) {
-                post.title = title_line[2..].trim().to_string();
+            match first_heading(content) {
+                Some(heading) => {
+                    post.title = heading;
+                    report.note("Title taken from the first heading");
+                }
+                None => report.note("No title found"),
             }
         }
         
-        Ok(post)
+        match post.tags.len() {
+            0 => {}
+            1 => report.note("1 tag imported"),
+            n => report.note(format!("{} tags imported", n)),
+        }
+        
+        Ok((post, report))
     }
 
     /// Generate a safe filename for the markdown file
@@ -221,6 +583,80 @@ impl BlogPost {
     }
 }
 
+/// Split a `key: value` line at the first colon followed by whitespace (or
+/// the end of the line), so colons inside values such as URLs are kept
+fn split_frontmatter_line(line: &str) -> Option<(&str, &str)> {
+    line.match_indices(':').find_map(|(index, _)| {
+        let value = &line[index + 1..];
+        (value.is_empty() || value.starts_with(char::is_whitespace)).then(|| (&line[..index], value))
+    })
+}
+
+/// Escape a value for a double-quoted frontmatter scalar
+fn escape_quoted(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            // Frontmatter is read line by line, so line breaks must stay escaped
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Read a frontmatter scalar, undoing `escape_quoted` if it's double-quoted.
+/// Other backslashes are kept as written.
+fn unquote(value: &str) -> String {
+    let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+        return value.to_string();
+    };
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&next @ ('"' | '\\'))) => {
+                unquoted.push(next);
+                chars.next();
+            }
+            ('\\', Some(&next @ ('n' | 'r' | 't'))) => {
+                unquoted.push(match next {
+                    'n' => '\n',
+                    'r' => '\r',
+                    _ => '\t',
+                });
+                chars.next();
+            }
+            _ => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+/// Text of the first `# ` heading, used as a title when none is given
+fn first_heading(content: &str) -> Option<String> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+}
+
+/// What an import found, defaulted or ignored, in the order it happened
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub notes: Vec<String>,
+}
+
+impl ImportReport {
+    fn note(&mut self, note: impl Into<String>) {
+        self.notes.push(note.into());
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NostrCredentials {
     pub private_key: String,
@@ -229,6 +665,14 @@ pub struct NostrCredentials {
     pub about: Option<String>,
     pub picture: Option<String>,
     pub nip05: Option<String>,
+    /// NIP-46 `bunker://` URI of a remote signer; `private_key` stays empty
+    /// when this is set
+    #[serde(default)]
+    pub bunker_uri: Option<String>,
+    /// Secret key (nsec) Blogster talks to the remote signer with. Generated
+    /// once per account so the signer keeps recognizing this app.
+    #[serde(default)]
+    pub bunker_app_key: Option<String>,
 }
 
 impl NostrCredentials {
@@ -240,6 +684,312 @@ impl NostrCredentials {
             about: None,
             picture: None,
             nip05: None,
+            bunker_uri: None,
+            bunker_app_key: None,
+        }
+    }
+
+    /// Credentials for a remote signer. The public key is filled in once
+    /// the signer has been reached.
+    pub fn remote(bunker_uri: String) -> Self {
+        let mut credentials = Self {
+            bunker_uri: Some(bunker_uri),
+            ..Self::new(String::new(), String::new())
+        };
+        credentials.ensure_bunker_app_key();
+        credentials
+    }
+
+    /// Give a remote signer account its app key if it has none yet.
+    /// Returns whether a key was generated and needs saving.
+    pub fn ensure_bunker_app_key(&mut self) -> bool {
+        if self.bunker_uri.is_none() || self.bunker_app_key.is_some() {
+            return false;
+        }
+        let keys = Keys::generate();
+        let secret_key = keys.secret_key().expect("generated keys have a secret key");
+        self.bunker_app_key = Some(secret_key.to_bech32().expect("secret keys encode as nsec"));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excluded_relays_round_trip() {
+        let mut post = BlogPost::new().with_title("Relay test".to_string());
+        post.excluded_relays = vec!["wss://test.relay".to_string()];
+        post.extra_relays = vec!["wss://niche.relay".to_string()];
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&post.to_markdown_with_frontmatter(), None).unwrap();
+        assert_eq!(reloaded.excluded_relays, post.excluded_relays);
+        assert_eq!(reloaded.extra_relays, post.extra_relays);
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let mut post = BlogPost::new()
+            .with_title("Round \"trip\" \\ back".to_string())
+            .with_content("# Heading\n\nBody text".to_string());
+        post.summary = Some("A \"quoted\" summary\"".to_string());
+        for tag in ["nostr", "rust", "\"writing\""] {
+            post.add_tag(tag.to_string());
         }
+        post.created_at = DateTime::parse_from_rfc3339("2024-03-01T09:30:00+00:00").unwrap().with_timezone(&Utc);
+        post.set_published(
+            "abc123".to_string(),
+            vec!["wss://relay.damus.io".to_string(), "wss://nos.lol".to_string()],
+            None,
+        );
+        post.published_by = Some("npub1example".to_string());
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&post.to_markdown_with_frontmatter(), None).unwrap();
+        assert_eq!(reloaded.id, post.id);
+        assert_eq!(reloaded.title, post.title);
+        assert_eq!(reloaded.content, post.content);
+        assert_eq!(reloaded.summary, post.summary);
+        assert_eq!(reloaded.tags, post.tags);
+        assert_eq!(reloaded.created_at, post.created_at);
+        assert_eq!(reloaded.updated_at, post.updated_at);
+        assert_eq!(reloaded.status, PostStatus::Published);
+        assert_eq!(reloaded.nostr_event_id, post.nostr_event_id);
+        assert_eq!(reloaded.published_relays, post.published_relays);
+        assert_eq!(reloaded.published_at, post.published_at);
+        assert!(reloaded.published_at.unwrap() > post.created_at);
+        assert_eq!(reloaded.published_by, post.published_by);
+    }
+
+    #[test]
+    fn test_republishing_keeps_published_at() {
+        let mut post = BlogPost::new().with_title("Update me".to_string());
+        post.created_at -= chrono::Duration::days(3);
+        assert!(!post.has_been_published());
+
+        // A draft written days ago is first published now, not when it was started
+        post.set_published("first".to_string(), vec![], None);
+        assert!(post.first_published_at() > post.created_at + chrono::Duration::days(2));
+        let first_published_at = post.first_published_at();
+
+        post.created_at = Utc::now() + chrono::Duration::days(1);
+        post.set_published("second".to_string(), vec![], None);
+        assert!(post.has_been_published());
+        assert_eq!(post.first_published_at(), first_published_at);
+    }
+
+    #[test]
+    fn test_post_published_before_dates_were_kept_uses_created_at() {
+        let mut post = BlogPost::new().with_title("Old article".to_string());
+        post.created_at -= chrono::Duration::days(30);
+        post.nostr_event_id = Some("older".to_string());
+        post.status = PostStatus::Published;
+        assert_eq!(post.published_at, None);
+
+        assert_eq!(post.first_published_at(), post.created_at);
+        post.set_published("newer".to_string(), vec![], None);
+        assert_eq!(post.published_at, Some(post.created_at));
+    }
+
+    #[test]
+    fn test_scheduled_post_round_trip() {
+        let now = Utc::now();
+        let mut post = BlogPost::new().with_title("Morning post".to_string());
+        post.scheduled_at = Some(now + chrono::Duration::hours(8));
+        assert!(!post.is_due(now));
+        assert!(post.is_due(now + chrono::Duration::hours(9)));
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&post.to_markdown_with_frontmatter(), None).unwrap();
+        assert_eq!(
+            reloaded.scheduled_at.map(|t| t.timestamp()),
+            post.scheduled_at.map(|t| t.timestamp())
+        );
+
+        post.set_published("abc123".to_string(), vec![], None);
+        assert_eq!(post.scheduled_at, None);
+    }
+
+    #[test]
+    fn test_apply_draft_keeps_newer_version() {
+        let mut local = BlogPost::new().with_title("Local".to_string()).with_content("Local text".to_string());
+        local.set_published("abc123".to_string(), vec![], None);
+
+        let mut older = local.clone().with_title("Older".to_string());
+        older.updated_at = local.updated_at - chrono::Duration::minutes(5);
+        assert!(!local.apply_draft(&older));
+        assert_eq!(local.title, "Local");
+
+        let mut newer = BlogPost::new().with_title("Newer".to_string()).with_content("Newer text".to_string());
+        newer.updated_at = local.updated_at + chrono::Duration::minutes(5);
+        assert!(local.apply_draft(&newer));
+        assert_eq!(local.title, "Newer");
+        assert_eq!(local.content, "Newer text");
+        assert_eq!(local.updated_at, newer.updated_at);
+        assert_eq!(local.nostr_event_id, Some("abc123".to_string()));
+    }
+
+    #[test]
+    fn test_colons_in_values_survive_reload() {
+        let mut post = BlogPost::new().with_title("Notes: part 2".to_string());
+        post.set_images(vec!["https://example.com/img.png".to_string(), "https://blossom.band/abc".to_string()]);
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&post.to_markdown_with_frontmatter(), None).unwrap();
+        assert_eq!(reloaded.image_url.as_deref(), Some("https://example.com/img.png"));
+        assert_eq!(reloaded.gallery_images, vec!["https://blossom.band/abc".to_string()]);
+        assert_eq!(reloaded.title, "Notes: part 2");
+
+        assert_eq!(split_frontmatter_line("image:https://x"), None);
+        assert_eq!(split_frontmatter_line("tags:"), Some(("tags", "")));
+    }
+
+    #[test]
+    fn test_line_breaks_and_quotes_survive_reload() {
+        let mut post = BlogPost::new().with_title("First line\nSecond \"line\"\twith a tab\r".to_string());
+        post.set_images(vec!["https://example.com/a\"b.png".to_string(), "https://example.com/c\\d.png".to_string()]);
+        post.extra_relays = vec!["wss://odd\"relay".to_string()];
+
+        let markdown = post.to_markdown_with_frontmatter();
+        assert!(markdown.contains("title: \"First line\\nSecond \\\"line\\\"\\twith a tab\\r\""));
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&markdown, None).unwrap();
+        assert_eq!(reloaded.title, post.title);
+        assert_eq!(reloaded.image_url, post.image_url);
+        assert_eq!(reloaded.gallery_images, post.gallery_images);
+        assert_eq!(reloaded.extra_relays, post.extra_relays);
+        assert_eq!(reloaded.content, post.content);
+    }
+
+    #[test]
+    fn test_private_flag_round_trip() {
+        let mut post = BlogPost::new().with_title("Diary".to_string());
+        post.private = true;
+        post.content = crate::private_post::encrypt_content("Dear diary", "passphrase").unwrap();
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&post.to_markdown_with_frontmatter(), None).unwrap();
+        assert!(reloaded.private);
+        assert!(reloaded.is_locked());
+        assert_eq!(reloaded.title, "Diary");
+    }
+
+    #[test]
+    fn test_pinned_round_trip() {
+        let mut post = BlogPost::new().with_title("Evergreen".to_string());
+        post.pinned = true;
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&post.to_markdown_with_frontmatter(), None).unwrap();
+        assert!(reloaded.pinned);
+
+        post.pinned = false;
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&post.to_markdown_with_frontmatter(), None).unwrap();
+        assert!(!reloaded.pinned);
+    }
+
+    #[test]
+    fn test_import_report() {
+        let content = "---\nstatus: \"Scheduled\"\nlayout: post\ntags:\n  - \"a\"\n  - \"b\"\n---\n\n# From heading\nBody";
+        let (post, report) = BlogPost::from_markdown_with_report(content, None).unwrap();
+
+        assert_eq!(post.title, "From heading");
+        assert_eq!(post.status, PostStatus::Draft);
+        assert_eq!(post.tags, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(
+            report.notes,
+            vec![
+                "Status \"Scheduled\" not recognized, defaulted to Draft".to_string(),
+                "Ignored unknown field \"layout\"".to_string(),
+                "No title in frontmatter, used the first heading".to_string(),
+                "2 tags imported".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_frontmatter_keeps_content() {
+        let (post, report) = BlogPost::from_markdown_with_report("---\ntitle: Oops\n# Heading\ntext", None).unwrap();
+        assert!(post.content.contains("text"));
+        assert_eq!(post.title, "Heading");
+        assert!(report.notes[0].contains("not closed"));
+    }
+
+    #[test]
+    fn test_gallery_round_trip_and_reorder() {
+        let mut post = BlogPost::new().with_title("Gallery".to_string());
+        post.set_images(vec![
+            "https://example.com/a.png".to_string(),
+            "https://example.com/b.png".to_string(),
+            "https://example.com/c.png".to_string(),
+        ]);
+        assert_eq!(post.image_url.as_deref(), Some("https://example.com/a.png"));
+
+        let reloaded = BlogPost::from_markdown_with_frontmatter(&post.to_markdown_with_frontmatter(), None).unwrap();
+        assert_eq!(reloaded.images(), post.images());
+
+        post.move_image(2, 0);
+        assert_eq!(post.image_url.as_deref(), Some("https://example.com/c.png"));
+        post.remove_image(0);
+        assert_eq!(post.image_url.as_deref(), Some("https://example.com/a.png"));
+        assert_eq!(post.gallery_images, vec!["https://example.com/b.png".to_string()]);
+    }
+
+    #[test]
+    fn test_duplicate_is_a_fresh_draft() {
+        let mut post = BlogPost::new().with_title("Original".to_string()).with_content("Body".to_string());
+        post.add_tag("nostr".to_string());
+        post.file_path = Some(PathBuf::from("original.md"));
+        post.set_published("event".to_string(), vec!["wss://relay.example".to_string()], None);
+
+        let copy = post.duplicate();
+        assert_ne!(copy.id, post.id);
+        assert_eq!(copy.title, "Original (copy)");
+        assert_eq!(copy.content, "Body");
+        assert_eq!(copy.tags, post.tags);
+        assert_eq!(copy.status, PostStatus::Draft);
+        assert!(copy.nostr_event_id.is_none() && copy.published_relays.is_empty() && copy.published_at.is_none());
+        assert!(copy.file_path.is_none());
+        assert_ne!(copy.generate_filename(), post.generate_filename());
+    }
+
+    #[test]
+    fn test_post_sort() {
+        let mut short = BlogPost::new().with_title("banana".to_string()).with_content("one two".to_string());
+        short.created_at -= chrono::Duration::days(1);
+        let long = BlogPost::new().with_title("Apple".to_string()).with_content("one two three".to_string());
+        let mut posts = vec![&short, &long];
+        let mut word_counts = WordCounts::default();
+
+        PostSort { key: PostSortKey::Title, descending: false }.sort(&mut posts, &mut word_counts);
+        assert_eq!(posts[0].title, "Apple");
+        PostSort { key: PostSortKey::WordCount, descending: true }.sort(&mut posts, &mut word_counts);
+        assert_eq!(posts[0].title, "Apple");
+        PostSort { key: PostSortKey::Created, descending: false }.sort(&mut posts, &mut word_counts);
+        assert_eq!(posts[0].title, "banana");
+    }
+
+    #[test]
+    fn test_word_counts_follow_edits() {
+        let mut word_counts = WordCounts::default();
+        let mut post = BlogPost::new().with_content("one two".to_string());
+        assert_eq!(word_counts.get(&post), 2);
+
+        // Same version: the remembered count is used
+        post.content = "one two three".to_string();
+        assert_eq!(word_counts.get(&post), 2);
+
+        post.updated_at += chrono::Duration::seconds(1);
+        assert_eq!(word_counts.get(&post), 3);
+    }
+
+    #[test]
+    fn test_word_count_ignores_markdown_syntax() {
+        let post = BlogPost::new().with_content(
+            "## Getting started\n\nRead the [setup guide](https://example.com/setup) and **then** the [FAQ](https://example.com/faq).\n\n```rust\nfn main() {\n    println!(\"hello\");\n}\n```\n\n![Screenshot](shot.png)\n\n- Run `cargo build` once"
+                .to_string(),
+        );
+        assert_eq!(post.word_count(), 14);
+
+        let code = format!("```\n{}\n```", "token ".repeat(1000));
+        let post = BlogPost::new().with_content(format!("{}\n\n{}", "word ".repeat(450), code));
+        assert_eq!(post.word_count(), 450);
+        assert_eq!(post.reading_time(), 2);
     }
 }
@@ -0,0 +1,127 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+
+/// Widths to check how an article wraps on different devices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PreviewWidth {
+    #[default]
+    Full,
+    Mobile,
+    Tablet,
+    Desktop,
+}
+
+impl PreviewWidth {
+    pub const ALL: [PreviewWidth; 4] = [PreviewWidth::Full, PreviewWidth::Mobile, PreviewWidth::Tablet, PreviewWidth::Desktop];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PreviewWidth::Full => "↔ Full width",
+            PreviewWidth::Mobile => "📱 Mobile (375px)",
+            PreviewWidth::Tablet => "📟 Tablet (768px)",
+            PreviewWidth::Desktop => "🖥 Desktop (1024px)",
+        }
+    }
+
+    /// Width the content is limited to, or `None` to use all available space
+    pub fn max_width(&self) -> Option<f32> {
+        match self {
+            PreviewWidth::Full => None,
+            PreviewWidth::Mobile => Some(375.0),
+            PreviewWidth::Tablet => Some(768.0),
+            PreviewWidth::Desktop => Some(1024.0),
+        }
+    }
+}
+
+/// End the current line, and with `blank` leave an empty line after it
+fn push_break(text: &mut String, blank: bool) {
+    let trimmed_len = text.trim_end_matches([' ', '\t']).len();
+    text.truncate(trimmed_len);
+    if text.is_empty() {
+        return;
+    }
+    let wanted = if blank { "\n\n" } else { "\n" };
+    while !text.ends_with(wanted) {
+        text.push('\n');
+    }
+}
+
+/// Remove Markdown syntax, leaving the text a reader would see: headings
+/// become plain lines, links their text and images their alt text
+pub fn strip_markdown(content: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut text = String::new();
+
+    for event in Parser::new_ext(content, options) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::FootnoteReference(label) => text.push_str(&format!("[{}]", label)),
+            Event::SoftBreak => text.push(' '),
+            Event::HardBreak => push_break(&mut text, false),
+            Event::Rule => push_break(&mut text, true),
+            Event::TaskListMarker(done) => text.push_str(if done { "[x] " } else { "[ ] " }),
+            Event::Start(Tag::Item) => {
+                push_break(&mut text, false);
+                text.push_str("• ");
+            }
+            Event::End(TagEnd::Item | TagEnd::TableHead | TagEnd::TableRow) => push_break(&mut text, false),
+            Event::End(TagEnd::TableCell) => text.push('\t'),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::CodeBlock
+                | TagEnd::BlockQuote(_)
+                | TagEnd::List(_)
+                | TagEnd::Table,
+            ) => push_break(&mut text, true),
+            _ => {}
+        }
+    }
+
+    text.trim_end().to_string()
+}
+
+/// Count the words a reader reads: Markdown syntax and link targets aren't
+/// words, and code blocks and image descriptions are skipped
+pub fn count_words(content: &str) -> usize {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut text = String::new();
+    // Nesting depth of code blocks and images
+    let mut skipping = 0usize;
+
+    for event in Parser::new_ext(content, options) {
+        match event {
+            Event::Start(Tag::CodeBlock(_) | Tag::Image { .. }) => skipping += 1,
+            Event::End(TagEnd::CodeBlock | TagEnd::Image) => skipping = skipping.saturating_sub(1),
+            Event::Text(t) | Event::Code(t) if skipping == 0 => text.push_str(&t),
+            // Words never continue across line breaks or blocks
+            Event::SoftBreak
+            | Event::HardBreak
+            | Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::TableCell
+                | TagEnd::BlockQuote(_)
+                | TagEnd::FootnoteDefinition,
+            ) => text.push(' '),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_markdown() {
+        let text = strip_markdown(
+            "# Title\n\nSome **bold** and [a link](https://example.com).\n\n![A cat](cat.png)\n\n- one\n- `two`\n\n> quoted",
+        );
+        assert_eq!(text, "Title\n\nSome bold and a link.\n\nA cat\n\n• one\n• two\n\nquoted");
+    }
+}
@@ -0,0 +1,119 @@
+use crate::credentials_file::{open, seal, sealed_salt, PassphraseKey};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Marks encrypted post content; followed by the salt, nonce and ciphertext
+const ENCRYPTED_PREFIX: &str = "blogster-encrypted:v1:";
+
+/// Whether post content on disk is an encrypted payload
+pub fn is_encrypted(content: &str) -> bool {
+    content.trim_start().starts_with(ENCRYPTED_PREFIX)
+}
+
+/// The private posts passphrase for this session and the keys derived from
+/// it. Each salt's key is derived once, and everything saved this session is
+/// encrypted with one salt, so saving and unlocking don't rerun Argon2.
+pub struct PrivateKeys {
+    passphrase: String,
+    write_key: PassphraseKey,
+    /// Keys for the salts of posts saved in earlier sessions
+    read_keys: Mutex<HashMap<Vec<u8>, PassphraseKey>>,
+}
+
+impl PrivateKeys {
+    /// Keys for `passphrase`. With `existing` content already encrypted, new
+    /// content reuses its salt so every post ends up sharing one key.
+    pub fn new(passphrase: String, existing: Option<&str>) -> Result<Self> {
+        let write_key = match existing.and_then(|content| sealed_salt(ENCRYPTED_PREFIX, content).ok()) {
+            Some(salt) => PassphraseKey::derive(&passphrase, &salt)?,
+            None => PassphraseKey::generate(&passphrase)?,
+        };
+        Ok(Self { passphrase, write_key, read_keys: Mutex::new(HashMap::new()) })
+    }
+
+    pub fn encrypt(&self, content: &str) -> Result<String> {
+        seal(ENCRYPTED_PREFIX, content, &self.write_key).context("Failed to encrypt post content")
+    }
+
+    pub fn decrypt(&self, content: &str) -> Result<String> {
+        let salt = sealed_salt(ENCRYPTED_PREFIX, content)?;
+        if salt == self.write_key.salt() {
+            return open(ENCRYPTED_PREFIX, content, &self.write_key);
+        }
+
+        let mut read_keys = self.read_keys.lock().unwrap_or_else(|e| e.into_inner());
+        let key = match read_keys.get(&salt) {
+            Some(key) => key.clone(),
+            None => {
+                let key = PassphraseKey::derive(&self.passphrase, &salt)?;
+                read_keys.insert(salt, key.clone());
+                key
+            }
+        };
+        open(ENCRYPTED_PREFIX, content, &key)
+    }
+}
+
+/// Encrypt post content with a key derived from `passphrase` and a fresh salt
+pub fn encrypt_content(content: &str, passphrase: &str) -> Result<String> {
+    PrivateKeys::new(passphrase.to_string(), None)?.encrypt(content)
+}
+
+/// Decrypt content written by `encrypt_content` or `PrivateKeys::encrypt`
+pub fn decrypt_content(content: &str, passphrase: &str) -> Result<String> {
+    PrivateKeys::new(passphrase.to_string(), Some(content))?.decrypt(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let encrypted = encrypt_content("# Secret\n\nNot for anyone else", "correct horse").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("Secret"));
+
+        assert_eq!(decrypt_content(&encrypted, "correct horse").unwrap(), "# Secret\n\nNot for anyone else");
+        assert!(decrypt_content(&encrypted, "wrong horse").is_err());
+    }
+
+    #[test]
+    fn test_empty_post_encrypts() {
+        let encrypted = encrypt_content("", "passphrase").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert_eq!(decrypt_content(&encrypted, "passphrase").unwrap(), "");
+    }
+
+    #[test]
+    fn test_long_post_encrypts() {
+        let content = "A long article. ".repeat(5_000);
+        assert!(content.len() > 64 * 1024);
+
+        let encrypted = encrypt_content(&content, "passphrase").unwrap();
+        assert_eq!(decrypt_content(&encrypted, "passphrase").unwrap(), content);
+    }
+
+    #[test]
+    fn test_session_keys_share_one_salt() {
+        let older = encrypt_content("From another session", "passphrase").unwrap();
+        let keys = PrivateKeys::new("passphrase".to_string(), Some(&older)).unwrap();
+
+        let newer = keys.encrypt("Saved today").unwrap();
+        assert_eq!(sealed_salt(ENCRYPTED_PREFIX, &newer).unwrap(), sealed_salt(ENCRYPTED_PREFIX, &older).unwrap());
+        assert_eq!(keys.decrypt(&older).unwrap(), "From another session");
+        assert_eq!(keys.decrypt(&newer).unwrap(), "Saved today");
+
+        let elsewhere = encrypt_content("Different salt", "passphrase").unwrap();
+        assert_eq!(keys.decrypt(&elsewhere).unwrap(), "Different salt");
+    }
+
+    #[test]
+    fn test_each_encryption_uses_a_new_salt() {
+        let first = encrypt_content("same", "passphrase").unwrap();
+        let second = encrypt_content("same", "passphrase").unwrap();
+        assert_ne!(first, second);
+        assert!(!is_encrypted("plain markdown"));
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+
+/// Width in pixels of one QR module
+const MODULE_PIXELS: usize = 8;
+/// Blank border required around a QR code, in modules
+const QUIET_ZONE: usize = 4;
+
+/// Render text as a black-on-white QR code image
+pub fn encode_qr(data: &str) -> Result<egui::ColorImage> {
+    let code = qrcode::QrCode::new(data.as_bytes()).context("Failed to encode QR code")?;
+    let width = code.width();
+    let modules = code.to_colors();
+
+    let size = (width + 2 * QUIET_ZONE) * MODULE_PIXELS;
+    let mut pixels = vec![egui::Color32::WHITE; size * size];
+    for (index, module) in modules.iter().enumerate() {
+        if *module != qrcode::Color::Dark {
+            continue;
+        }
+        let (row, column) = (index / width + QUIET_ZONE, index % width + QUIET_ZONE);
+        for y in row * MODULE_PIXELS..(row + 1) * MODULE_PIXELS {
+            for x in column * MODULE_PIXELS..(column + 1) * MODULE_PIXELS {
+                pixels[y * size + x] = egui::Color32::BLACK;
+            }
+        }
+    }
+
+    Ok(egui::ColorImage {
+        size: [size, size],
+        pixels,
+    })
+}
+
+/// Decode every QR code found in an image file
+pub fn decode_qr_image(path: &Path) -> Result<Vec<String>> {
+    let image = image::open(path)
+        .with_context(|| format!("Failed to open image {}", path.display()))?
+        .to_luma8();
+
+    let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+        image.width() as usize,
+        image.height() as usize,
+        |x, y| image.get_pixel(x as u32, y as u32)[0],
+    );
+
+    let contents: Vec<String> = prepared
+        .detect_grids()
+        .into_iter()
+        .filter_map(|grid| grid.decode().ok().map(|(_, content)| content))
+        .collect();
+
+    if contents.is_empty() {
+        return Err(anyhow!("No readable QR code found in the image"));
+    }
+
+    Ok(contents)
+}
+
+/// Pull an nsec out of decoded QR content, accepting a `nostr:` URI prefix
+pub fn extract_nsec(content: &str) -> Option<String> {
+    let content = content.trim();
+    let content = content.strip_prefix("nostr:").unwrap_or(content);
+
+    content.starts_with("nsec1").then(|| content.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_nsec() {
+        assert_eq!(extract_nsec(" nsec1abc \n"), Some("nsec1abc".to_string()));
+        assert_eq!(extract_nsec("nostr:nsec1abc"), Some("nsec1abc".to_string()));
+        assert_eq!(extract_nsec("npub1abc"), None);
+        assert_eq!(extract_nsec("https://example.com"), None);
+    }
+}
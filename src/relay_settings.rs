@@ -1,10 +1,75 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Well-known public relays offered as suggestions when adding a relay
+pub const POPULAR_RELAYS: &[&str] = &[
+    "wss://relay.damus.io",
+    "wss://nos.lol",
+    "wss://relay.nostr.band",
+    "wss://relay.primal.net",
+    "wss://nostr.wine",
+    "wss://relay.snort.social",
+    "wss://nostr-pub.wellorder.net",
+    "wss://nostr.mom",
+    "wss://offchain.pub",
+    "wss://relay.nostr.bg",
+    "wss://purplepag.es",
+    "wss://nostr.land",
+];
+
+/// Maximum number of suggestions shown while typing
+const MAX_SUGGESTIONS: usize = 6;
+
+/// A relay the user added, with what it's used for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomRelay {
+    pub url: String,
+    /// Used to look up events, e.g. when recovering posts
+    pub read: bool,
+    /// Posts, profiles and deletions are published here
+    pub write: bool,
+}
+
+impl CustomRelay {
+    pub fn new(url: String) -> Self {
+        Self { url, read: true, write: true }
+    }
+}
+
+/// Custom relays as stored: bare URLs from older settings files, or full entries
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredRelay {
+    Url(String),
+    Relay(CustomRelay),
+}
+
+/// Read custom relays, migrating bare URLs to read+write relays
+fn deserialize_custom_relays<'de, D>(deserializer: D) -> Result<Vec<CustomRelay>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let stored = Vec::<StoredRelay>::deserialize(deserializer)?;
+    Ok(stored
+        .into_iter()
+        .map(|relay| match relay {
+            StoredRelay::Url(url) => CustomRelay::new(url),
+            StoredRelay::Relay(relay) => relay,
+        })
+        .collect())
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RelaySettings {
-    pub custom_relays: Vec<String>,
+    #[serde(deserialize_with = "deserialize_custom_relays")]
+    pub custom_relays: Vec<CustomRelay>,
     pub use_default_relays: bool,
     pub use_custom_relays: bool,
+    /// Relays that are never used, even if they are defaults or custom relays
+    #[serde(default)]
+    pub blocked_relays: Vec<String>,
+    /// Every relay the user has added, kept after removal for suggestions
+    #[serde(default)]
+    pub relay_history: Vec<String>,
 }
 
 impl Default for RelaySettings {
@@ -13,6 +78,8 @@ impl Default for RelaySettings {
             custom_relays: Vec::new(),
             use_default_relays: true,
             use_custom_relays: false,
+            blocked_relays: Vec::new(),
+            relay_history: Vec::new(),
         }
     }
 }
@@ -33,8 +100,31 @@ impl RelaySettings {
         ]
     }
 
-    /// Get all active relays based on current settings
+    /// Get all active relays based on current settings, for reading and writing
     pub fn get_active_relays(&self) -> Vec<String> {
+        self.with_fallback(self.selected_relays(false))
+    }
+
+    /// Active relays that events are published to
+    pub fn get_write_relays(&self) -> Vec<String> {
+        self.with_fallback(self.selected_relays(true))
+    }
+
+    fn with_fallback(&self, mut relays: Vec<String>) -> Vec<String> {
+        // If no relays are selected, fall back to defaults
+        if relays.is_empty() {
+            relays = Self::get_default_relays();
+        }
+
+        // Blocked relays are never used, whichever list they came from
+        relays.retain(|relay| !self.is_blocked(relay));
+
+        relays
+    }
+
+    /// Relays chosen in the settings, without falling back to the defaults.
+    /// Default relays are used for both reading and writing.
+    fn selected_relays(&self, write_only: bool) -> Vec<String> {
         let mut relays = Vec::new();
 
         // Add default relays if enabled
@@ -44,22 +134,86 @@ impl RelaySettings {
 
         // Add custom relays if enabled
         if self.use_custom_relays {
-            relays.extend(self.custom_relays.clone());
+            relays.extend(
+                self.custom_relays
+                    .iter()
+                    .filter(|relay| if write_only { relay.write } else { relay.read || relay.write })
+                    .map(|relay| relay.url.clone()),
+            );
         }
 
-        // Remove duplicates and ensure we have at least one relay
+        // Remove duplicates
         relays.sort();
         relays.dedup();
 
-        // If no relays are selected, fall back to defaults
+        relays
+    }
+
+    /// The exact relays a post will be published to, or a message explaining
+    /// why publishing can't go ahead. Unlike `get_active_relays` this never
+    /// falls back to the defaults, so an empty selection is reported instead
+    /// of quietly publishing somewhere else. Only write relays are included,
+    /// plus the post's one-off `extra_relays`.
+    pub fn publish_relays(&self, excluded_relays: &[String], extra_relays: &[String]) -> Result<Vec<String>, String> {
+        let selected = self.selected_relays(true);
+        if selected.is_empty() && extra_relays.is_empty() {
+            return Err("No write relays are selected. Enable the default relays or a custom relay's Write option in Relay Settings.".to_string());
+        }
+
+        let relays: Vec<String> = selected
+            .into_iter()
+            .chain(extra_relays.iter().cloned())
+            .filter(|relay| !self.is_blocked(relay))
+            .collect();
         if relays.is_empty() {
-            relays = Self::get_default_relays();
+            return Err("Every selected relay is blocked. Unblock a relay in Relay Settings.".to_string());
         }
 
-        relays
+        let invalid: Vec<String> = relays
+            .iter()
+            .filter_map(|relay| Self::validate_relay_url(relay).err().map(|e| format!("{} ({})", relay, e)))
+            .collect();
+        if !invalid.is_empty() {
+            return Err(format!("Fix or remove invalid relay URLs in Relay Settings: {}", invalid.join(", ")));
+        }
+
+        let mut publish_relays: Vec<String> = Vec::new();
+        for relay in relays {
+            if !excluded_relays.contains(&relay) && !publish_relays.contains(&relay) {
+                publish_relays.push(relay);
+            }
+        }
+        if publish_relays.is_empty() {
+            return Err("All relays are unticked for this post. Tick at least one relay to publish to.".to_string());
+        }
+
+        Ok(publish_relays)
+    }
+
+    /// Check whether a relay is on the blocklist
+    pub fn is_blocked(&self, relay_url: &str) -> bool {
+        self.blocked_relays.iter().any(|r| r == relay_url)
+    }
+
+    /// Add a relay to the blocklist
+    pub fn block_relay(&mut self, relay_url: &str) {
+        if !self.is_blocked(relay_url) {
+            self.blocked_relays.push(relay_url.to_string());
+        }
+    }
+
+    /// Remove a relay from the blocklist
+    pub fn unblock_relay(&mut self, relay_url: &str) -> bool {
+        let before = self.blocked_relays.len();
+        self.blocked_relays.retain(|r| r != relay_url);
+        self.blocked_relays.len() != before
     }
 
-    /// Add a custom relay
+    pub fn has_custom_relay(&self, relay_url: &str) -> bool {
+        self.custom_relays.iter().any(|relay| relay.url == relay_url)
+    }
+
+    /// Add a custom relay, used for both reading and writing
     pub fn add_relay(&mut self, relay_url: String) -> Result<(), String> {
         // Basic validation
         if !relay_url.starts_with("wss://") && !relay_url.starts_with("ws://") {
@@ -71,17 +225,92 @@ impl RelaySettings {
         }
 
         // Check for duplicates
-        if self.custom_relays.contains(&relay_url) {
+        if self.has_custom_relay(&relay_url) {
             return Err("Relay already exists".to_string());
         }
 
-        self.custom_relays.push(relay_url);
+        if !self.relay_history.contains(&relay_url) {
+            self.relay_history.push(relay_url.clone());
+        }
+        self.custom_relays.push(CustomRelay::new(relay_url));
         Ok(())
     }
 
+    /// Add relays from an imported list as custom relays, keeping their
+    /// read/write flags, and turn the custom list on. Returns how many were
+    /// new; invalid URLs are skipped.
+    pub fn import_relays(&mut self, relays: Vec<CustomRelay>) -> usize {
+        let mut added = 0;
+        for relay in relays {
+            let url = Self::normalize_relay_url(&relay.url);
+            if Self::validate_relay_url(&url).is_ok() && self.add_relay(url).is_ok() {
+                if let Some(added_relay) = self.custom_relays.last_mut() {
+                    added_relay.read = relay.read;
+                    added_relay.write = relay.write;
+                }
+                added += 1;
+            }
+        }
+        if !self.custom_relays.is_empty() {
+            self.use_custom_relays = true;
+        }
+        added
+    }
+
+    /// Tidy up a typed relay address: add a missing `wss://`, map http(s) to
+    /// ws(s), lowercase the host and drop a trailing slash
+    pub fn normalize_relay_url(input: &str) -> String {
+        let input = input.trim();
+        let (scheme, rest) = match input.split_once("://") {
+            Some((scheme, rest)) => match scheme.to_lowercase().as_str() {
+                "ws" | "http" => ("ws", rest),
+                _ => ("wss", rest),
+            },
+            None => ("wss", input),
+        };
+
+        let (host, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+
+        format!("{}://{}{}", scheme, host.to_lowercase(), path.trim_end_matches('/'))
+    }
+
+    /// Known relays matching what has been typed so far, excluding ones
+    /// already in the custom list
+    pub fn suggestions(&self, input: &str) -> Vec<String> {
+        let query = input.trim().to_lowercase();
+        let query = query.split_once("://").map(|(_, rest)| rest.to_string()).unwrap_or(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut suggestions: Vec<String> = Vec::new();
+        let candidates = self
+            .relay_history
+            .iter()
+            .cloned()
+            .chain(POPULAR_RELAYS.iter().map(|r| r.to_string()));
+
+        for relay in candidates {
+            if relay.to_lowercase().contains(&query)
+                && !self.has_custom_relay(&relay)
+                && !suggestions.contains(&relay)
+            {
+                suggestions.push(relay);
+            }
+            if suggestions.len() == MAX_SUGGESTIONS {
+                break;
+            }
+        }
+
+        suggestions
+    }
+
     /// Remove a custom relay
     pub fn remove_relay(&mut self, relay_url: &str) -> bool {
-        if let Some(index) = self.custom_relays.iter().position(|r| r == relay_url) {
+        if let Some(index) = self.custom_relays.iter().position(|r| r.url == relay_url) {
             self.custom_relays.remove(index);
             true
         } else {
@@ -148,4 +377,120 @@ mod tests {
         let relays = settings.get_active_relays();
         assert_eq!(relays.len(), 6); // Default + custom
     }
+
+    #[test]
+    fn test_blocked_relays_excluded() {
+        let mut settings = RelaySettings::new();
+        settings.add_relay("wss://custom.relay.com".to_string()).unwrap();
+        settings.use_custom_relays = true;
+
+        settings.block_relay("wss://relay.damus.io");
+        settings.block_relay("wss://custom.relay.com");
+
+        let relays = settings.get_active_relays();
+        assert_eq!(relays.len(), 4);
+        assert!(!relays.contains(&"wss://relay.damus.io".to_string()));
+        assert!(!relays.contains(&"wss://custom.relay.com".to_string()));
+
+        // Blocklist also applies to the fallback defaults
+        settings.use_default_relays = false;
+        settings.use_custom_relays = false;
+        assert!(!settings.get_active_relays().contains(&"wss://relay.damus.io".to_string()));
+
+        assert!(settings.unblock_relay("wss://relay.damus.io"));
+        assert!(settings.get_active_relays().contains(&"wss://relay.damus.io".to_string()));
+    }
+
+    #[test]
+    fn test_blocklist_defaults_when_missing() {
+        let json = r#"{"custom_relays":[],"use_default_relays":true,"use_custom_relays":false}"#;
+        let settings: RelaySettings = serde_json::from_str(json).unwrap();
+        assert!(settings.blocked_relays.is_empty());
+    }
+
+    #[test]
+    fn test_import_relays_skips_duplicates_and_invalid() {
+        let mut settings = RelaySettings::new();
+        settings.add_relay("wss://nos.lol".to_string()).unwrap();
+
+        let added = settings.import_relays(vec![
+            CustomRelay::new("wss://nos.lol/".to_string()),
+            CustomRelay { url: "wss://Read.Relay.com".to_string(), read: true, write: false },
+            CustomRelay::new("not a relay".to_string()),
+        ]);
+        assert_eq!(added, 1);
+        assert!(settings.has_custom_relay("wss://nos.lol"));
+        assert_eq!(settings.custom_relays.len(), 2);
+        assert_eq!(
+            settings.custom_relays[1],
+            CustomRelay { url: "wss://read.relay.com".to_string(), read: true, write: false }
+        );
+        assert!(settings.use_custom_relays);
+    }
+
+    #[test]
+    fn test_bare_relay_urls_migrate_to_read_write() {
+        let json = r#"{"custom_relays":["wss://old.relay.com",{"url":"wss://read.relay.com","read":true,"write":false}],"use_default_relays":false,"use_custom_relays":true}"#;
+        let settings: RelaySettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.custom_relays[0], CustomRelay::new("wss://old.relay.com".to_string()));
+        assert!(!settings.custom_relays[1].write);
+
+        assert_eq!(settings.get_active_relays(), vec!["wss://old.relay.com".to_string(), "wss://read.relay.com".to_string()]);
+        assert_eq!(settings.get_write_relays(), vec!["wss://old.relay.com".to_string()]);
+        assert_eq!(settings.publish_relays(&[], &[]).unwrap(), vec!["wss://old.relay.com".to_string()]);
+    }
+
+    #[test]
+    fn test_normalize_relay_url() {
+        assert_eq!(RelaySettings::normalize_relay_url(" relay.Example.com/ "), "wss://relay.example.com");
+        assert_eq!(RelaySettings::normalize_relay_url("https://nos.lol"), "wss://nos.lol");
+        assert_eq!(RelaySettings::normalize_relay_url("ws://localhost:8080"), "ws://localhost:8080");
+        assert_eq!(RelaySettings::normalize_relay_url("wss://relay.example.com/inbox/"), "wss://relay.example.com/inbox");
+    }
+
+    #[test]
+    fn test_suggestions_include_history_and_skip_added() {
+        let mut settings = RelaySettings::new();
+        settings.add_relay("wss://my.private.relay".to_string()).unwrap();
+        settings.add_relay("wss://nos.lol".to_string()).unwrap();
+        settings.remove_relay("wss://my.private.relay");
+
+        assert_eq!(settings.suggestions("private"), vec!["wss://my.private.relay".to_string()]);
+        assert!(settings.suggestions("wss://nos.lol").is_empty());
+        assert!(settings.suggestions("damus").contains(&"wss://relay.damus.io".to_string()));
+        assert!(settings.suggestions("").is_empty());
+    }
+
+    #[test]
+    fn test_publish_relays_never_falls_back() {
+        let mut settings = RelaySettings::new();
+        settings.use_default_relays = false;
+        assert!(settings.publish_relays(&[], &[]).is_err());
+        assert_eq!(settings.get_active_relays().len(), 5);
+
+        settings.use_custom_relays = true;
+        settings.custom_relays = vec![
+            CustomRelay::new("wss://good.relay.com".to_string()),
+            CustomRelay::new("wss://bad".to_string()),
+        ];
+        let error = settings.publish_relays(&[], &[]).unwrap_err();
+        assert!(error.contains("wss://bad"));
+        assert!(!error.contains("good.relay.com"));
+
+        settings.custom_relays.pop();
+        assert_eq!(settings.publish_relays(&[], &[]).unwrap(), vec!["wss://good.relay.com".to_string()]);
+        assert!(settings.publish_relays(&["wss://good.relay.com".to_string()], &[]).is_err());
+
+        let niche = vec!["wss://niche.relay.com".to_string()];
+        assert_eq!(
+            settings.publish_relays(&["wss://good.relay.com".to_string()], &niche).unwrap(),
+            niche
+        );
+
+        settings.block_relay("wss://good.relay.com");
+        assert!(settings.publish_relays(&[], &[]).unwrap_err().contains("blocked"));
+
+        settings.use_custom_relays = false;
+        assert_eq!(settings.publish_relays(&[], &niche).unwrap(), niche);
+    }
 }
@@ -0,0 +1,400 @@
+use crate::post::BlogPost;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Maximum number of context snippets kept per post
+const MAX_SNIPPETS: usize = 3;
+/// Characters of context shown on each side of a match
+const SNIPPET_CONTEXT: usize = 40;
+
+/// A matching line within a post
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchSnippet {
+    /// 1-based line number in the post content
+    pub line_number: usize,
+    /// Character offset of the match within the whole content
+    pub char_offset: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub post_id: uuid::Uuid,
+    pub title: String,
+    pub title_matches: bool,
+    pub match_count: usize,
+    pub snippets: Vec<SearchSnippet>,
+}
+
+struct IndexedPost {
+    id: uuid::Uuid,
+    title: String,
+    title_lower: Vec<char>,
+    /// Lowercased content lines paired with the original line and the char
+    /// offset at which the line starts
+    lines: Vec<(Vec<char>, String, usize)>,
+}
+
+/// Lowercase one char at a time so positions in the result line up with the
+/// original text
+fn lowercase_chars(text: &str) -> Vec<char> {
+    text.chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect()
+}
+
+fn find_all(haystack: &[char], needle: &[char]) -> Vec<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return Vec::new();
+    }
+    (0..=haystack.len() - needle.len())
+        .filter(|&i| haystack[i..i + needle.len()] == *needle)
+        .collect()
+}
+
+fn snippet_around(line: &str, position: usize, needle_len: usize) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let start = position.saturating_sub(SNIPPET_CONTEXT);
+    let end = (position + needle_len + SNIPPET_CONTEXT).min(chars.len());
+
+    let mut snippet: String = chars[start..end].iter().collect();
+    snippet = snippet.trim().to_string();
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// In-memory full-text index over titles and bodies of all posts
+#[derive(Default)]
+pub struct SearchIndex {
+    posts: Vec<IndexedPost>,
+    /// Ids and modification times the index was built from
+    signature: Vec<(uuid::Uuid, DateTime<Utc>)>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild the index if any post was added, removed or modified since the
+    /// last build. Returns whether it was rebuilt.
+    pub fn update(&mut self, posts: &[BlogPost]) -> bool {
+        let signature: Vec<_> = posts.iter().map(|p| (p.id, p.updated_at)).collect();
+        if signature == self.signature {
+            return false;
+        }
+
+        self.posts = posts
+            .iter()
+            .map(|post| {
+                let mut offset = 0;
+                let lines = post
+                    .content
+                    .split('\n')
+                    .map(|line| {
+                        let entry = (lowercase_chars(line), line.to_string(), offset);
+                        offset += line.chars().count() + 1;
+                        entry
+                    })
+                    .collect();
+
+                IndexedPost {
+                    id: post.id,
+                    title: post.title.clone(),
+                    title_lower: lowercase_chars(&post.title),
+                    lines,
+                }
+            })
+            .collect();
+        self.signature = signature;
+        true
+    }
+
+    /// Case-insensitive search; results are ordered by number of matches
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        let needle = lowercase_chars(query.trim());
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results: Vec<SearchResult> = self
+            .posts
+            .iter()
+            .filter_map(|post| {
+                let title_count = find_all(&post.title_lower, &needle).len();
+                let mut match_count = title_count;
+                let mut snippets = Vec::new();
+
+                for (index, (lower, original, line_offset)) in post.lines.iter().enumerate() {
+                    let positions = find_all(lower, &needle);
+                    match_count += positions.len();
+
+                    if let Some(&first) = positions.first() {
+                        if snippets.len() < MAX_SNIPPETS {
+                            snippets.push(SearchSnippet {
+                                line_number: index + 1,
+                                char_offset: line_offset + first,
+                                text: snippet_around(original, first, needle.len()),
+                            });
+                        }
+                    }
+                }
+
+                (match_count > 0).then(|| SearchResult {
+                    post_id: post.id,
+                    title: post.title.clone(),
+                    title_matches: title_count > 0,
+                    match_count,
+                    snippets,
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.match_count.cmp(&a.match_count));
+        results
+    }
+}
+
+/// Lowercased words of `text`, split at anything that isn't a letter or digit
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Inverted index from words to the posts containing them, so the sidebar
+/// filter is a set lookup rather than a scan of every post
+#[derive(Default)]
+pub struct TokenIndex {
+    /// Sorted so all words starting with a prefix are found together
+    words: BTreeMap<String, HashSet<uuid::Uuid>>,
+    /// Lowercased tags without a leading `#`
+    tags: HashMap<String, HashSet<uuid::Uuid>>,
+    /// Ids, modification times and whether each post was still locked;
+    /// unlocking a post doesn't touch its modification time
+    signature: Vec<(uuid::Uuid, DateTime<Utc>, bool)>,
+}
+
+impl TokenIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild the index if any post was added, removed or modified since the
+    /// last build. Returns whether it was rebuilt.
+    pub fn update(&mut self, posts: &[BlogPost]) -> bool {
+        let signature: Vec<_> = posts.iter().map(|p| (p.id, p.updated_at, p.is_locked())).collect();
+        if signature == self.signature {
+            return false;
+        }
+
+        self.words.clear();
+        self.tags.clear();
+        for post in posts {
+            // The content of a locked private post is still encrypted
+            let content = if post.is_locked() { "" } else { post.content.as_str() };
+            let tags = post.tags.iter().map(String::as_str);
+            for word in tokenize(&post.title).chain(tokenize(content)).chain(tags.clone().flat_map(tokenize)) {
+                self.words.entry(word).or_default().insert(post.id);
+            }
+            for tag in tags {
+                let tag = tag.trim_start_matches('#').to_lowercase();
+                self.tags.entry(tag).or_default().insert(post.id);
+            }
+        }
+        self.signature = signature;
+        true
+    }
+
+    /// Posts with a word starting with `prefix`
+    fn with_prefix(&self, prefix: &str) -> HashSet<uuid::Uuid> {
+        self.words
+            .range(prefix.to_string()..)
+            .take_while(|(word, _)| word.starts_with(prefix))
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect()
+    }
+
+    /// Posts with `tag`, ignoring case and a leading `#`
+    fn with_tag(&self, tag: &str) -> HashSet<uuid::Uuid> {
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        self.tags.get(&tag).cloned().unwrap_or_default()
+    }
+
+    /// Every tag with the number of posts using it, most used first
+    pub fn tag_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<_> = self.tags.iter().map(|(tag, ids)| (tag.clone(), ids.len())).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Posts matching every term of `query` and having all of `tags`. Words
+    /// match by prefix and `tag:name` terms need that exact tag. Returns
+    /// `None` when there is nothing to filter by.
+    pub fn filter(&self, query: &str, tags: &[String]) -> Option<HashSet<uuid::Uuid>> {
+        let mut matches: Option<HashSet<uuid::Uuid>> = None;
+        let mut narrow = |ids: HashSet<uuid::Uuid>| {
+            matches = Some(match matches.take() {
+                Some(matches) => matches.intersection(&ids).copied().collect(),
+                None => ids,
+            });
+        };
+
+        for term in query.split_whitespace() {
+            match term.strip_prefix("tag:") {
+                Some(tag) if tag.trim_start_matches('#').is_empty() => {}
+                Some(tag) => narrow(self.with_tag(tag)),
+                // "nostr-sdk" is indexed as two words; both must be there
+                None => tokenize(term).for_each(|word| narrow(self.with_prefix(&word))),
+            }
+        }
+        for tag in tags {
+            narrow(self.with_tag(tag));
+        }
+
+        matches
+    }
+}
+
+/// Score how well `query` matches `candidate` when its characters appear in
+/// order but not necessarily together, as in a command palette. Matches at
+/// the start of words and runs of adjacent characters score higher. `None`
+/// means the query doesn't match at all.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let candidate = lowercase_chars(candidate);
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match = None;
+
+    for q in lowercase_chars(query).into_iter().filter(|c| !c.is_whitespace()) {
+        let index = position + candidate[position..].iter().position(|&c| c == q)?;
+        score += 1;
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 2;
+        }
+        previous_match = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_counts_matches_and_offsets() {
+        let mut post = BlogPost::new();
+        post.title = "Lightning notes".to_string();
+        post.content = "Intro\nWhy LIGHTNING matters, lightning everywhere\nEnd".to_string();
+
+        let mut other = BlogPost::new();
+        other.title = "Unrelated".to_string();
+
+        let mut index = SearchIndex::new();
+        index.update(&[post.clone(), other]);
+
+        let results = index.search("lightning");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].post_id, post.id);
+        assert!(results[0].title_matches);
+        assert_eq!(results[0].match_count, 3);
+
+        let snippet = &results[0].snippets[0];
+        assert_eq!(snippet.line_number, 2);
+        assert_eq!(snippet.char_offset, "Intro\nWhy ".chars().count());
+    }
+
+    #[test]
+    fn test_index_rebuilds_when_posts_change() {
+        let mut post = BlogPost::new();
+        post.content = "first draft".to_string();
+
+        let mut index = SearchIndex::new();
+        index.update(&[post.clone()]);
+        assert!(index.search("second").is_empty());
+
+        post.content = "second draft".to_string();
+        post.updated_at += chrono::Duration::seconds(1);
+        index.update(&[post]);
+        assert_eq!(index.search("second").len(), 1);
+    }
+
+    #[test]
+    fn test_token_index_and_queries() {
+        let mut rust = BlogPost::new();
+        rust.title = "Writing Rust".to_string();
+        rust.content = "Ownership and the borrow checker".to_string();
+        rust.tags = vec!["rust".to_string(), "programming".to_string()];
+
+        let mut nostr = BlogPost::new();
+        nostr.title = "Relays explained".to_string();
+        nostr.content = "Why nostr-sdk makes relays easy, even in Rust".to_string();
+        nostr.tags = vec!["nostr".to_string()];
+
+        let mut index = TokenIndex::new();
+        index.update(&[rust.clone(), nostr.clone()]);
+
+        assert_eq!(index.filter("  ", &[]), None);
+        assert_eq!(index.filter("rust", &[]).unwrap(), HashSet::from([rust.id, nostr.id]));
+        assert_eq!(index.filter("tag:Rust", &[]).unwrap(), HashSet::from([rust.id]));
+        assert_eq!(index.filter("borr RUST", &[]).unwrap(), HashSet::from([rust.id]));
+        assert_eq!(index.filter("nostr-sdk relays", &[]).unwrap(), HashSet::from([nostr.id]));
+        assert!(index.filter("tag:nostr borrow", &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_token_index_rebuilds_when_post_unlocked() {
+        let mut post = BlogPost::new();
+        post.private = true;
+        post.content = crate::private_post::encrypt_content("Dear diary", "passphrase").unwrap();
+
+        let mut index = TokenIndex::new();
+        index.update(&[post.clone()]);
+        assert!(index.filter("diary", &[]).unwrap().is_empty());
+
+        post.content = "Dear diary".to_string();
+        assert!(index.update(&[post.clone()]));
+        assert_eq!(index.filter("diary", &[]).unwrap(), HashSet::from([post.id]));
+    }
+
+    #[test]
+    fn test_tag_filters_combine_with_query() {
+        let mut first = BlogPost::new();
+        first.content = "Zaps and relays".to_string();
+        first.tags = vec!["Nostr".to_string(), "bitcoin".to_string()];
+
+        let mut second = BlogPost::new();
+        second.content = "Relays only".to_string();
+        second.tags = vec!["nostr".to_string()];
+
+        let mut index = TokenIndex::new();
+        index.update(&[first.clone(), second.clone()]);
+
+        assert_eq!(index.tag_counts(), vec![("nostr".to_string(), 2), ("bitcoin".to_string(), 1)]);
+        let nostr = vec!["nostr".to_string()];
+        assert_eq!(index.filter("", &nostr).unwrap(), HashSet::from([first.id, second.id]));
+        assert_eq!(index.filter("zaps", &nostr).unwrap(), HashSet::from([first.id]));
+        let both = vec!["nostr".to_string(), "bitcoin".to_string()];
+        assert_eq!(index.filter("relays", &both).unwrap(), HashSet::from([first.id]));
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts() {
+        assert_eq!(fuzzy_score("", "New Post"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "New Post"), None);
+        assert_eq!(fuzzy_score("tsop", "New Post"), None);
+
+        let new_post = fuzzy_score("np", "New Post").unwrap();
+        let open_folder = fuzzy_score("np", "Open Posts Folder").unwrap();
+        assert!(new_post > open_folder);
+        assert!(fuzzy_score("relay set", "Relay Settings") > fuzzy_score("relay set", "Reconnect Relays"));
+    }
+}
@@ -1,15 +1,68 @@
+use crate::app_settings::AppSettings;
 use crate::blossom_client::BlossomSettings;
-use crate::post::{BlogPost, NostrCredentials};
+use crate::credentials_file::{self, CredentialsLocked};
+use crate::html_export::{render_html, render_site_index, render_site_page, site_page_name, site_stylesheet, HtmlExportOptions, SITE_STYLESHEET};
+use crate::post::{BlogPost, ImportReport, NostrCredentials};
+use crate::private_post::PrivateKeys;
 use crate::relay_settings::RelaySettings;
-use crate::theme::{Theme, CustomThemeColors};
+use crate::theme::{Theme, ThemeColors, CustomThemeColors};
+use crate::writing_stats::WritingStats;
 use anyhow::{Context, Result};
 use base64::Engine;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Read a text file that may not be UTF-8 (e.g. a Latin-1 export from an
+/// old blog), detecting its encoding and transcoding it to UTF-8
+pub fn read_text_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Ok(text.to_string());
+    }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    let encoding = detector.guess(None, true);
+
+    let (text, _, had_errors) = encoding.decode(bytes);
+    if had_errors {
+        tracing::warn!(
+            "{} isn't valid {}; some characters were replaced",
+            path.display(),
+            encoding.name()
+        );
+    } else {
+        tracing::info!("Transcoded {} from {} to UTF-8", path.display(), encoding.name());
+    }
+    Ok(text.into_owned())
+}
+
+/// Label of the account stored under the original, unlabeled credentials
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// Saved accounts. The keyring can't be enumerated, so the labels are
+/// tracked separately from the credentials themselves.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct AccountIndex {
+    accounts: Vec<String>,
+    /// Account to sign in with; `None` means the default account
+    active: Option<String>,
+}
+
 pub struct Storage {
     posts_dir: PathBuf,
     config_dir: PathBuf,
+    /// Overrides the default crash-recovery directory
+    custom_recovery_dir: Option<PathBuf>,
+    /// Keys for private posts, derived from the passphrase; only ever kept in memory
+    private_keys: Option<PrivateKeys>,
+    /// Passphrase the credentials file fallback is encrypted with; only ever kept in memory
+    credentials_passphrase: Option<String>,
 }
 
 impl Storage {
@@ -38,6 +91,9 @@ impl Storage {
         Ok(Self {
             posts_dir,
             config_dir,
+            custom_recovery_dir: None,
+            private_keys: None,
+            credentials_passphrase: None,
         })
     }
 
@@ -88,15 +144,34 @@ impl Storage {
         Ok(())
     }
 
-    /// Save a blog post as a markdown file
+    /// Save a blog post as a markdown file. Private posts have their content
+    /// encrypted; title and other frontmatter stay readable for listing.
     pub fn save_post(&self, post: &BlogPost) -> Result<PathBuf> {
         let filename = post.generate_filename();
         let file_path = self.posts_dir.join(&filename);
         
-        let content = post.to_markdown_with_frontmatter();
+        let content = if post.private && !post.is_locked() {
+            let keys = self.private_keys
+                .as_ref()
+                .context("Set the private posts passphrase before saving a private post")?;
+            let mut encrypted = post.clone();
+            encrypted.content = keys.encrypt(&post.content)?;
+            encrypted.to_markdown_with_frontmatter()
+        } else {
+            post.to_markdown_with_frontmatter()
+        };
         fs::write(&file_path, content)
             .with_context(|| format!("Failed to save post to {}", file_path.display()))?;
         
+        // A new title means a new file name; don't leave the old file behind
+        if let Some(old_path) = post.file_path.as_ref().filter(|old_path| **old_path != file_path) {
+            if old_path.starts_with(&self.posts_dir) && old_path.exists() {
+                if let Err(e) = fs::remove_file(old_path) {
+                    tracing::warn!("Failed to remove {}: {}", old_path.display(), e);
+                }
+            }
+        }
+        
         tracing::info!("Saved post '{}' to {}", post.title, file_path.display());
         Ok(file_path)
     }
@@ -109,6 +184,28 @@ impl Storage {
         BlogPost::from_markdown_with_frontmatter(&content, Some(file_path.to_path_buf()))
     }
 
+    /// Remember the keys private posts are encrypted with for this session
+    pub fn set_private_keys(&mut self, keys: Option<PrivateKeys>) {
+        self.private_keys = keys;
+    }
+
+    pub fn has_private_passphrase(&self) -> bool {
+        self.private_keys.is_some()
+    }
+
+    /// Decrypt a locked private post in place with the session passphrase
+    pub fn unlock_post(&self, post: &mut BlogPost) -> Result<()> {
+        if !post.is_locked() {
+            return Ok(());
+        }
+        let keys = self.private_keys
+            .as_ref()
+            .context("Enter the private posts passphrase first")?;
+        post.content = keys.decrypt(&post.content)
+            .with_context(|| format!("Failed to unlock \"{}\"", post.title))?;
+        Ok(())
+    }
+
     /// Load all blog posts from the posts directory
     pub fn load_all_posts(&self) -> Result<Vec<BlogPost>> {
         let mut posts = Vec::new();
@@ -143,29 +240,214 @@ impl Storage {
         Ok(posts)
     }
 
-    /// Delete a blog post file
+    /// Move a blog post file to the trash, from where it can be restored
     pub fn delete_post(&self, post: &BlogPost) -> Result<()> {
-        if let Some(file_path) = &post.file_path {
-            if file_path.exists() {
-                fs::remove_file(file_path)
-                    .with_context(|| format!("Failed to delete post file {}", file_path.display()))?;
-                tracing::info!("Deleted post file: {}", file_path.display());
+        // If no file path is stored, try to find it by generated filename
+        let file_path = post
+            .file_path
+            .clone()
+            .unwrap_or_else(|| self.posts_dir.join(post.generate_filename()));
+        if !file_path.exists() {
+            return Ok(());
+        }
+
+        let trash_dir = self.trash_dir();
+        fs::create_dir_all(&trash_dir)
+            .context("Failed to create trash directory")?;
+        let file_name = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| post.generate_filename());
+        let trashed_path = trash_dir.join(trash_file_name(&file_name, chrono::Local::now().naive_local()));
+        fs::rename(&file_path, &trashed_path)
+            .with_context(|| format!("Failed to move post file {} to the trash", file_path.display()))?;
+
+        tracing::info!("Moved post file {} to the trash", file_path.display());
+        Ok(())
+    }
+
+    fn trash_dir(&self) -> PathBuf {
+        self.posts_dir.join(TRASH_DIR)
+    }
+
+    /// Posts in the trash, most recently deleted first
+    pub fn list_trash(&self) -> Result<Vec<TrashedPost>> {
+        let trash_dir = self.trash_dir();
+        if !trash_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut trashed = Vec::new();
+        for entry in fs::read_dir(&trash_dir).context("Failed to read trash directory")? {
+            let path = entry.context("Failed to read directory entry")?.path();
+            let Some((_, deleted_at)) = path
+                .file_name()
+                .and_then(|name| parse_trash_file_name(&name.to_string_lossy()))
+            else {
+                continue;
+            };
+            match self.load_post(&path) {
+                Ok(post) => trashed.push(TrashedPost { post, deleted_at, path }),
+                Err(e) => tracing::warn!("Skipping unreadable trashed post {}: {}", path.display(), e),
             }
+        }
+
+        trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(trashed)
+    }
+
+    /// Move the most recently trashed copy of a post back to the posts
+    /// directory and load it
+    pub fn restore_post(&self, id: uuid::Uuid) -> Result<BlogPost> {
+        let trashed = self
+            .list_trash()?
+            .into_iter()
+            .find(|t| t.post.id == id)
+            .context("The post is no longer in the trash")?;
+        let file_name = trashed
+            .path
+            .file_name()
+            .and_then(|name| parse_trash_file_name(&name.to_string_lossy()))
+            .map(|(original, _)| original)
+            .unwrap_or_else(|| trashed.post.generate_filename());
+
+        let file_path = self.posts_dir.join(file_name);
+        if file_path.exists() {
+            anyhow::bail!("{} already exists", file_path.display());
+        }
+        fs::rename(&trashed.path, &file_path)
+            .with_context(|| format!("Failed to restore {}", trashed.path.display()))?;
+
+        tracing::info!("Restored post '{}' from the trash", trashed.post.title);
+        self.load_post(&file_path)
+    }
+
+    /// Permanently delete every trashed copy of a post
+    pub fn delete_from_trash(&self, id: uuid::Uuid) -> Result<()> {
+        for trashed in self.list_trash()?.into_iter().filter(|t| t.post.id == id) {
+            fs::remove_file(&trashed.path)
+                .with_context(|| format!("Failed to delete {}", trashed.path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Permanently delete everything in the trash. Returns how many posts were removed.
+    pub fn empty_trash(&self) -> Result<usize> {
+        let trashed = self.list_trash()?;
+        for trashed in &trashed {
+            fs::remove_file(&trashed.path)
+                .with_context(|| format!("Failed to delete {}", trashed.path.display()))?;
+        }
+        tracing::info!("Emptied the trash ({} posts)", trashed.len());
+        Ok(trashed.len())
+    }
+
+    /// Keyring username the credentials of `account` are stored under. The
+    /// default account keeps the original unlabeled entry.
+    fn credentials_key(account: &str) -> String {
+        if account == DEFAULT_ACCOUNT {
+            "nostr_credentials".to_string()
         } else {
-            // If no file path is stored, try to find and delete by generated filename
-            let filename = post.generate_filename();
-            let file_path = self.posts_dir.join(&filename);
-            if file_path.exists() {
-                fs::remove_file(&file_path)
-                    .with_context(|| format!("Failed to delete post file {}", file_path.display()))?;
-                tracing::info!("Deleted post file: {}", file_path.display());
-            }
+            format!("nostr_credentials::{}", account)
+        }
+    }
+
+    /// Fallback file holding the credentials of `account`
+    fn credentials_path(&self, account: &str) -> PathBuf {
+        if account == DEFAULT_ACCOUNT {
+            self.config_dir.join("credentials.enc")
+        } else {
+            self.config_dir.join(format!("credentials-{}.enc", account))
         }
+    }
+
+    /// Check an account label before credentials are stored under it
+    pub fn validate_account_label(label: &str) -> Result<(), String> {
+        if label.is_empty() {
+            return Err("Please enter an account name".to_string());
+        }
+        if label.len() > 32 {
+            return Err("Account names can be at most 32 characters".to_string());
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err("Account names can only use letters, digits, '-' and '_'".to_string());
+        }
+        Ok(())
+    }
+
+    fn load_account_index(&self) -> AccountIndex {
+        let index_path = self.config_dir.join("accounts.json");
+
+        if !index_path.exists() {
+            // Installs from before named accounts only have the default one
+            let accounts = match self.load_credentials(DEFAULT_ACCOUNT) {
+                Ok(Some(_)) => vec![DEFAULT_ACCOUNT.to_string()],
+                _ => Vec::new(),
+            };
+            return AccountIndex { accounts, active: None };
+        }
+
+        fs::read_to_string(&index_path)
+            .context("Failed to read accounts")
+            .and_then(|content| serde_json::from_str(&content).context("Failed to parse accounts"))
+            .unwrap_or_else(|e| {
+                tracing::warn!("{:#}", e);
+                AccountIndex::default()
+            })
+    }
+
+    fn save_account_index(&self, index: &AccountIndex) -> Result<()> {
+        let index_path = self.config_dir.join("accounts.json");
+        let content = serde_json::to_string_pretty(index)
+            .context("Failed to serialize accounts")?;
+
+        fs::write(&index_path, content)
+            .with_context(|| format!("Failed to write accounts to {}", index_path.display()))?;
+
         Ok(())
     }
 
-    /// Save Nostr credentials securely using keyring with file fallback
-    pub fn save_credentials(&self, credentials: &NostrCredentials) -> Result<()> {
+    /// Labels of all accounts with saved credentials, in the order they were added
+    pub fn list_accounts(&self) -> Vec<String> {
+        self.load_account_index().accounts
+    }
+
+    /// The account signed in at startup
+    pub fn active_account(&self) -> String {
+        self.load_account_index()
+            .active
+            .unwrap_or_else(|| DEFAULT_ACCOUNT.to_string())
+    }
+
+    /// Remember which account to sign in with
+    pub fn set_active_account(&self, account: &str) -> Result<()> {
+        let mut index = self.load_account_index();
+        index.active = Some(account.to_string());
+        self.save_account_index(&index)?;
+        tracing::info!("Switched to account \"{}\"", account);
+        Ok(())
+    }
+
+    /// Remember the passphrase the credentials file is encrypted with for this session
+    pub fn set_credentials_passphrase(&mut self, passphrase: Option<String>) {
+        self.credentials_passphrase = passphrase;
+    }
+
+    pub fn has_credentials_passphrase(&self) -> bool {
+        self.credentials_passphrase.is_some()
+    }
+
+    /// Save Nostr credentials for `account` securely using keyring with file fallback
+    pub fn save_credentials(&self, account: &str, credentials: &NostrCredentials) -> Result<()> {
+        Self::validate_account_label(account).map_err(anyhow::Error::msg)?;
+
+        // A remote signer holds the key; never write one alongside its URI
+        let mut credentials = credentials.clone();
+        if credentials.bunker_uri.is_some() {
+            credentials.private_key.clear();
+        }
+        let credentials = &credentials;
+
         let json = serde_json::to_string(credentials)
             .context("Failed to serialize credentials")?;
         
@@ -173,7 +455,7 @@ impl Storage {
         let mut file_success = false;
 
         // Try keyring first
-        let entry = keyring::Entry::new("blogster", "nostr_credentials")
+        let entry = keyring::Entry::new("blogster", &Self::credentials_key(account))
             .context("Failed to create keyring entry")?;
         
         match entry.set_password(&json) {
@@ -186,28 +468,60 @@ impl Storage {
             }
         }
         
-        // ALWAYS also save to file as backup (in case keyring is MockCredential)
-        match self.save_credentials_to_file(credentials) {
-            Ok(()) => {
-                file_success = true;
+        // Also save an encrypted file as backup (in case keyring is MockCredential)
+        if self.credentials_passphrase.is_some() {
+            match self.save_credentials_to_file(account, credentials) {
+                Ok(()) => {
+                    file_success = true;
+                }
+                Err(e) => {
+                    tracing::warn!("File backup failed: {}", e);
+                }
             }
-            Err(e) => {
-                tracing::warn!("File backup failed: {}", e);
+        } else {
+            tracing::info!("No credentials passphrase set, skipping the file backup");
+            if keyring_success {
+                // Don't leave an outdated or unencrypted copy behind
+                if let Err(e) = self.delete_fallback_credentials(account) {
+                    tracing::warn!("Failed to remove old credentials file: {:#}", e);
+                }
             }
         }
         
         // Return success if either method worked
-        if keyring_success || file_success {
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("Both keyring and file storage failed"))
+        if !keyring_success && !file_success {
+            if self.credentials_passphrase.is_none() {
+                return Err(anyhow::anyhow!("The system keyring is unavailable. Set a passphrase to store your credentials in an encrypted file instead."));
+            }
+            return Err(anyhow::anyhow!("Both keyring and file storage failed"));
         }
+
+        let mut index = self.load_account_index();
+        if !index.accounts.iter().any(|a| a == account) {
+            index.accounts.push(account.to_string());
+            self.save_account_index(&index)?;
+        }
+        Ok(())
     }
 
-    /// Load Nostr credentials from keyring with file fallback
-    pub fn load_credentials(&self) -> Result<Option<NostrCredentials>> {
+    /// Load the Nostr credentials of `account` from keyring with file fallback
+    pub fn load_credentials(&self, account: &str) -> Result<Option<NostrCredentials>> {
+        let mut credentials = self.read_credentials(account)?;
+        // Remote signer accounts saved before app keys were kept get one now,
+        // so every later session reuses it
+        if let Some(credentials) = credentials.as_mut() {
+            if credentials.ensure_bunker_app_key() {
+                if let Err(e) = self.save_credentials(account, credentials) {
+                    tracing::warn!("Failed to save the remote signer app key: {:#}", e);
+                }
+            }
+        }
+        Ok(credentials)
+    }
+
+    fn read_credentials(&self, account: &str) -> Result<Option<NostrCredentials>> {
         // Try keyring first
-        let entry = keyring::Entry::new("blogster", "nostr_credentials")
+        let entry = keyring::Entry::new("blogster", &Self::credentials_key(account))
             .context("Failed to create keyring entry")?;
         
         match entry.get_password() {
@@ -219,22 +533,22 @@ impl Storage {
             }
             Err(keyring::Error::NoEntry) => {
                 tracing::debug!("No credentials in keyring, trying file fallback");
-                self.load_credentials_from_file()
+                self.load_credentials_from_file(account)
             }
             Err(e) => {
                 tracing::warn!("Keyring failed ({}), trying file fallback", e);
-                self.load_credentials_from_file()
+                self.load_credentials_from_file(account)
             }
         }
     }
 
-    /// Delete stored Nostr credentials from both keyring and file
-    pub fn delete_credentials(&self) -> Result<()> {
+    /// Delete the stored Nostr credentials of `account` from both keyring and file
+    pub fn delete_credentials(&self, account: &str) -> Result<()> {
         let mut keyring_result = Ok(());
         let mut file_result = Ok(());
 
         // Try to delete from keyring
-        let entry = keyring::Entry::new("blogster", "nostr_credentials")
+        let entry = keyring::Entry::new("blogster", &Self::credentials_key(account))
             .context("Failed to create keyring entry")?;
         
         match entry.delete_credential() {
@@ -251,60 +565,65 @@ impl Storage {
         }
 
         // Also try to delete from file fallback
-        file_result = self.delete_fallback_credentials();
+        file_result = self.delete_fallback_credentials(account);
 
         // Return success if either method worked
-        if keyring_result.is_ok() || file_result.is_ok() {
-            Ok(())
-        } else {
-            keyring_result
+        if keyring_result.is_err() && file_result.is_err() {
+            return keyring_result;
+        }
+
+        let mut index = self.load_account_index();
+        index.accounts.retain(|a| a != account);
+        if index.active.as_deref() == Some(account) {
+            index.active = None;
         }
+        self.save_account_index(&index)
     }
 
-    /// Save credentials to encrypted file as fallback
-    fn save_credentials_to_file(&self, credentials: &NostrCredentials) -> Result<()> {
-        eprintln!("🔧 DEBUG: save_credentials_to_file called");
-        let credentials_path = self.config_dir.join("credentials.enc");
-        eprintln!("🔧 DEBUG: Saving to path: {:?}", credentials_path);
+    /// Save credentials to a passphrase-encrypted file as fallback
+    fn save_credentials_to_file(&self, account: &str, credentials: &NostrCredentials) -> Result<()> {
+        let passphrase = self.credentials_passphrase
+            .as_deref()
+            .context("Set a passphrase before saving credentials to a file")?;
+        let credentials_path = self.credentials_path(account);
         
         let json = serde_json::to_string(credentials)
             .context("Failed to serialize credentials")?;
-        eprintln!("🔧 DEBUG: Serialized JSON: {}", json);
+        let encrypted = credentials_file::encrypt(&json, passphrase)?;
         
-        // Simple base64 encoding (not encryption, but obscures the data)
-        let encoded = base64::prelude::BASE64_STANDARD.encode(json.as_bytes());
-        eprintln!("🔧 DEBUG: Encoded length: {}", encoded.len());
+        fs::write(&credentials_path, encrypted)
+            .with_context(|| format!("Failed to write credentials file {}", credentials_path.display()))?;
         
-        match std::fs::write(&credentials_path, encoded) {
-            Ok(()) => {
-                eprintln!("🔧 DEBUG: File write successful");
-                tracing::info!("Saved Nostr credentials to file fallback");
-                Ok(())
-            }
-            Err(e) => {
-                eprintln!("🔧 DEBUG: File write failed: {}", e);
-                Err(anyhow::anyhow!("Failed to write credentials file: {}", e))
-            }
-        }
+        tracing::info!("Saved Nostr credentials to encrypted file fallback");
+        Ok(())
     }
 
-    /// Load credentials from file fallback
-    fn load_credentials_from_file(&self) -> Result<Option<NostrCredentials>> {
-        let credentials_path = self.config_dir.join("credentials.enc");
+    /// Load credentials from file fallback. Files from before encryption
+    /// was added are only base64-encoded; they're read as-is and replaced by
+    /// an encrypted file on the next save.
+    fn load_credentials_from_file(&self, account: &str) -> Result<Option<NostrCredentials>> {
+        let credentials_path = self.credentials_path(account);
         
         if !credentials_path.exists() {
             tracing::debug!("No credentials file found");
             return Ok(None);
         }
         
-        let encoded = std::fs::read_to_string(&credentials_path)
+        let content = std::fs::read_to_string(&credentials_path)
             .context("Failed to read credentials file")?;
         
-        let json_bytes = base64::prelude::BASE64_STANDARD.decode(encoded.trim())
-            .context("Failed to decode credentials")?;
-        
-        let json = String::from_utf8(json_bytes)
-            .context("Invalid UTF-8 in credentials file")?;
+        let json = if credentials_file::is_encrypted(&content) {
+            let passphrase = self.credentials_passphrase
+                .as_deref()
+                .ok_or(CredentialsLocked)?;
+            credentials_file::decrypt(&content, passphrase)?
+        } else {
+            tracing::warn!("Credentials file {} is not encrypted; save the credentials with a passphrase to encrypt it", credentials_path.display());
+            let json_bytes = base64::prelude::BASE64_STANDARD.decode(content.trim())
+                .context("Failed to decode credentials")?;
+            String::from_utf8(json_bytes)
+                .context("Invalid UTF-8 in credentials file")?
+        };
         
         let credentials = serde_json::from_str(&json)
             .context("Failed to deserialize credentials")?;
@@ -314,8 +633,8 @@ impl Storage {
     }
 
     /// Delete fallback credentials file
-    fn delete_fallback_credentials(&self) -> Result<()> {
-        let credentials_path = self.config_dir.join("credentials.enc");
+    fn delete_fallback_credentials(&self, account: &str) -> Result<()> {
+        let credentials_path = self.credentials_path(account);
         
         if credentials_path.exists() {
             std::fs::remove_file(&credentials_path)
@@ -346,19 +665,63 @@ impl Storage {
         Ok(())
     }
 
-    /// Import a post from a specific location
-    pub fn import_post(&self, source: &Path) -> Result<BlogPost> {
-        let content = fs::read_to_string(source)
-            .with_context(|| format!("Failed to read post from {}", source.display()))?;
+    /// Export a post as a standalone HTML document
+    pub fn export_post_html(&self, post: &BlogPost, destination: &Path, options: HtmlExportOptions) -> Result<()> {
+        fs::write(destination, render_html(post, options))
+            .with_context(|| format!("Failed to export post to {}", destination.display()))?;
+
+        tracing::info!("Exported post '{}' as HTML to {}", post.title, destination.display());
+        Ok(())
+    }
+
+    /// Export posts as a static site: a page per post, an `index.html`
+    /// listing them and a stylesheet in the colors of `theme_colors`
+    pub fn export_site(&self, posts: &[BlogPost], dir: &Path, theme_colors: &ThemeColors, options: HtmlExportOptions) -> Result<()> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        for post in posts {
+            let path = dir.join(site_page_name(post));
+            fs::write(&path, render_site_page(post, options))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        fs::write(dir.join("index.html"), render_site_index(posts))
+            .with_context(|| format!("Failed to write the index in {}", dir.display()))?;
+        fs::write(dir.join(SITE_STYLESHEET), site_stylesheet(theme_colors))
+            .with_context(|| format!("Failed to write the stylesheet in {}", dir.display()))?;
+
+        tracing::info!("Exported {} posts as a static site to {}", posts.len(), dir.display());
+        Ok(())
+    }
+
+    /// Write a one-off copy of a post to any location. Unlike saving, the
+    /// post's tracked file and the library are left untouched.
+    pub fn save_post_copy(&self, post: &BlogPost, destination: &Path, include_frontmatter: bool) -> Result<()> {
+        let content = if include_frontmatter {
+            post.to_markdown_with_frontmatter()
+        } else {
+            post.content.clone()
+        };
+        fs::write(destination, content)
+            .with_context(|| format!("Failed to save a copy to {}", destination.display()))?;
         
-        let mut post = BlogPost::from_markdown_with_frontmatter(&content, None)?;
+        tracing::info!("Saved a copy of '{}' to {}", post.title, destination.display());
+        Ok(())
+    }
+
+    /// Import a post from a specific location, along with notes on how its
+    /// frontmatter was interpreted
+    pub fn import_post(&self, source: &Path) -> Result<(BlogPost, ImportReport)> {
+        let content = read_text_file(source)?;
+        
+        let (mut post, report) = BlogPost::from_markdown_with_report(&content, None)?;
         
         // Save the imported post to our posts directory
         let file_path = self.save_post(&post)?;
         post.file_path = Some(file_path);
         
         tracing::info!("Imported post '{}' from {}", post.title, source.display());
-        Ok(post)
+        Ok((post, report))
     }
 
     pub fn save_blossom_settings(&self, settings: &BlossomSettings) -> Result<()> {
@@ -373,6 +736,11 @@ impl Storage {
         Ok(())
     }
 
+    /// Whether Blossom settings have been saved on this device
+    pub fn has_blossom_settings(&self) -> bool {
+        self.config_dir.join("blossom_settings.json").exists()
+    }
+
     pub fn load_blossom_settings(&self) -> Result<BlossomSettings> {
         let settings_path = self.config_dir.join("blossom_settings.json");
         
@@ -455,6 +823,36 @@ impl Storage {
         Ok(colors)
     }
 
+    /// Write custom theme colors to a JSON file that can be shared and imported
+    pub fn export_custom_theme(colors: &CustomThemeColors, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(colors)
+            .context("Failed to serialize custom colors")?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write theme to {}", path.display()))?;
+
+        tracing::info!("Exported custom theme to {}", path.display());
+        Ok(())
+    }
+
+    /// Read custom theme colors from a shared theme file. Colors that are
+    /// missing or invalid keep their defaults and are listed in the result.
+    pub fn import_custom_theme(path: &Path) -> Result<(CustomThemeColors, Vec<String>)> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read theme from {}", path.display()))?;
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .context("The theme file isn't valid JSON")?;
+        if !value.is_object() {
+            anyhow::bail!("The theme file doesn't contain any colors");
+        }
+
+        let (colors, fallbacks) = CustomThemeColors::from_json(&value);
+        if !fallbacks.is_empty() {
+            tracing::warn!("Theme {} is missing or has invalid colors, using defaults: {:?}", path.display(), fallbacks);
+        }
+        tracing::info!("Imported custom theme from {}", path.display());
+        Ok((colors, fallbacks))
+    }
+
     /// Save relay settings
     pub fn save_relay_settings(&self, settings: &RelaySettings) -> Result<()> {
         let settings_path = self.config_dir.join("relay_settings.json");
@@ -486,4 +884,477 @@ impl Storage {
         tracing::info!("Loaded relay settings with {} custom relays", settings.custom_relays.len());
         Ok(settings)
     }
+
+    /// Save general application settings
+    pub fn save_app_settings(&self, settings: &AppSettings) -> Result<()> {
+        let settings_path = self.config_dir.join("app_settings.json");
+        let content = serde_json::to_string_pretty(settings)
+            .context("Failed to serialize app settings")?;
+        
+        fs::write(&settings_path, content)
+            .with_context(|| format!("Failed to write app settings to {}", settings_path.display()))?;
+        
+        tracing::info!("Saved app settings");
+        Ok(())
+    }
+
+    /// Load general application settings
+    pub fn load_app_settings(&self) -> Result<AppSettings> {
+        let settings_path = self.config_dir.join("app_settings.json");
+        
+        if !settings_path.exists() {
+            tracing::info!("No app settings file found, using default");
+            return Ok(AppSettings::default());
+        }
+
+        let content = fs::read_to_string(&settings_path)
+            .with_context(|| format!("Failed to read app settings from {}", settings_path.display()))?;
+        
+        let settings: AppSettings = serde_json::from_str(&content)
+            .context("Failed to parse app settings")?;
+        
+        tracing::info!("Loaded app settings");
+        Ok(settings)
+    }
+
+    /// Save the most-recently-used post ids (newest first)
+    pub fn save_recent_posts(&self, recent: &[uuid::Uuid]) -> Result<()> {
+        let recent_path = self.config_dir.join("recent_posts.json");
+        let content = serde_json::to_string_pretty(recent)
+            .context("Failed to serialize recent posts")?;
+        
+        fs::write(&recent_path, content)
+            .with_context(|| format!("Failed to write recent posts to {}", recent_path.display()))?;
+        
+        Ok(())
+    }
+
+    /// Load the most-recently-used post ids (newest first)
+    pub fn load_recent_posts(&self) -> Result<Vec<uuid::Uuid>> {
+        let recent_path = self.config_dir.join("recent_posts.json");
+        
+        if !recent_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&recent_path)
+            .with_context(|| format!("Failed to read recent posts from {}", recent_path.display()))?;
+        
+        serde_json::from_str(&content)
+            .context("Failed to parse recent posts")
+    }
+
+    /// Save daily writing totals
+    pub fn save_writing_stats(&self, stats: &WritingStats) -> Result<()> {
+        let stats_path = self.config_dir.join("writing_stats.json");
+        let content = serde_json::to_string_pretty(stats)
+            .context("Failed to serialize writing stats")?;
+        
+        fs::write(&stats_path, content)
+            .with_context(|| format!("Failed to write writing stats to {}", stats_path.display()))?;
+        
+        Ok(())
+    }
+
+    /// Load daily writing totals
+    pub fn load_writing_stats(&self) -> Result<WritingStats> {
+        let stats_path = self.config_dir.join("writing_stats.json");
+        
+        if !stats_path.exists() {
+            return Ok(WritingStats::default());
+        }
+
+        let content = fs::read_to_string(&stats_path)
+            .with_context(|| format!("Failed to read writing stats from {}", stats_path.display()))?;
+        
+        serde_json::from_str(&content)
+            .context("Failed to parse writing stats")
+    }
+
+    /// Whether the first-run onboarding has been completed or skipped
+    pub fn is_onboarding_complete(&self) -> bool {
+        self.config_dir.join("onboarding_complete").exists()
+    }
+
+    /// Remember that onboarding should not be shown again
+    pub fn mark_onboarding_complete(&self) -> Result<()> {
+        let marker_path = self.config_dir.join("onboarding_complete");
+        fs::write(&marker_path, chrono::Utc::now().to_rfc3339())
+            .with_context(|| format!("Failed to write onboarding marker to {}", marker_path.display()))
+    }
+
+    /// Directory used for crash-recovery snapshots when none is configured
+    pub fn default_recovery_dir(&self) -> PathBuf {
+        self.config_dir.join("recovery")
+    }
+
+    fn recovery_dir(&self) -> PathBuf {
+        self.custom_recovery_dir
+            .clone()
+            .unwrap_or_else(|| self.default_recovery_dir())
+    }
+
+    /// Write crash-recovery snapshots to `dir` instead of the default location.
+    /// Snapshots already in the old directory move along, so a crash before
+    /// the change can still be recovered from.
+    pub fn set_recovery_dir(&mut self, dir: Option<PathBuf>) {
+        let old_dir = self.recovery_dir();
+        self.custom_recovery_dir = dir;
+        let new_dir = self.recovery_dir();
+        tracing::info!("Recovery directory: {}", new_dir.display());
+
+        if old_dir != new_dir {
+            if let Err(e) = Self::move_recovery_snapshots(&old_dir, &new_dir) {
+                tracing::warn!("Failed to move recovery snapshots to {}: {:#}", new_dir.display(), e);
+            }
+        }
+    }
+
+    fn move_recovery_snapshots(from: &Path, to: &Path) -> Result<()> {
+        if !from.is_dir() {
+            return Ok(());
+        }
+        for entry in fs::read_dir(from).context("Failed to read the old recovery directory")? {
+            let path = entry.context("Failed to read directory entry")?.path();
+            if path.extension().map_or(true, |ext| ext != "json") {
+                continue;
+            }
+            fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+            let target = to.join(path.file_name().unwrap_or_default());
+            // Renaming fails across drives; fall back to copying
+            if fs::rename(&path, &target).is_err() {
+                fs::copy(&path, &target).with_context(|| format!("Failed to copy {}", path.display()))?;
+                fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a folder can hold crash-recovery snapshots: it must be writable
+    /// and not the posts or config folder, where snapshots would be mistaken
+    /// for posts or settings
+    pub fn check_recovery_dir(&self, dir: &Path) -> Result<()> {
+        let same_dir = |other: &Path| match (dir.canonicalize(), other.canonicalize()) {
+            (Ok(dir), Ok(other)) => dir == other,
+            _ => dir == other,
+        };
+        if same_dir(&self.posts_dir) {
+            anyhow::bail!("{} is the posts folder", dir.display());
+        }
+        if same_dir(&self.config_dir) {
+            anyhow::bail!("{} is the settings folder", dir.display());
+        }
+        Self::check_dir_writable(dir)
+    }
+
+    /// Make sure `dir` exists and files can be written to it
+    pub fn check_dir_writable(dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let probe_path = dir.join(".blogster-write-test");
+        fs::write(&probe_path, b"")
+            .with_context(|| format!("{} is not writable", dir.display()))?;
+        fs::remove_file(&probe_path)
+            .with_context(|| format!("Failed to clean up {}", probe_path.display()))
+    }
+
+    /// Write a crash-recovery copy of a post that is being edited.
+    ///
+    /// Snapshots live in the config directory, not next to the real posts, and
+    /// are only used to restore unsaved work after a crash.
+    pub fn save_recovery_snapshot(&self, post: &BlogPost) -> Result<()> {
+        // Snapshots are plain JSON, which would leak a private post's content
+        if post.private {
+            return Ok(());
+        }
+        
+        let recovery_dir = self.recovery_dir();
+        fs::create_dir_all(&recovery_dir)
+            .context("Failed to create recovery directory")?;
+
+        let snapshot_path = recovery_dir.join(format!("{}.json", post.id));
+        let content = serde_json::to_string(post)
+            .context("Failed to serialize recovery snapshot")?;
+
+        fs::write(&snapshot_path, content)
+            .with_context(|| format!("Failed to write recovery snapshot to {}", snapshot_path.display()))
+    }
+
+    /// Load all crash-recovery snapshots left behind by a previous session
+    pub fn load_recovery_snapshots(&self) -> Result<Vec<BlogPost>> {
+        let recovery_dir = self.recovery_dir();
+        if !recovery_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&recovery_dir).context("Failed to read recovery directory")? {
+            let path = entry.context("Failed to read directory entry")?.path();
+            if path.extension().map_or(false, |ext| ext == "json") {
+                match fs::read_to_string(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|content| serde_json::from_str(&content).map_err(anyhow::Error::from))
+                {
+                    Ok(post) => snapshots.push(post),
+                    Err(e) => tracing::warn!("Skipping unreadable recovery snapshot {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        Ok(snapshots)
+    }
+
+    /// Remove the crash-recovery snapshot of a post, if there is one
+    pub fn clear_recovery_snapshot(&self, id: uuid::Uuid) -> Result<()> {
+        let snapshot_path = self.recovery_dir().join(format!("{}.json", id));
+        if snapshot_path.exists() {
+            fs::remove_file(&snapshot_path)
+                .with_context(|| format!("Failed to remove recovery snapshot {}", snapshot_path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Folder inside the posts directory that deleted posts are moved to
+const TRASH_DIR: &str = ".trash";
+/// Separates a trashed file's original name from when it was deleted
+const TRASH_STAMP_SEPARATOR: &str = ".deleted-";
+const TRASH_STAMP_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// A deleted post waiting in the trash
+#[derive(Debug, Clone)]
+pub struct TrashedPost {
+    pub post: BlogPost,
+    /// Local time the post was deleted
+    pub deleted_at: chrono::NaiveDateTime,
+    path: PathBuf,
+}
+
+/// Name of a post file in the trash. It keeps the `.md` extension so the
+/// post loads like any other.
+fn trash_file_name(file_name: &str, deleted_at: chrono::NaiveDateTime) -> String {
+    let stem = file_name.strip_suffix(".md").unwrap_or(file_name);
+    format!("{}{}{}.md", stem, TRASH_STAMP_SEPARATOR, deleted_at.format(TRASH_STAMP_FORMAT))
+}
+
+/// Original file name and deletion time of a file in the trash
+fn parse_trash_file_name(name: &str) -> Option<(String, chrono::NaiveDateTime)> {
+    let (original, stamp) = name.strip_suffix(".md")?.rsplit_once(TRASH_STAMP_SEPARATOR)?;
+    let deleted_at = chrono::NaiveDateTime::parse_from_str(stamp, TRASH_STAMP_FORMAT).ok()?;
+    Some((format!("{}.md", original), deleted_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_file_names_round_trip() {
+        let deleted_at = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_hms_opt(13, 45, 9).unwrap();
+        let name = trash_file_name("my_post_1234.md", deleted_at);
+        assert_eq!(name, "my_post_1234.deleted-20240501T134509.md");
+        assert_eq!(parse_trash_file_name(&name), Some(("my_post_1234.md".to_string(), deleted_at)));
+        assert_eq!(parse_trash_file_name("my_post_1234.md"), None);
+    }
+
+    fn trash_test_storage() -> (PathBuf, Storage) {
+        let dir = std::env::temp_dir().join(format!("blogster-trash-{}", uuid::Uuid::new_v4()));
+        let storage = Storage {
+            posts_dir: dir.join("posts"),
+            config_dir: dir.join("config"),
+            custom_recovery_dir: None,
+            private_keys: None,
+            credentials_passphrase: None,
+        };
+        fs::create_dir_all(&storage.posts_dir).unwrap();
+        (dir, storage)
+    }
+
+    #[test]
+    fn test_delete_and_restore_from_trash() {
+        let (dir, storage) = trash_test_storage();
+        let mut post = BlogPost::new()
+            .with_title("Second thoughts".to_string())
+            .with_content("Worth keeping after all".to_string());
+        let file_path = storage.save_post(&post).unwrap();
+        post.file_path = Some(file_path.clone());
+
+        storage.delete_post(&post).unwrap();
+        let on_disk_after_delete = file_path.exists();
+        let trash = storage.list_trash().unwrap();
+        let posts_after_delete = storage.load_all_posts().unwrap();
+
+        let restored = storage.restore_post(post.id).unwrap();
+        let on_disk_after_restore = file_path.exists();
+        let trash_after_restore = storage.list_trash().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!on_disk_after_delete);
+        assert!(posts_after_delete.is_empty());
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].post.id, post.id);
+
+        assert!(on_disk_after_restore);
+        assert!(trash_after_restore.is_empty());
+        assert_eq!(restored.id, post.id);
+        assert_eq!(restored.title, "Second thoughts");
+        assert_eq!(restored.content, "Worth keeping after all");
+        assert_eq!(restored.file_path, Some(file_path));
+    }
+
+    #[test]
+    fn test_purging_the_trash() {
+        let (dir, storage) = trash_test_storage();
+        let posts: Vec<BlogPost> = ["One", "Two", "Three"]
+            .iter()
+            .map(|title| {
+                let mut post = BlogPost::new().with_title(title.to_string());
+                post.file_path = Some(storage.save_post(&post).unwrap());
+                storage.delete_post(&post).unwrap();
+                post
+            })
+            .collect();
+
+        storage.delete_from_trash(posts[0].id).unwrap();
+        let remaining: Vec<uuid::Uuid> = storage.list_trash().unwrap().iter().map(|t| t.post.id).collect();
+        let restore_purged = storage.restore_post(posts[0].id);
+        let emptied = storage.empty_trash().unwrap();
+        let trash_after_empty = storage.list_trash().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(remaining.len(), 2);
+        assert!(!remaining.contains(&posts[0].id));
+        assert!(restore_purged.is_err());
+        assert_eq!(emptied, 2);
+        assert!(trash_after_empty.is_empty());
+    }
+
+    #[test]
+    fn test_read_latin1_file() {
+        let path = std::env::temp_dir().join(format!("blogster-latin1-{}.md", uuid::Uuid::new_v4()));
+        // "# Café crème\n\nUne recette très simple, déjà testée." in ISO-8859-1
+        fs::write(&path, b"# Caf\xE9 cr\xE8me\n\nUne recette tr\xE8s simple, d\xE9j\xE0 test\xE9e.\n").unwrap();
+
+        let text = read_text_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(text.starts_with("# Café crème"));
+        assert!(text.contains("déjà testée"));
+
+        let post = BlogPost::from_markdown_with_frontmatter(&text, None).unwrap();
+        assert_eq!(post.title, "Café crème");
+    }
+
+    #[test]
+    fn test_renamed_post_reloads_once() {
+        let dir = std::env::temp_dir().join(format!("blogster-posts-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let storage = Storage {
+            posts_dir: dir.clone(),
+            config_dir: dir.clone(),
+            custom_recovery_dir: None,
+            private_keys: None,
+            credentials_passphrase: None,
+        };
+
+        let mut post = BlogPost::new().with_title("First title".to_string());
+        post.file_path = Some(storage.save_post(&post).unwrap());
+        post.title = "Second title".to_string();
+        post.file_path = Some(storage.save_post(&post).unwrap());
+
+        let posts = storage.load_all_posts().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].title, "Second title");
+        assert_eq!(posts[0].file_path, post.file_path);
+    }
+
+    #[test]
+    fn test_recovery_dir_must_be_separate() {
+        let dir = std::env::temp_dir().join(format!("blogster-dirs-{}", uuid::Uuid::new_v4()));
+        let storage = Storage {
+            posts_dir: dir.join("posts"),
+            config_dir: dir.join("config"),
+            custom_recovery_dir: None,
+            private_keys: None,
+            credentials_passphrase: None,
+        };
+        fs::create_dir_all(&storage.posts_dir).unwrap();
+        fs::create_dir_all(&storage.config_dir).unwrap();
+
+        assert!(storage.check_recovery_dir(&dir.join("posts")).is_err());
+        assert!(storage.check_recovery_dir(&dir.join("config").join("..").join("config")).is_err());
+        assert!(storage.check_recovery_dir(&dir.join("recovery")).is_ok());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_changing_recovery_dir_keeps_snapshots() {
+        let dir = std::env::temp_dir().join(format!("blogster-move-{}", uuid::Uuid::new_v4()));
+        let mut storage = Storage {
+            posts_dir: dir.join("posts"),
+            config_dir: dir.join("config"),
+            custom_recovery_dir: None,
+            private_keys: None,
+            credentials_passphrase: None,
+        };
+        let post = BlogPost::new().with_title("Unsaved".to_string());
+        storage.save_recovery_snapshot(&post).unwrap();
+
+        storage.set_recovery_dir(Some(dir.join("elsewhere")));
+        let snapshots = storage.load_recovery_snapshots().unwrap();
+        let moved = dir.join("elsewhere").join(format!("{}.json", post.id)).exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(moved);
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].id, post.id);
+    }
+
+    #[test]
+    fn test_check_dir_writable_creates_dir() {
+        let dir = std::env::temp_dir().join(format!("blogster-recovery-{}", uuid::Uuid::new_v4())).join("nested");
+
+        Storage::check_dir_writable(&dir).unwrap();
+        assert!(dir.is_dir());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_custom_theme_import_fills_in_defaults() {
+        let path = std::env::temp_dir().join(format!("blogster-theme-{}.json", uuid::Uuid::new_v4()));
+        let mut colors = CustomThemeColors::default();
+        colors.primary = [1, 2, 3];
+        Storage::export_custom_theme(&colors, &path).unwrap();
+        let (imported, fallbacks) = Storage::import_custom_theme(&path).unwrap();
+        assert_eq!(imported.primary, [1, 2, 3]);
+        assert!(fallbacks.is_empty());
+
+        fs::write(&path, r#"{"primary": [10, 20, 30], "error": [300, 0, 0], "text": [1, 2]}"#).unwrap();
+        let (imported, fallbacks) = Storage::import_custom_theme(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let defaults = CustomThemeColors::default();
+        assert_eq!(imported.primary, [10, 20, 30]);
+        assert_eq!(imported.error, defaults.error);
+        assert_eq!(imported.text, defaults.text);
+        assert_eq!(fallbacks.len(), 11);
+        assert!(fallbacks.contains(&"error".to_string()));
+    }
+
+    #[test]
+    fn test_account_labels() {
+        assert_eq!(Storage::credentials_key(DEFAULT_ACCOUNT), "nostr_credentials");
+        assert_eq!(Storage::credentials_key("work"), "nostr_credentials::work");
+
+        assert!(Storage::validate_account_label("work").is_ok());
+        assert!(Storage::validate_account_label("side_project-2").is_ok());
+        assert!(Storage::validate_account_label("").is_err());
+        assert!(Storage::validate_account_label("../work").is_err());
+        assert!(Storage::validate_account_label("my blog").is_err());
+    }
 }
@@ -0,0 +1,178 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    Comma,
+    Tab,
+    Semicolon,
+}
+
+impl Delimiter {
+    pub fn as_char(&self) -> char {
+        match self {
+            Delimiter::Comma => ',',
+            Delimiter::Tab => '\t',
+            Delimiter::Semicolon => ';',
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Delimiter::Comma => "Comma (CSV)",
+            Delimiter::Tab => "Tab (TSV)",
+            Delimiter::Semicolon => "Semicolon",
+        }
+    }
+
+    pub fn all() -> Vec<Delimiter> {
+        vec![Delimiter::Tab, Delimiter::Comma, Delimiter::Semicolon]
+    }
+}
+
+/// Guess the delimiter of pasted tabular data.
+///
+/// Returns `None` unless there are at least two rows that all split into the
+/// same number (> 1) of columns, so ordinary prose isn't mistaken for a table.
+pub fn detect_delimiter(text: &str) -> Option<Delimiter> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() < 2 {
+        return None;
+    }
+
+    Delimiter::all().into_iter().find(|delimiter| {
+        let counts: Vec<usize> = lines
+            .iter()
+            .map(|line| split_row(line, *delimiter).len())
+            .collect();
+        counts[0] > 1 && counts.iter().all(|c| *c == counts[0])
+    })
+}
+
+/// Split text into rows of cells, honouring CSV-style double quotes
+pub fn parse_rows(text: &str, delimiter: Delimiter) -> Vec<Vec<String>> {
+    text.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| split_row(line, delimiter))
+        .collect()
+}
+
+fn split_row(line: &str, delimiter: Delimiter) -> Vec<String> {
+    let separator = delimiter.as_char();
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => {
+                cells.push(current.trim().to_string());
+                current.clear();
+            }
+            c => current.push(c),
+        }
+    }
+    cells.push(current.trim().to_string());
+
+    cells
+}
+
+/// Guess whether the first row is a header: it is if none of its cells are
+/// numbers while at least one later cell is, or if the data has no numbers at all
+pub fn looks_like_header(rows: &[Vec<String>]) -> bool {
+    let is_number = |cell: &String| cell.replace(',', "").parse::<f64>().is_ok();
+
+    match rows.split_first() {
+        Some((first, rest)) if !rest.is_empty() => {
+            let first_has_numbers = first.iter().any(is_number);
+            let rest_has_numbers = rest.iter().flatten().any(is_number);
+            !first_has_numbers && (rest_has_numbers || first.iter().all(|c| !c.is_empty()))
+        }
+        _ => false,
+    }
+}
+
+/// Render rows as a markdown pipe table
+pub fn to_markdown_table(rows: &[Vec<String>], has_header: bool) -> String {
+    let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let format_row = |cells: &[String]| {
+        let padded: Vec<String> = (0..columns)
+            .map(|i| cells.get(i).map(|c| c.replace('|', "\\|")).unwrap_or_default())
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+
+    let (header, body) = if has_header {
+        (rows[0].clone(), &rows[1..])
+    } else {
+        ((1..=columns).map(|i| format!("Column {}", i)).collect(), rows)
+    };
+
+    let mut table = Vec::with_capacity(body.len() + 2);
+    table.push(format_row(&header));
+    table.push(format!("|{}", " --- |".repeat(columns)));
+    for row in body {
+        table.push(format_row(row));
+    }
+
+    table.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_to_table() {
+        let csv = "Name,Sats\nAlice,1000\n\"Bob, Jr.\",21\n";
+        assert_eq!(detect_delimiter(csv), Some(Delimiter::Comma));
+
+        let rows = parse_rows(csv, Delimiter::Comma);
+        assert_eq!(rows[2], vec!["Bob, Jr.".to_string(), "21".to_string()]);
+        assert!(looks_like_header(&rows));
+
+        assert_eq!(
+            to_markdown_table(&rows, true),
+            "| Name | Sats |\n| --- | --- |\n| Alice | 1000 |\n| Bob, Jr. | 21 |"
+        );
+    }
+
+    #[test]
+    fn test_tsv_to_table() {
+        let tsv = "1\t2\t3\n4\t5\t6";
+        assert_eq!(detect_delimiter(tsv), Some(Delimiter::Tab));
+
+        let rows = parse_rows(tsv, Delimiter::Tab);
+        assert!(!looks_like_header(&rows));
+
+        assert_eq!(
+            to_markdown_table(&rows, false),
+            "| Column 1 | Column 2 | Column 3 |\n| --- | --- | --- |\n| 1 | 2 | 3 |\n| 4 | 5 | 6 |"
+        );
+    }
+
+    #[test]
+    fn test_prose_is_not_a_table() {
+        assert_eq!(detect_delimiter("Just one line, with a comma"), None);
+        assert_eq!(detect_delimiter("First line, one comma\nsecond line, two, commas"), None);
+    }
+
+    #[test]
+    fn test_pipes_are_escaped_and_rows_padded() {
+        let rows = vec![
+            vec!["a|b".to_string(), "c".to_string()],
+            vec!["d".to_string()],
+        ];
+        assert_eq!(
+            to_markdown_table(&rows, true),
+            "| a\\|b | c |\n| --- | --- |\n| d |  |"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use egui::{Color32, Visuals};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Theme {
@@ -13,6 +14,8 @@ pub enum Theme {
     TokyoNight,
     OneDark,
     MaterialDark,
+    /// Catppuccin Mocha or Latte, following the OS light/dark preference
+    System,
     Custom,
 }
 
@@ -53,6 +56,27 @@ impl Default for CustomThemeColors {
 }
 
 impl CustomThemeColors {
+    /// Read colors from a theme file's JSON, keeping the default for every
+    /// color that is missing or isn't three 0-255 channels. Returns the names
+    /// of the colors that fell back to their defaults.
+    pub fn from_json(value: &serde_json::Value) -> (Self, Vec<String>) {
+        let mut colors = serde_json::to_value(Self::default()).unwrap_or_default();
+        let mut fallbacks = Vec::new();
+        if let Some(fields) = colors.as_object_mut() {
+            for (name, color) in fields.iter_mut() {
+                let channels = value.get(name).and_then(|v| v.as_array()).filter(|channels| {
+                    channels.len() == 3 && channels.iter().all(|c| c.as_u64().is_some_and(|c| c <= 255))
+                });
+                match channels {
+                    Some(channels) => *color = serde_json::Value::Array(channels.clone()),
+                    None => fallbacks.push(name.clone()),
+                }
+            }
+        }
+        let colors = serde_json::from_value(colors).unwrap_or_default();
+        (colors, fallbacks)
+    }
+
     pub fn to_theme_colors(&self) -> ThemeColors {
         ThemeColors {
             primary: Color32::from_rgb(self.primary[0], self.primary[1], self.primary[2]),
@@ -124,11 +148,14 @@ impl Theme {
             Theme::TokyoNight => "Tokyo Night",
             Theme::OneDark => "One Dark",
             Theme::MaterialDark => "Material Dark",
+            Theme::System => "Follow System",
             Theme::Custom => "Custom",
         }
     }
 
-    pub fn colors(&self, custom_colors: Option<&CustomThemeColors>) -> ThemeColors {
+    /// Colors of the theme. `system_dark` is the OS preference the app last
+    /// read, used by `Theme::System`.
+    pub fn colors(&self, custom_colors: Option<&CustomThemeColors>, system_dark: bool) -> ThemeColors {
         match self {
             Theme::Custom => {
                 if let Some(custom) = custom_colors {
@@ -137,11 +164,11 @@ impl Theme {
                     CustomThemeColors::default().to_theme_colors()
                 }
             }
-            _ => self.predefined_colors(),
+            _ => self.predefined_colors(system_dark),
         }
     }
 
-    fn predefined_colors(&self) -> ThemeColors {
+    fn predefined_colors(&self, system_dark: bool) -> ThemeColors {
         match self {
             Theme::CatppuccinMocha => ThemeColors {
                 primary: Color32::from_rgb(137, 180, 250),      // Blue
@@ -283,6 +310,7 @@ impl Theme {
                 surface: Color32::from_rgb(46, 60, 67),         // Surface
                 border: Color32::from_rgb(84, 110, 122),        // Border
             },
+            Theme::System => Theme::for_system(system_dark).predefined_colors(system_dark),
             Theme::Custom => {
                 // This should never be called since Custom uses custom_colors
                 CustomThemeColors::default().to_theme_colors()
@@ -290,6 +318,23 @@ impl Theme {
         }
     }
 
+    /// The theme `Theme::System` stands for while the OS is in dark or light mode
+    pub fn for_system(dark: bool) -> Theme {
+        if dark {
+            Theme::CatppuccinMocha
+        } else {
+            Theme::CatppuccinLatte
+        }
+    }
+
+    /// The theme actually shown, given whether the OS is in dark mode
+    pub fn resolve(self, system_dark: bool) -> Theme {
+        match self {
+            Theme::System => Theme::for_system(system_dark),
+            theme => theme,
+        }
+    }
+
     pub fn all_themes() -> Vec<Theme> {
         vec![
             Theme::CatppuccinMocha,
@@ -302,11 +347,12 @@ impl Theme {
             Theme::TokyoNight,
             Theme::OneDark,
             Theme::MaterialDark,
+            Theme::System,
             Theme::Custom,
         ]
     }
 
-    pub fn apply(&self, ctx: &egui::Context) {
+    pub fn apply(&self, ctx: &egui::Context, system_dark: bool) {
         match self {
             Theme::CatppuccinMocha => apply_catppuccin_mocha(ctx),
             Theme::CatppuccinLatte => apply_catppuccin_latte(ctx),
@@ -318,9 +364,25 @@ impl Theme {
             Theme::TokyoNight => apply_tokyo_night(ctx),
             Theme::OneDark => apply_one_dark(ctx),
             Theme::MaterialDark => apply_material_dark(ctx),
+            Theme::System => Theme::for_system(system_dark).apply(ctx, system_dark),
             Theme::Custom => apply_catppuccin_mocha(ctx), // Default base for custom themes
         }
     }
+
+    /// Apply the theme, building `Theme::Custom` from the user's colors
+    pub fn apply_with(&self, ctx: &egui::Context, custom_colors: &CustomThemeColors, system_dark: bool) {
+        match self {
+            Theme::Custom => apply_custom_theme(ctx, custom_colors),
+            _ => self.apply(ctx, system_dark),
+        }
+    }
+}
+
+/// Whether the OS is set to dark mode. Reading the preference can block for
+/// a moment, so the app checks it off the UI thread. An OS without a
+/// preference counts as dark, the app's default look.
+pub fn system_prefers_dark() -> bool {
+    !matches!(dark_light::detect(), dark_light::Mode::Light)
 }
 
 pub struct CatppuccinMocha;
@@ -425,6 +487,30 @@ pub fn apply_catppuccin_latte(ctx: &egui::Context) {
     ctx.set_visuals(visuals);
 }
 
+// Custom: backgrounds and accents from the user's chosen colors
+pub fn apply_custom_theme(ctx: &egui::Context, custom_colors: &CustomThemeColors) {
+    let colors = custom_colors.to_theme_colors();
+    let [r, g, b] = custom_colors.background;
+    let is_light = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000 > 128;
+    let mut visuals = if is_light { Visuals::light() } else { Visuals::dark() };
+    
+    visuals.window_fill = colors.background;
+    visuals.panel_fill = colors.background;
+    visuals.faint_bg_color = colors.surface;
+    visuals.extreme_bg_color = colors.surface;
+    visuals.window_stroke.color = colors.border;
+    
+    // Don't override text color to allow RichText colors to work
+    visuals.widgets.active.bg_fill = colors.primary;
+    visuals.selection.bg_fill = colors.primary.linear_multiply(0.3);
+    visuals.selection.stroke.color = colors.primary;
+    visuals.hyperlink_color = colors.primary;
+    visuals.warn_fg_color = colors.warning;
+    visuals.error_fg_color = colors.error;
+    
+    ctx.set_visuals(visuals);
+}
+
 // Gruvbox Dark
 pub fn apply_gruvbox_dark(ctx: &egui::Context) {
     let mut visuals = Visuals::dark();
@@ -553,6 +639,20 @@ pub fn apply_material_dark(ctx: &egui::Context) {
     ctx.set_visuals(visuals);
 }
 
+/// Make panel and window backgrounds translucent. Call after `Theme::apply`,
+/// since it scales the colors the theme just set.
+pub fn apply_window_opacity(ctx: &egui::Context, opacity: f32) {
+    if opacity >= 1.0 {
+        return;
+    }
+    ctx.style_mut(|style| {
+        let visuals = &mut style.visuals;
+        visuals.panel_fill = visuals.panel_fill.gamma_multiply(opacity);
+        visuals.window_fill = visuals.window_fill.gamma_multiply(opacity);
+        visuals.extreme_bg_color = visuals.extreme_bg_color.gamma_multiply(opacity);
+    });
+}
+
 pub fn get_accent_color(index: usize) -> Color32 {
     let colors = [
         CatppuccinMocha::BLUE,
@@ -566,3 +666,15 @@ pub fn get_accent_color(index: usize) -> Color32 {
     ];
     colors[index % colors.len()]
 }
+
+/// Color for a tag: the user's choice if they picked one, otherwise an accent
+/// color derived from the tag name so it stays the same between runs
+pub fn tag_color(tag: &str, tag_colors: &HashMap<String, [u8; 3]>) -> Color32 {
+    let key = tag.to_lowercase();
+    if let Some([r, g, b]) = tag_colors.get(&key) {
+        return Color32::from_rgb(*r, *g, *b);
+    }
+
+    let hash = key.bytes().fold(0usize, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as usize));
+    get_accent_color(hash)
+}
@@ -0,0 +1,124 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Words written per day, counted from the growth of posts between saves.
+///
+/// Deleting text never subtracts from a day; only words added count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WritingStats {
+    /// Words added on each day
+    pub daily_words: BTreeMap<NaiveDate, usize>,
+    /// Word count of each post at its last save
+    pub last_counts: HashMap<uuid::Uuid, usize>,
+    /// Daily word goal; 0 disables it
+    pub daily_goal: usize,
+}
+
+impl Default for WritingStats {
+    fn default() -> Self {
+        Self {
+            daily_words: BTreeMap::new(),
+            last_counts: HashMap::new(),
+            daily_goal: 500,
+        }
+    }
+}
+
+impl WritingStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the current word count of posts we haven't seen before as their
+    /// baseline, so existing writing isn't counted as written today
+    pub fn seed(&mut self, posts: impl IntoIterator<Item = (uuid::Uuid, usize)>) {
+        for (id, words) in posts {
+            self.last_counts.entry(id).or_insert(words);
+        }
+    }
+
+    /// Record a save of a post and return the words added since its last save
+    pub fn record_save(&mut self, post_id: uuid::Uuid, word_count: usize, date: NaiveDate) -> usize {
+        let previous = self.last_counts.insert(post_id, word_count).unwrap_or(0);
+        let added = word_count.saturating_sub(previous);
+        if added > 0 {
+            *self.daily_words.entry(date).or_insert(0) += added;
+        }
+        added
+    }
+
+    pub fn words_on(&self, date: NaiveDate) -> usize {
+        self.daily_words.get(&date).copied().unwrap_or(0)
+    }
+
+    /// Consecutive days with writing up to today. A streak isn't broken until
+    /// a whole day passes without writing, so yesterday still counts.
+    pub fn streak(&self, today: NaiveDate) -> usize {
+        let mut day = if self.words_on(today) > 0 {
+            today
+        } else {
+            match today.pred_opt() {
+                Some(yesterday) => yesterday,
+                None => return 0,
+            }
+        };
+
+        let mut streak = 0;
+        while self.words_on(day) > 0 {
+            streak += 1;
+            match day.pred_opt() {
+                Some(previous) => day = previous,
+                None => break,
+            }
+        }
+        streak
+    }
+
+    /// Forget all history but keep the goal and the post baselines
+    pub fn reset(&mut self) {
+        self.daily_words.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_counts_only_added_words() {
+        let mut stats = WritingStats::new();
+        let existing = uuid::Uuid::new_v4();
+        stats.seed([(existing, 1000)]);
+
+        assert_eq!(stats.record_save(existing, 1100, day(1)), 100);
+        assert_eq!(stats.record_save(existing, 1050, day(1)), 0);
+        assert_eq!(stats.record_save(existing, 1080, day(1)), 30);
+
+        let new_post = uuid::Uuid::new_v4();
+        assert_eq!(stats.record_save(new_post, 20, day(1)), 20);
+        assert_eq!(stats.words_on(day(1)), 150);
+    }
+
+    #[test]
+    fn test_streak() {
+        let mut stats = WritingStats::new();
+        let post = uuid::Uuid::new_v4();
+        stats.record_save(post, 10, day(2));
+        stats.record_save(post, 20, day(3));
+        stats.record_save(post, 30, day(4));
+
+        assert_eq!(stats.streak(day(4)), 3);
+        // Nothing written yet today, but the streak is still alive
+        assert_eq!(stats.streak(day(5)), 3);
+        assert_eq!(stats.streak(day(6)), 0);
+
+        stats.reset();
+        assert_eq!(stats.streak(day(4)), 0);
+    }
+}